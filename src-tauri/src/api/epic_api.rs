@@ -1,26 +1,30 @@
 use crate::utils::AppError;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct EpicProfile {
-    pub accountId: String,
-    pub displayName: String,
-    pub preferredLanguage: String,
-    pub linkedAccounts: Vec<LinkedAccount>,
+    pub account_id: String,
+    pub display_name: String,
+    pub preferred_language: String,
+    pub linked_accounts: Vec<LinkedAccount>,
     pub country: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct LinkedAccount {
-    pub identityProviderId: String,
-    pub displayName: String,
+    pub identity_provider_id: String,
+    pub display_name: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct EpicFriend {
-    pub accountId: String,
-    pub displayName: String,
+    pub account_id: String,
+    pub display_name: String,
     pub status: String,
     pub presence: Option<EpicPresence>,
 }
@@ -31,9 +35,38 @@ pub struct EpicPresence {
     pub game: Option<String>,
 }
 
+// Entrée de la bibliothèque du compte (jeux possédés, installés ou non)
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EpicLibraryItem {
+    pub namespace: String,
+    pub catalog_item_id: String,
+    #[serde(default)]
+    pub app_name: Option<String>,
+    #[serde(default)]
+    pub sandbox_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EpicLibraryPage {
+    #[serde(default)]
+    records: Vec<EpicLibraryItem>,
+    #[serde(default)]
+    response_metadata: Option<EpicResponseMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EpicResponseMetadata {
+    next_cursor: Option<String>,
+}
+
 pub struct EpicApi {
     client: Client,
     access_token: String,
+    library_url: String,
+    catalog_url: String,
 }
 
 impl EpicApi {
@@ -41,13 +74,89 @@ impl EpicApi {
         Self {
             client: Client::new(),
             access_token,
+            library_url: "https://library-service.live.use1a.on.epicgames.com".to_string(),
+            catalog_url: "https://catalog-public-service-prod06.ol.epicgames.com".to_string(),
+        }
+    }
+
+    pub fn with_base_urls(mut self, library_url: &str, catalog_url: &str) -> Self {
+        self.library_url = library_url.trim_end_matches('/').to_string();
+        self.catalog_url = catalog_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    // Toutes les pages de la bibliothèque du compte
+    pub async fn get_library_items(&self) -> Result<Vec<EpicLibraryItem>, AppError> {
+        let mut items = Vec::new();
+        let mut cursor: Option<String> = None;
+
+        loop {
+            let mut request = self
+                .client
+                .get(format!("{}/library/api/public/items", self.library_url))
+                .query(&[("includeMetadata", "true")])
+                .bearer_auth(&self.access_token);
+            if let Some(cursor) = &cursor {
+                request = request.query(&[("cursor", cursor)]);
+            }
+
+            let page: EpicLibraryPage = request
+                .send()
+                .await
+                .and_then(|response| response.error_for_status())
+                .map_err(|e| AppError {
+                    message: format!("Failed to fetch Epic library: {}", e),
+                })?
+                .json()
+                .await
+                .map_err(|e| AppError {
+                    message: format!("Failed to parse Epic library: {}", e),
+                })?;
+
+            let count = page.records.len();
+            items.extend(page.records);
+            cursor = page.response_metadata.and_then(|metadata| metadata.next_cursor);
+            if cursor.is_none() || count == 0 {
+                break;
+            }
         }
+
+        Ok(items)
+    }
+
+    // Titres des éléments du catalogue d'un namespace, indexés par catalogItemId
+    pub async fn get_catalog_items(
+        &self,
+        namespace: &str,
+        item_ids: &[&str],
+    ) -> Result<HashMap<String, serde_json::Value>, AppError> {
+        let mut query: Vec<(&str, &str)> = item_ids.iter().map(|id| ("id", *id)).collect();
+        query.extend([("country", "US"), ("locale", "en-US")]);
+
+        self.client
+            .get(format!(
+                "{}/catalog/api/shared/namespace/{}/bulk/items",
+                self.catalog_url, namespace
+            ))
+            .query(&query)
+            .bearer_auth(&self.access_token)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| AppError {
+                message: format!("Failed to fetch Epic catalog: {}", e),
+            })?
+            .json()
+            .await
+            .map_err(|e| AppError {
+                message: format!("Failed to parse Epic catalog: {}", e),
+            })
     }
 
-    pub async fn get_profile(&self, accountId: String) -> Result<EpicProfile, AppError> {
+    pub async fn get_profile(&self, account_id: String) -> Result<EpicProfile, AppError> {
         let url = format!(
             "https://api.epicgames.dev/epic/id/v2/accounts?accountId={}",
-            accountId
+            account_id
         );
         let response = self
             .client
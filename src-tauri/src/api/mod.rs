@@ -1,7 +1,9 @@
 mod battlenet_api;
 mod epic_api;
 mod steam_api;
+#[cfg(test)]
+pub mod stub_server;
 
 pub use battlenet_api::BattleNetApi;
 pub use epic_api::EpicApi;
-pub use steam_api::SteamApi;
+pub use steam_api::{SteamApi, SteamNewsItem, SteamOwnedGame};
@@ -1,7 +1,6 @@
 use crate::utils::AppError;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SteamProfile {
@@ -22,16 +21,55 @@ pub struct SteamFriend {
 }
 
 #[derive(Debug, Serialize, Deserialize)]
-pub struct GameMediaInfo {
-    pub thumbnail: Option<String>,
-    pub cover: Option<String>,
-    pub screenshots: Vec<String>,
-    pub description: Option<String>,
+pub struct SteamOwnedGame {
+    pub appid: u32,
+    #[serde(default)]
+    pub name: Option<String>,
+    // Minutes
+    #[serde(default)]
+    pub playtime_forever: i64,
+    #[serde(default)]
+    pub rtime_last_played: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SteamNewsItem {
+    pub gid: String,
+    pub title: String,
+    pub url: String,
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub contents: Option<String>,
+    #[serde(default)]
+    pub feedlabel: Option<String>,
+    pub date: i64,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SteamPublishedFile {
+    pub publishedfileid: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub preview_url: Option<String>,
+    #[serde(default)]
+    pub time_updated: Option<i64>,
+}
+
+// Réponse de GetNewsForApp ; None côté appelant quand le serveur répond 304
+#[derive(Debug)]
+pub struct SteamNewsPage {
+    pub items: Vec<SteamNewsItem>,
+    pub etag: Option<String>,
 }
 
 pub struct SteamApi {
     client: Client,
     api_key: String,
+    api_url: String,
     store_api_url: String,
 }
 
@@ -40,14 +78,21 @@ impl SteamApi {
         Self {
             client: Client::new(),
             api_key,
+            api_url: "https://api.steampowered.com".to_string(),
             store_api_url: "https://store.steampowered.com/api".to_string(),
         }
     }
 
+    pub fn with_base_urls(mut self, api_url: &str, store_api_url: &str) -> Self {
+        self.api_url = api_url.trim_end_matches('/').to_string();
+        self.store_api_url = store_api_url.trim_end_matches('/').to_string();
+        self
+    }
+
     pub async fn get_profile(&self, steam_id: &str) -> Result<SteamProfile, AppError> {
         let url = format!(
-            "https://api.steampowered.com/ISteamUser/GetPlayerSummaries/v2/?key={}&steamids={}",
-            self.api_key, steam_id
+            "{}/ISteamUser/GetPlayerSummaries/v2/?key={}&steamids={}",
+            self.api_url, self.api_key, steam_id
         );
 
         let response = self.client.get(&url).send().await.map_err(|e| AppError {
@@ -65,8 +110,8 @@ impl SteamApi {
 
     pub async fn get_friends(&self, steam_id: &str) -> Result<Vec<SteamFriend>, AppError> {
         let url = format!(
-            "https://api.steampowered.com/ISteamUser/GetFriendList/v1/?key={}&steamid={}&relationship=friend",
-            self.api_key, steam_id
+            "{}/ISteamUser/GetFriendList/v1/?key={}&steamid={}&relationship=friend",
+            self.api_url, self.api_key, steam_id
         );
 
         let response = self.client.get(&url).send().await.map_err(|e| AppError {
@@ -82,52 +127,118 @@ impl SteamApi {
         })
     }
 
-    pub async fn get_app_details(&self, app_id: &str) -> Result<GameMediaInfo, AppError> {
-        let url = format!("{}/appdetails?appids={}", self.store_api_url, app_id);
+    pub async fn get_owned_games(&self, steam_id: &str) -> Result<Vec<SteamOwnedGame>, AppError> {
+        let url = format!(
+            "{}/IPlayerService/GetOwnedGames/v1/?key={}&steamid={}&include_appinfo=1&include_played_free_games=1&format=json",
+            self.api_url, self.api_key, steam_id
+        );
+
+        let response = self.client.get(&url).send().await.map_err(|e| AppError {
+            message: format!("Failed to fetch Steam owned games: {}", e),
+        })?;
+
+        let data: serde_json::Value = response.json().await.map_err(|e| AppError {
+            message: format!("Failed to parse Steam owned games: {}", e),
+        })?;
 
-        #[derive(Deserialize)]
-        struct SteamAppDetails {
-            data: Option<SteamAppData>,
+        // Profil privé : la réponse ne contient pas de liste
+        if data["response"]["games"].is_null() {
+            return Ok(Vec::new());
         }
 
-        #[derive(Deserialize)]
-        struct SteamAppData {
-            name: String,
-            header_image: String,
-            background: Option<String>,
-            description: Option<String>,
-            screenshots: Option<Vec<Screenshot>>,
+        serde_json::from_value(data["response"]["games"].clone()).map_err(|e| AppError {
+            message: format!("Failed to parse owned games data: {}", e),
+        })
+    }
+
+    // ISteamNews ne demande pas de clé ; l'ETag précédent évite de retélécharger
+    // un flux inchangé
+    pub async fn get_news_for_app(
+        &self,
+        app_id: &str,
+        count: u32,
+        etag: Option<&str>,
+    ) -> Result<Option<SteamNewsPage>, AppError> {
+        let url = format!(
+            "{}/ISteamNews/GetNewsForApp/v2/?appid={}&count={}&maxlength=0&format=json",
+            self.api_url, app_id, count
+        );
+
+        let mut request = self.client.get(&url);
+        if let Some(etag) = etag {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
         }
+        let response = request.send().await.map_err(|e| AppError {
+            message: format!("Failed to fetch Steam news: {}", e),
+        })?;
 
-        #[derive(Deserialize)]
-        struct Screenshot {
-            path_full: String,
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(None);
+        }
+        if !response.status().is_success() {
+            return Err(AppError {
+                message: format!("Failed to fetch Steam news: HTTP {}", response.status()),
+            });
         }
 
-        let response = self.client.get(&url).send().await.map_err(|e| AppError {
-            message: format!("Failed to fetch Steam app details: {}", e),
+        let etag = response
+            .headers()
+            .get(reqwest::header::ETAG)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let data: serde_json::Value = response.json().await.map_err(|e| AppError {
+            message: format!("Failed to parse Steam news: {}", e),
         })?;
 
-        let mut details: HashMap<String, SteamAppDetails> =
-            response.json().await.map_err(|e| AppError {
-                message: format!("Failed to parse Steam response: {}", e),
-            })?;
+        // Application sans actualités : pas de liste
+        let items = if data["appnews"]["newsitems"].is_null() {
+            Vec::new()
+        } else {
+            serde_json::from_value(data["appnews"]["newsitems"].clone()).map_err(|e| AppError {
+                message: format!("Failed to parse Steam news items: {}", e),
+            })?
+        };
 
-        let app_details = details
-            .remove(app_id)
-            .and_then(|d| d.data)
-            .ok_or_else(|| AppError {
-                message: format!("No data found for app_id: {}", app_id),
+        Ok(Some(SteamNewsPage { items, etag }))
+    }
+
+    // Détails publics d'éléments du Workshop (sans clé)
+    pub async fn get_published_file_details(
+        &self,
+        item_ids: &[&str],
+    ) -> Result<Vec<SteamPublishedFile>, AppError> {
+        let url = format!(
+            "{}/ISteamRemoteStorage/GetPublishedFileDetails/v1/",
+            self.api_url
+        );
+
+        let mut form = vec![("itemcount".to_string(), item_ids.len().to_string())];
+        for (index, item_id) in item_ids.iter().enumerate() {
+            form.push((format!("publishedfileids[{}]", index), item_id.to_string()));
+        }
+
+        let response = self
+            .client
+            .post(&url)
+            .form(&form)
+            .send()
+            .await
+            .map_err(|e| AppError {
+                message: format!("Failed to fetch workshop details: {}", e),
             })?;
 
-        Ok(GameMediaInfo {
-            thumbnail: Some(app_details.header_image),
-            cover: app_details.background,
-            screenshots: app_details
-                .screenshots
-                .map(|s| s.iter().map(|ss| ss.path_full.clone()).collect())
-                .unwrap_or_default(),
-            description: app_details.description,
+        let data: serde_json::Value = response.json().await.map_err(|e| AppError {
+            message: format!("Failed to parse workshop details: {}", e),
+        })?;
+
+        if data["response"]["publishedfiledetails"].is_null() {
+            return Ok(Vec::new());
+        }
+
+        serde_json::from_value(data["response"]["publishedfiledetails"].clone()).map_err(|e| {
+            AppError {
+                message: format!("Failed to parse workshop details data: {}", e),
+            }
         })
     }
 }
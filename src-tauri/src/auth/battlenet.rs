@@ -18,32 +18,6 @@ pub struct BattleNetAuth {
     redirect_uri: String,
 }
 
-fn deserialize_number_to_string<'de, D>(deserializer: D) -> Result<String, D::Error>
-where
-    D: serde::Deserializer<'de>,
-{
-    #[derive(Deserialize)]
-    #[serde(untagged)]
-    enum StringOrNumber {
-        String(String),
-        Number(i64),
-    }
-
-    Ok(match StringOrNumber::deserialize(deserializer)? {
-        StringOrNumber::String(s) => s,
-        StringOrNumber::Number(n) => n.to_string(),
-    })
-}
-
-#[derive(Debug, Deserialize, Serialize)]
-pub struct BattleNetProfile {
-    #[serde(deserialize_with = "deserialize_number_to_string")]
-    pub id: String,
-    pub battletag: String,
-    #[serde(rename = "sub")]
-    pub account_id: String,
-}
-
 impl BattleNetAuth {
     pub fn new(client_id: String, client_secret: String) -> Self {
         Self {
@@ -93,25 +67,4 @@ impl BattleNetAuth {
             message: format!("Failed to parse token response: {}", e),
         })
     }
-
-    pub async fn get_profile(&self, token: &str) -> Result<BattleNetProfile, AppError> {
-        let response = self
-            .client
-            .get("https://oauth.battle.net/oauth/userinfo")
-            .bearer_auth(token)
-            .send()
-            .await
-            .map_err(|e| AppError {
-                message: format!("Failed to fetch profile: {}", e),
-            })?;
-
-        // Log de la réponse brute pour debug
-        let text = response.text().await.map_err(|e| AppError {
-            message: format!("Failed to get response text: {}", e),
-        })?;
-
-        serde_json::from_str(&text).map_err(|e| AppError {
-            message: format!("Failed to parse profile: {}", e),
-        })
-    }
 }
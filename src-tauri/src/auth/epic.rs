@@ -2,7 +2,6 @@ use crate::utils::AppError;
 use base64::{engine::general_purpose::STANDARD, Engine as _};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use serde_json::json;
 use url::Url;
 
 #[derive(Deserialize, Serialize, Debug)]
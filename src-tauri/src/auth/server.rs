@@ -81,37 +81,35 @@ impl AuthServer {
     pub fn start(&self) -> std::io::Result<()> {
         let listener = TcpListener::bind(format!("127.0.0.1:{}", self.port))?;
 
-        for stream in listener.incoming() {
-            if let Ok(mut stream) = stream {
-                let mut reader = BufReader::new(&stream);
-                let mut request_line = String::new();
-                reader.read_line(&mut request_line)?;
+        for mut stream in listener.incoming().flatten() {
+            let mut reader = BufReader::new(&stream);
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line)?;
 
-                if request_line.starts_with("GET") {
-                    let redirect_url =
-                        if let Some(identity) = self.extract_steam_identity(&request_line) {
-                            format!("glaunch://auth/steam/callback?openid.identity={}", identity)
-                        } else if let Some(code) = self.extract_epic_code(&request_line) {
-                            format!("glaunch://auth/epic/callback?code={}", code)
-                        } else if let Some(code) = self.extract_battlenet_code(&request_line) {
-                            format!("glaunch://auth/battlenet/callback?code={}", code)
-                        } else {
-                            continue;
-                        };
+            if request_line.starts_with("GET") {
+                let redirect_url =
+                    if let Some(identity) = self.extract_steam_identity(&request_line) {
+                        format!("glaunch://auth/steam/callback?openid.identity={}", identity)
+                    } else if let Some(code) = self.extract_epic_code(&request_line) {
+                        format!("glaunch://auth/epic/callback?code={}", code)
+                    } else if let Some(code) = self.extract_battlenet_code(&request_line) {
+                        format!("glaunch://auth/battlenet/callback?code={}", code)
+                    } else {
+                        continue;
+                    };
 
-                    let html = Self::get_redirect_html(&redirect_url);
-                    let response = format!(
-                        "HTTP/1.1 200 OK\r\n\
-                       Content-Type: text/html; charset=utf-8\r\n\
-                       Content-Length: {}\r\n\
-                       \r\n\
-                       {}",
-                        html.len(),
-                        html
-                    );
+                let html = Self::get_redirect_html(&redirect_url);
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\n\
+                   Content-Type: text/html; charset=utf-8\r\n\
+                   Content-Length: {}\r\n\
+                   \r\n\
+                   {}",
+                    html.len(),
+                    html
+                );
 
-                    let _ = stream.write_all(response.as_bytes());
-                }
+                let _ = stream.write_all(response.as_bytes());
             }
         }
         Ok(())
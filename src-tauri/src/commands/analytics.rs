@@ -0,0 +1,116 @@
+use crate::models::{
+    PlayStreaks, PlaytimeBucket, PlaytimeDimension, PlaytimeGranularity, PlaytimeHeatmap,
+    PlaytimeShare, TopGame, YearReview,
+};
+use crate::services::AnalyticsZone;
+use crate::AppState;
+
+// Les bornes sont des timestamps Unix ; utc_offset_minutes remplace le fuseau du système
+
+#[tauri::command]
+pub async fn get_playtime_totals(
+    state: tauri::State<'_, AppState>,
+    granularity: PlaytimeGranularity,
+    from: Option<i64>,
+    to: Option<i64>,
+    utc_offset_minutes: Option<i32>,
+) -> Result<Vec<PlaytimeBucket>, String> {
+    state
+        .game_manager
+        .analytics_service
+        .playtime_totals(
+            granularity,
+            from,
+            to,
+            AnalyticsZone::from_offset_minutes(utc_offset_minutes),
+        )
+        .await
+        .map_err(|e| e.message)
+}
+
+#[tauri::command]
+pub async fn get_play_streaks(
+    state: tauri::State<'_, AppState>,
+    utc_offset_minutes: Option<i32>,
+) -> Result<PlayStreaks, String> {
+    state
+        .game_manager
+        .analytics_service
+        .play_streaks(AnalyticsZone::from_offset_minutes(utc_offset_minutes))
+        .await
+        .map_err(|e| e.message)
+}
+
+#[tauri::command]
+pub async fn get_playtime_breakdown(
+    state: tauri::State<'_, AppState>,
+    dimension: PlaytimeDimension,
+    from: Option<i64>,
+    to: Option<i64>,
+) -> Result<Vec<PlaytimeShare>, String> {
+    state
+        .game_manager
+        .analytics_service
+        .playtime_breakdown(dimension, from, to)
+        .await
+        .map_err(|e| e.message)
+}
+
+#[tauri::command]
+pub async fn get_playtime_heatmap(
+    state: tauri::State<'_, AppState>,
+    from: Option<i64>,
+    to: Option<i64>,
+    utc_offset_minutes: Option<i32>,
+) -> Result<PlaytimeHeatmap, String> {
+    state
+        .game_manager
+        .analytics_service
+        .playtime_heatmap(from, to, AnalyticsZone::from_offset_minutes(utc_offset_minutes))
+        .await
+        .map_err(|e| e.message)
+}
+
+#[tauri::command]
+pub async fn get_top_games(
+    state: tauri::State<'_, AppState>,
+    from: Option<i64>,
+    to: Option<i64>,
+    limit: Option<usize>,
+) -> Result<Vec<TopGame>, String> {
+    state
+        .game_manager
+        .analytics_service
+        .top_games(from, to, limit.unwrap_or(10))
+        .await
+        .map_err(|e| e.message)
+}
+
+#[tauri::command]
+pub async fn year_review(
+    state: tauri::State<'_, AppState>,
+    year: i32,
+    utc_offset_minutes: Option<i32>,
+) -> Result<YearReview, String> {
+    state
+        .game_manager
+        .year_review_service
+        .year_review(year, AnalyticsZone::from_offset_minutes(utc_offset_minutes))
+        .await
+        .map_err(|e| e.message)
+}
+
+// Page HTML autonome du bilan de l'année, prête à être enregistrée ou partagée
+#[tauri::command]
+pub async fn year_review_html(
+    state: tauri::State<'_, AppState>,
+    year: i32,
+    utc_offset_minutes: Option<i32>,
+) -> Result<String, String> {
+    let service = &state.game_manager.year_review_service;
+    let review = service
+        .year_review(year, AnalyticsZone::from_offset_minutes(utc_offset_minutes))
+        .await
+        .map_err(|e| e.message)?;
+    service.render_html(&review).await.map_err(|e| e.message)
+}
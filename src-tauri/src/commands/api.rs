@@ -1,7 +1,5 @@
 use crate::api::{BattleNetApi, EpicApi, SteamApi};
-use crate::auth::epic::EpicTokenResponse;
 use crate::auth::EpicAuth;
-use crate::utils::AppError;
 
 /// Commandes API Steam
 #[tauri::command]
@@ -38,9 +36,9 @@ pub async fn exchange_epic_code(code: String) -> Result<String, String> {
 }
 
 #[tauri::command]
-pub async fn get_epic_profile(token: String, accountId: String) -> Result<String, String> {
+pub async fn get_epic_profile(token: String, account_id: String) -> Result<String, String> {
     let api = EpicApi::new(token);
-    let profile = api.get_profile(accountId).await.map_err(|e| e.message)?;
+    let profile = api.get_profile(account_id).await.map_err(|e| e.message)?;
 
     serde_json::to_string(&profile).map_err(|e| e.to_string())
 }
@@ -1,21 +1,15 @@
-use crate::auth::epic::EpicTokenResponse;
 use crate::auth::{BattleNetAuth, EpicAuth, SteamAuth};
-use crate::utils::AppError;
 
 /// Commandes d'authentification Steam
 #[tauri::command]
 pub async fn auth_steam() -> Result<String, String> {
-    let api_key = std::env::var("STEAM_API_KEY").map_err(|_| "STEAM_API_KEY not set")?;
-
-    let auth = SteamAuth::new(api_key);
+    let auth = SteamAuth::new();
     Ok(auth.get_auth_url())
 }
 
 #[tauri::command]
 pub async fn auth_steam_callback(identity: String) -> Result<String, String> {
-    let api_key = std::env::var("STEAM_API_KEY").map_err(|_| "STEAM_API_KEY not set")?;
-
-    let auth = SteamAuth::new(api_key);
+    let auth = SteamAuth::new();
     let steam_id = auth
         .extract_steam_id(&identity)
         .ok_or("Invalid Steam identity")?;
@@ -1,8 +1,11 @@
 use crate::models::CustomGameConfig;
 use crate::models::Game;
+use crate::models::{
+    GameAction, GameUpdate, OwnedLibrarySync, PlaytimeImportSource, PlaytimeImportSummary,
+    ScreenshotIndexSummary, UserScreenshot, WorkshopItem,
+};
 use crate::services::IgdbSearchResult;
 use crate::AppState;
-use std::path::PathBuf;
 
 #[tauri::command]
 pub async fn scan_games(
@@ -49,6 +52,15 @@ pub async fn launch_game(game_id: String, state: tauri::State<'_, AppState>) ->
         .map_err(|e| e.message)
 }
 
+#[tauri::command]
+pub async fn stop_game(game_id: String, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    state
+        .game_manager
+        .stop_game(&game_id)
+        .await
+        .map_err(|e| e.message)
+}
+
 #[tauri::command]
 pub async fn get_active_game(state: tauri::State<'_, AppState>) -> Result<Option<String>, String> {
     Ok(state
@@ -81,6 +93,12 @@ pub async fn update_game_with_igdb(
         .metadata_service
         .update_with_igdb_id(&game_id, igdb_id)
         .await
+        .map_err(|e| e.message)?;
+
+    state
+        .game_manager
+        .refresh_works()
+        .await
         .map_err(|e| e.message)
 }
 
@@ -114,3 +132,159 @@ pub async fn delete_game(state: tauri::State<'_, AppState>, game_id: String) ->
         .await
         .map_err(|e| e.message)
 }
+
+#[tauri::command]
+pub async fn set_game_completed(
+    state: tauri::State<'_, AppState>,
+    game_id: String,
+    completed: bool,
+) -> Result<(), String> {
+    state
+        .game_manager
+        .set_game_completed(&game_id, completed)
+        .await
+        .map_err(|e| e.message)
+}
+
+#[tauri::command]
+pub async fn import_steam_playtime(
+    state: tauri::State<'_, AppState>,
+    source: PlaytimeImportSource,
+    steam_id: Option<String>,
+) -> Result<PlaytimeImportSummary, String> {
+    let api_key = std::env::var("STEAM_API_KEY").ok();
+
+    state
+        .game_manager
+        .import_steam_playtime(source, api_key, steam_id)
+        .await
+        .map_err(|e| e.message)
+}
+
+// Sans jeton Epic ni clé STEAM_API_KEY, seule la bibliothèque GOG Galaxy locale est lue
+#[tauri::command]
+pub async fn sync_owned_library(
+    state: tauri::State<'_, AppState>,
+    steam_id: Option<String>,
+    epic_token: Option<String>,
+) -> Result<Vec<OwnedLibrarySync>, String> {
+    let steam_api_key = std::env::var("STEAM_API_KEY").ok();
+
+    state
+        .game_manager
+        .sync_owned_library(steam_api_key, steam_id, epic_token)
+        .await
+        .map_err(|e| e.message)
+}
+
+#[tauri::command]
+pub async fn get_game_actions(
+    state: tauri::State<'_, AppState>,
+    game_id: String,
+) -> Result<Vec<GameAction>, String> {
+    state
+        .game_manager
+        .game_actions(&game_id)
+        .await
+        .map_err(|e| e.message)
+}
+
+#[tauri::command]
+pub async fn run_game_action(
+    state: tauri::State<'_, AppState>,
+    game_id: String,
+    action: GameAction,
+) -> Result<(), String> {
+    state
+        .game_manager
+        .run_game_action(&game_id, action)
+        .await
+        .map_err(|e| e.message)
+}
+
+#[tauri::command]
+pub async fn get_recent_updates(
+    state: tauri::State<'_, AppState>,
+    since: Option<i64>,
+    limit: Option<i64>,
+) -> Result<Vec<GameUpdate>, String> {
+    state
+        .game_manager
+        .recent_updates(since, limit.unwrap_or(20))
+        .await
+        .map_err(|e| e.message)
+}
+
+#[tauri::command]
+pub async fn get_game_versions(
+    state: tauri::State<'_, AppState>,
+    game_id: String,
+) -> Result<Vec<GameUpdate>, String> {
+    state
+        .game_manager
+        .game_versions(&game_id)
+        .await
+        .map_err(|e| e.message)
+}
+
+#[tauri::command]
+pub async fn get_workshop_items(
+    state: tauri::State<'_, AppState>,
+    game_id: String,
+    with_details: Option<bool>,
+) -> Result<Vec<WorkshopItem>, String> {
+    state
+        .game_manager
+        .workshop_items(&game_id, with_details.unwrap_or(false))
+        .await
+        .map_err(|e| e.message)
+}
+
+#[tauri::command]
+pub async fn open_workshop_item(
+    state: tauri::State<'_, AppState>,
+    game_id: String,
+    item_id: String,
+) -> Result<(), String> {
+    state
+        .game_manager
+        .open_workshop_item(&game_id, &item_id)
+        .await
+        .map_err(|e| e.message)
+}
+
+#[tauri::command]
+pub async fn remove_workshop_item(
+    state: tauri::State<'_, AppState>,
+    game_id: String,
+    item_id: String,
+) -> Result<(), String> {
+    state
+        .game_manager
+        .remove_workshop_item(&game_id, &item_id)
+        .await
+        .map_err(|e| e.message)
+}
+
+#[tauri::command]
+pub async fn get_game_gallery(
+    state: tauri::State<'_, AppState>,
+    game_id: String,
+) -> Result<Vec<UserScreenshot>, String> {
+    state
+        .game_manager
+        .game_gallery(&game_id)
+        .await
+        .map_err(|e| e.message)
+}
+
+#[tauri::command]
+pub async fn reindex_screenshots(
+    state: tauri::State<'_, AppState>,
+) -> Result<ScreenshotIndexSummary, String> {
+    state
+        .game_manager
+        .index_screenshots()
+        .await
+        .map_err(|e| e.message)
+}
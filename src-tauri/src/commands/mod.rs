@@ -1,11 +1,23 @@
+mod analytics;
 mod api;
 mod auth;
 mod games;
+mod monitor;
+mod news;
 mod overlay;
+mod runners;
+mod sessions;
 mod settings;
+mod works;
 
+pub use analytics::*;
 pub use api::*;
 pub use auth::*;
 pub use games::*;
+pub use monitor::*;
+pub use news::*;
 pub use overlay::*;
+pub use runners::*;
+pub use sessions::*;
 pub use settings::*;
+pub use works::*;
@@ -0,0 +1,45 @@
+use crate::models::{ActiveSessionStatus, CrashRecord, MonitorStatus};
+use crate::AppState;
+
+#[tauri::command]
+pub async fn monitor_status(state: tauri::State<'_, AppState>) -> Result<MonitorStatus, String> {
+    Ok(state.game_monitor.status())
+}
+
+#[tauri::command]
+pub async fn list_active_sessions(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<ActiveSessionStatus>, String> {
+    Ok(state.game_monitor.list_active_sessions())
+}
+
+#[tauri::command]
+pub async fn stop_session(state: tauri::State<'_, AppState>, game_id: String) -> Result<(), String> {
+    if state.game_monitor.stop_tracking(&game_id).await {
+        Ok(())
+    } else {
+        Err(format!("No active session for game {}", game_id))
+    }
+}
+
+#[tauri::command]
+pub async fn focus_game(state: tauri::State<'_, AppState>, game_id: String) -> Result<(), String> {
+    if state.game_monitor.focus_game(&game_id) {
+        Ok(())
+    } else {
+        Err(format!("Failed to focus game {}", game_id))
+    }
+}
+
+#[tauri::command]
+pub async fn get_recent_crashes(
+    state: tauri::State<'_, AppState>,
+    since: Option<i64>,
+    limit: Option<i64>,
+) -> Result<Vec<CrashRecord>, String> {
+    state
+        .game_manager
+        .get_recent_crashes(since, limit.unwrap_or(20))
+        .await
+        .map_err(|e| e.message)
+}
@@ -0,0 +1,59 @@
+use crate::models::{GameNews, NewsRefreshSummary};
+use crate::AppState;
+
+#[tauri::command]
+pub async fn refresh_news(
+    state: tauri::State<'_, AppState>,
+    force: Option<bool>,
+) -> Result<NewsRefreshSummary, String> {
+    state
+        .game_manager
+        .refresh_news(force.unwrap_or(false))
+        .await
+        .map_err(|e| e.message)
+}
+
+#[tauri::command]
+pub async fn get_game_news(
+    state: tauri::State<'_, AppState>,
+    game_id: Option<String>,
+    unread_only: Option<bool>,
+    limit: Option<i64>,
+) -> Result<Vec<GameNews>, String> {
+    state
+        .game_manager
+        .game_news(
+            game_id.as_deref(),
+            unread_only.unwrap_or(false),
+            limit.unwrap_or(50),
+        )
+        .await
+        .map_err(|e| e.message)
+}
+
+#[tauri::command]
+pub async fn get_news_since_last_played(
+    state: tauri::State<'_, AppState>,
+    game_id: Option<String>,
+    limit: Option<i64>,
+) -> Result<Vec<GameNews>, String> {
+    state
+        .game_manager
+        .news_since_last_played(game_id.as_deref(), limit.unwrap_or(50))
+        .await
+        .map_err(|e| e.message)
+}
+
+#[tauri::command]
+pub async fn mark_news_read(
+    state: tauri::State<'_, AppState>,
+    game_id: String,
+    gids: Option<Vec<String>>,
+    read: Option<bool>,
+) -> Result<u64, String> {
+    state
+        .game_manager
+        .mark_news_read(&game_id, gids.as_deref(), read.unwrap_or(true))
+        .await
+        .map_err(|e| e.message)
+}
@@ -1,5 +1,5 @@
-use crate::OverlayState;
-use tauri::{Emitter, Manager};
+use crate::{AppState, OverlayState};
+use tauri::Manager;
 
 #[tauri::command]
 pub async fn toggle_overlay(state: tauri::State<'_, OverlayState>) -> Result<(), String> {
@@ -20,3 +20,21 @@ pub async fn toggle_overlay(state: tauri::State<'_, OverlayState>) -> Result<(),
     }
     Ok(())
 }
+
+#[tauri::command]
+pub async fn set_overlay_target(
+    app_state: tauri::State<'_, AppState>,
+    state: tauri::State<'_, OverlayState>,
+    game_id: String,
+) -> Result<(), String> {
+    let mut overlay_guard = state.overlay.lock().map_err(|e| e.to_string())?;
+    let overlay = overlay_guard
+        .as_mut()
+        .ok_or_else(|| "Overlay not initialized".to_string())?;
+
+    if overlay.set_target_game(&app_state.game_monitor, &game_id) {
+        Ok(())
+    } else {
+        Err(format!("No active session for game {}", game_id))
+    }
+}
@@ -0,0 +1,61 @@
+use crate::models::{RunnerConfig, RunnerKind, WineInstall};
+use crate::AppState;
+use std::collections::HashMap;
+
+#[tauri::command]
+pub async fn list_wine_builds(state: tauri::State<'_, AppState>) -> Result<Vec<WineInstall>, String> {
+    Ok(state.game_manager.list_wine_builds())
+}
+
+#[tauri::command]
+pub async fn get_game_runner(
+    state: tauri::State<'_, AppState>,
+    game_id: String,
+) -> Result<RunnerConfig, String> {
+    state
+        .game_manager
+        .get_game_runner(&game_id)
+        .await
+        .map_err(|e| e.message)
+}
+
+#[tauri::command]
+pub async fn set_game_runner(
+    state: tauri::State<'_, AppState>,
+    game_id: String,
+    config: RunnerConfig,
+) -> Result<(), String> {
+    state
+        .game_manager
+        .set_game_runner(&game_id, config)
+        .await
+        .map_err(|e| e.message)
+}
+
+#[tauri::command]
+pub async fn create_wine_prefix(
+    state: tauri::State<'_, AppState>,
+    game_id: String,
+    kind: RunnerKind,
+    runner_path: Option<String>,
+    prefix_path: Option<String>,
+) -> Result<RunnerConfig, String> {
+    state
+        .game_manager
+        .create_wine_prefix(&game_id, kind, runner_path, prefix_path)
+        .await
+        .map_err(|e| e.message)
+}
+
+#[tauri::command]
+pub async fn set_dll_overrides(
+    state: tauri::State<'_, AppState>,
+    game_id: String,
+    overrides: HashMap<String, String>,
+) -> Result<RunnerConfig, String> {
+    state
+        .game_manager
+        .set_dll_overrides(&game_id, overrides)
+        .await
+        .map_err(|e| e.message)
+}
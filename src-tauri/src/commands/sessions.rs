@@ -0,0 +1,94 @@
+use crate::models::{SessionExportFormat, SessionPage, SessionRecord};
+use crate::services::AnalyticsZone;
+use crate::AppState;
+use std::path::Path;
+
+#[tauri::command]
+pub async fn list_sessions(
+    state: tauri::State<'_, AppState>,
+    game_id: Option<String>,
+    page: Option<u32>,
+    page_size: Option<u32>,
+) -> Result<SessionPage, String> {
+    state
+        .game_manager
+        .list_sessions(game_id.as_deref(), page.unwrap_or(0), page_size.unwrap_or(50))
+        .await
+        .map_err(|e| e.message)
+}
+
+#[tauri::command]
+pub async fn add_session(
+    state: tauri::State<'_, AppState>,
+    game_id: String,
+    start_time: i64,
+    end_time: i64,
+) -> Result<SessionRecord, String> {
+    state
+        .game_manager
+        .add_session(&game_id, start_time, end_time)
+        .await
+        .map_err(|e| e.message)
+}
+
+#[tauri::command]
+pub async fn update_session(
+    state: tauri::State<'_, AppState>,
+    session_id: i64,
+    start_time: i64,
+    end_time: i64,
+) -> Result<SessionRecord, String> {
+    state
+        .game_manager
+        .update_session(session_id, start_time, end_time)
+        .await
+        .map_err(|e| e.message)
+}
+
+#[tauri::command]
+pub async fn merge_sessions(
+    state: tauri::State<'_, AppState>,
+    session_ids: Vec<i64>,
+) -> Result<SessionRecord, String> {
+    state
+        .game_manager
+        .merge_sessions(&session_ids)
+        .await
+        .map_err(|e| e.message)
+}
+
+#[tauri::command]
+pub async fn delete_session(state: tauri::State<'_, AppState>, session_id: i64) -> Result<(), String> {
+    state
+        .game_manager
+        .delete_session(session_id)
+        .await
+        .map_err(|e| e.message)
+}
+
+// Les bornes sont des timestamps Unix ; utc_offset_minutes remplace le fuseau du
+// système pour les colonnes en heure locale
+#[tauri::command]
+pub async fn export_sessions(
+    state: tauri::State<'_, AppState>,
+    path: String,
+    format: SessionExportFormat,
+    from: Option<i64>,
+    to: Option<i64>,
+    game_ids: Option<Vec<String>>,
+    utc_offset_minutes: Option<i32>,
+) -> Result<usize, String> {
+    state
+        .game_manager
+        .export_service
+        .export_sessions(
+            Path::new(&path),
+            format,
+            from,
+            to,
+            game_ids.as_deref(),
+            AnalyticsZone::from_offset_minutes(utc_offset_minutes),
+        )
+        .await
+        .map_err(|e| e.message)
+}
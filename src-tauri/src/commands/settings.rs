@@ -2,7 +2,8 @@ use tauri_plugin_updater::UpdaterExt;
 use tauri::AppHandle;
 use crate::models::UpdateStatus;
 use crate::utils::settings::{AppSettings, SettingsManager};
-use tauri::Emitter;
+use crate::AppState;
+use tauri::{Emitter, Manager};
 use std::path::PathBuf;
 use serde::Serialize;
 
@@ -24,7 +25,13 @@ pub async fn save_app_settings(app_handle: tauri::AppHandle, settings: AppSettin
     let mut settings_manager = SettingsManager::new(&app_handle).map_err(|e| e.message)?;
     settings_manager
         .update_settings(settings)
-        .map_err(|e| e.message)
+        .map_err(|e| e.message)?;
+
+    if let Some(state) = app_handle.try_state::<AppState>() {
+        state.game_monitor.apply_settings(settings_manager.get_settings());
+        state.game_manager.apply_settings(settings_manager.get_settings());
+    }
+    Ok(())
 }
 
 #[tauri::command]
@@ -0,0 +1,59 @@
+use crate::models::Work;
+use crate::AppState;
+
+#[tauri::command]
+pub async fn get_works(state: tauri::State<'_, AppState>) -> Result<Vec<Work>, String> {
+    state.game_manager.works().await.map_err(|e| e.message)
+}
+
+// work_id absent : le jeu est sorti de son œuvre actuelle
+#[tauri::command]
+pub async fn set_game_work(
+    state: tauri::State<'_, AppState>,
+    game_id: String,
+    work_id: Option<String>,
+) -> Result<String, String> {
+    state
+        .game_manager
+        .assign_game_work(&game_id, work_id.as_deref())
+        .await
+        .map_err(|e| e.message)
+}
+
+#[tauri::command]
+pub async fn reset_game_work(
+    state: tauri::State<'_, AppState>,
+    game_id: String,
+) -> Result<(), String> {
+    state
+        .game_manager
+        .reset_game_work(&game_id)
+        .await
+        .map_err(|e| e.message)
+}
+
+#[tauri::command]
+pub async fn set_preferred_installation(
+    state: tauri::State<'_, AppState>,
+    work_id: String,
+    game_id: String,
+) -> Result<(), String> {
+    state
+        .game_manager
+        .set_preferred_installation(&work_id, &game_id)
+        .await
+        .map_err(|e| e.message)
+}
+
+#[tauri::command]
+pub async fn launch_work(
+    state: tauri::State<'_, AppState>,
+    work_id: String,
+    game_id: Option<String>,
+) -> Result<(), String> {
+    state
+        .game_manager
+        .launch_work(&work_id, game_id.as_deref())
+        .await
+        .map_err(|e| e.message)
+}
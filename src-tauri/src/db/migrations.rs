@@ -16,6 +16,66 @@ const MIGRATIONS: &[Migration] = &[
         description: "Initial schema",
         up_sql: include_str!("./migrations/001_initial_schema.sql"),
     },
+    Migration {
+        version: 2,
+        description: "Game runners",
+        up_sql: include_str!("./migrations/002_game_runners.sql"),
+    },
+    Migration {
+        version: 3,
+        description: "Session activity",
+        up_sql: include_str!("./migrations/003_session_activity.sql"),
+    },
+    Migration {
+        version: 4,
+        description: "Session end reason",
+        up_sql: include_str!("./migrations/004_session_end_reason.sql"),
+    },
+    Migration {
+        version: 5,
+        description: "Session exit status",
+        up_sql: include_str!("./migrations/005_session_exit_status.sql"),
+    },
+    Migration {
+        version: 6,
+        description: "Game completion",
+        up_sql: include_str!("./migrations/006_game_completion.sql"),
+    },
+    Migration {
+        version: 7,
+        description: "Imported playtime",
+        up_sql: include_str!("./migrations/007_imported_playtime.sql"),
+    },
+    Migration {
+        version: 8,
+        description: "Owned games",
+        up_sql: include_str!("./migrations/008_owned_games.sql"),
+    },
+    Migration {
+        version: 9,
+        description: "Game versions",
+        up_sql: include_str!("./migrations/009_game_versions.sql"),
+    },
+    Migration {
+        version: 10,
+        description: "Game news",
+        up_sql: include_str!("./migrations/010_game_news.sql"),
+    },
+    Migration {
+        version: 11,
+        description: "User screenshots",
+        up_sql: include_str!("./migrations/011_user_screenshots.sql"),
+    },
+    Migration {
+        version: 12,
+        description: "Game add-ons",
+        up_sql: include_str!("./migrations/012_game_addons.sql"),
+    },
+    Migration {
+        version: 13,
+        description: "Works",
+        up_sql: include_str!("./migrations/013_works.sql"),
+    },
     // Les futures migrations seront ajoutées ici
 ];
 
@@ -0,0 +1,10 @@
+-- Configuration du runner (natif, Wine, Proton) par jeu
+CREATE TABLE IF NOT EXISTS game_runners (
+    game_id TEXT PRIMARY KEY,
+    runner TEXT NOT NULL DEFAULT 'Native',
+    wine_path TEXT,
+    prefix_path TEXT,
+    dll_overrides TEXT NOT NULL DEFAULT '{}',
+    updated_at INTEGER NOT NULL,
+    FOREIGN KEY(game_id) REFERENCES games(id) ON DELETE CASCADE
+);
@@ -0,0 +1,3 @@
+-- Répartition du temps de session entre jeu actif et inactivité (AFK, jeu minimisé)
+ALTER TABLE game_sessions ADD COLUMN active_seconds INTEGER;
+ALTER TABLE game_sessions ADD COLUMN idle_seconds INTEGER;
@@ -0,0 +1,2 @@
+-- Raison de fin de session (sortie du jeu, arrêt par l'utilisateur, fermeture de glaunch...)
+ALTER TABLE game_sessions ADD COLUMN end_reason TEXT;
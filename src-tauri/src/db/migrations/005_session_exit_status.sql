@@ -0,0 +1,6 @@
+-- Statut de sortie des sessions et compteur de crashs par jeu
+ALTER TABLE game_sessions ADD COLUMN exit_code INTEGER;
+ALTER TABLE game_sessions ADD COLUMN exit_signal INTEGER;
+ALTER TABLE game_sessions ADD COLUMN killed INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE game_sessions ADD COLUMN crashed INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE game_stats ADD COLUMN crash_count INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,2 @@
+-- Date à laquelle l'utilisateur a marqué le jeu comme terminé
+ALTER TABLE game_stats ADD COLUMN completed_at INTEGER;
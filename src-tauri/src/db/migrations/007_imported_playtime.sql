@@ -0,0 +1,5 @@
+-- Temps de jeu importé d'une autre source (Steam), conservé à part des sessions
+ALTER TABLE game_stats ADD COLUMN imported_playtime INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE game_stats ADD COLUMN imported_last_played INTEGER;
+ALTER TABLE game_stats ADD COLUMN imported_source TEXT;
+ALTER TABLE game_stats ADD COLUMN imported_at INTEGER;
@@ -0,0 +1,2 @@
+-- Jeux possédés sur un compte (Steam, Epic, GOG) mais non installés
+ALTER TABLE games ADD COLUMN installed INTEGER NOT NULL DEFAULT 1;
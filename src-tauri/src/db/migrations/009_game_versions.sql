@@ -0,0 +1,14 @@
+-- Historique des versions observées lors des scans (buildid Steam, AppVersion Epic)
+CREATE TABLE IF NOT EXISTS game_versions (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    game_id TEXT NOT NULL,
+    version TEXT NOT NULL,
+    previous_version TEXT,
+    first_seen INTEGER NOT NULL,
+    install_size INTEGER NOT NULL DEFAULT 0,
+    size_delta INTEGER,
+    UNIQUE(game_id, version),
+    FOREIGN KEY(game_id) REFERENCES games(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_game_versions_first_seen ON game_versions(first_seen);
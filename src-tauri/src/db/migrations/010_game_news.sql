@@ -0,0 +1,26 @@
+-- Actualités des jeux (ISteamNews), avec leur état lu / non lu
+CREATE TABLE IF NOT EXISTS game_news (
+    game_id TEXT NOT NULL,
+    gid TEXT NOT NULL,
+    title TEXT NOT NULL,
+    url TEXT NOT NULL,
+    author TEXT,
+    contents TEXT,
+    feed_label TEXT,
+    date INTEGER NOT NULL,
+    tags TEXT NOT NULL DEFAULT '[]',
+    is_read INTEGER NOT NULL DEFAULT 0,
+    fetched_at INTEGER NOT NULL,
+    PRIMARY KEY(game_id, gid),
+    FOREIGN KEY(game_id) REFERENCES games(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_game_news_date ON game_news(game_id, date);
+
+-- Dernier rafraîchissement du flux de chaque jeu (TTL et ETag)
+CREATE TABLE IF NOT EXISTS news_fetch_state (
+    game_id TEXT PRIMARY KEY,
+    etag TEXT,
+    fetched_at INTEGER NOT NULL,
+    FOREIGN KEY(game_id) REFERENCES games(id) ON DELETE CASCADE
+);
@@ -0,0 +1,15 @@
+-- Captures d'écran locales des joueurs, distinctes des screenshots promotionnels
+CREATE TABLE IF NOT EXISTS user_screenshots (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    game_id TEXT NOT NULL,
+    path TEXT NOT NULL UNIQUE,
+    taken_at INTEGER NOT NULL,
+    width INTEGER,
+    height INTEGER,
+    caption TEXT,
+    source TEXT NOT NULL,
+    indexed_at INTEGER NOT NULL,
+    FOREIGN KEY(game_id) REFERENCES games(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_screenshots_game ON user_screenshots(game_id, taken_at);
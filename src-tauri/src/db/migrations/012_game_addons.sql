@@ -0,0 +1,5 @@
+-- DLC, bandes-son, outils et démos rattachés à leur jeu de base
+ALTER TABLE games ADD COLUMN kind TEXT NOT NULL DEFAULT 'Base';
+ALTER TABLE games ADD COLUMN parent_game_id TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_games_parent ON games(parent_game_id);
@@ -0,0 +1,28 @@
+-- Œuvres : installations d'un même jeu sur plusieurs boutiques
+CREATE TABLE IF NOT EXISTS works (
+    id TEXT PRIMARY KEY,
+    title TEXT NOT NULL,
+    normalized_title TEXT NOT NULL,
+    release_year INTEGER,
+    igdb_id INTEGER,
+    preferred_game_id TEXT,
+    created_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL
+);
+
+-- work_locked : rattachement choisi par l'utilisateur, jamais remis en cause
+ALTER TABLE games ADD COLUMN work_id TEXT REFERENCES works(id) ON DELETE SET NULL;
+ALTER TABLE games ADD COLUMN work_locked INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS idx_games_work ON games(work_id);
+
+-- Jeu IGDB retenu pour les métadonnées
+ALTER TABLE game_metadata ADD COLUMN igdb_id INTEGER;
+
+-- Identifiants de boutique connus d'IGDB (external_games)
+CREATE TABLE IF NOT EXISTS igdb_external_games (
+    platform TEXT NOT NULL,
+    platform_id TEXT NOT NULL,
+    igdb_id INTEGER NOT NULL,
+    PRIMARY KEY(platform, platform_id)
+);
@@ -1,18 +1,20 @@
 // src/db/mod.rs
 use crate::utils::AppError;
 use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
-use std::path::PathBuf;
 use std::time::Duration;
 mod migrations;
 mod queries;
-mod schema;
 use migrations::run_migrations;
 use crate::log_debug;
 use crate::log_error;
 use tauri::AppHandle;
 use crate::utils::AppPaths;
 
-pub use queries::{GameQueries, MetadataQueries, SessionQueries};
+pub use queries::{
+    AnalyticsQueries, GameQueries, MetadataQueries, NewsQueries, RunnerQueries, ScreenshotQueries,
+    SessionQueries, VersionQueries, WorkQueries,
+};
+pub use queries::nest_addons;
 
 pub struct Database {
     pool: SqlitePool,
@@ -62,6 +64,25 @@ impl Database {
         }
     }
 
+    // Base éphémère pour les tests, avec le schéma à jour
+    #[cfg(test)]
+    pub async fn in_memory() -> Result<Self, AppError> {
+        // Une seule connexion : chaque connexion à :memory: ouvre une base distincte
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect("sqlite::memory:")
+            .await
+            .map_err(|e| AppError {
+                message: format!("Failed to connect to database: {}", e),
+            })?;
+
+        let db = Self { pool };
+        db.initialize().await?;
+        Ok(db)
+    }
+
     pub async fn initialize(&self) -> Result<(), AppError> {
         log_debug!("Initializing database...");
         run_migrations(&self.pool).await?;
@@ -69,15 +90,39 @@ impl Database {
         Ok(())
     }
 
-    pub fn games(&self) -> GameQueries {
+    pub fn games(&self) -> GameQueries<'_> {
         GameQueries::new(&self.pool)
     }
 
-    pub fn sessions(&self) -> SessionQueries {
+    pub fn sessions(&self) -> SessionQueries<'_> {
         SessionQueries::new(&self.pool)
     }
 
-    pub fn metadata(&self) -> MetadataQueries {
+    pub fn metadata(&self) -> MetadataQueries<'_> {
         MetadataQueries::new(&self.pool)
     }
+
+    pub fn runners(&self) -> RunnerQueries<'_> {
+        RunnerQueries::new(&self.pool)
+    }
+
+    pub fn analytics(&self) -> AnalyticsQueries<'_> {
+        AnalyticsQueries::new(&self.pool)
+    }
+
+    pub fn versions(&self) -> VersionQueries<'_> {
+        VersionQueries::new(&self.pool)
+    }
+
+    pub fn news(&self) -> NewsQueries<'_> {
+        NewsQueries::new(&self.pool)
+    }
+
+    pub fn screenshots(&self) -> ScreenshotQueries<'_> {
+        ScreenshotQueries::new(&self.pool)
+    }
+
+    pub fn works(&self) -> WorkQueries<'_> {
+        WorkQueries::new(&self.pool)
+    }
 }
@@ -1,11 +1,21 @@
 // src/db/queries.rs
-use crate::models::{Game, GameInstallation, GameMedia, GameMetadata, GameStats, Platform};
+use crate::models::{
+    Game, GameInstallation, GameKind, GameMedia, GameMetadata, GameStats, Platform, PlaytimeMode,
+    CrashRecord, GameFacets, GameNews, GameUpdate, ScreenshotSource, UserScreenshot, RunnerConfig, RunnerKind, SessionEndReason, SessionOutcome, SessionPage,
+    SessionRecord, SessionTimes, WorkRecord,
+};
+use crate::api::SteamNewsItem;
 use crate::utils::AppError;
 use chrono::Utc;
-use sqlx::{Executor, Row, SqlitePool};
+use sqlx::sqlite::{SqliteConnection, SqliteRow};
+use sqlx::{Row, SqlitePool};
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use crate::log_debug;
 
+// Taille maximale d'une page de l'historique des sessions
+const MAX_SESSION_PAGE_SIZE: u32 = 500;
+
 pub struct GameQueries<'a> {
     pool: &'a SqlitePool,
 }
@@ -34,7 +44,11 @@ impl<'a> GameQueries<'a> {
                 s.last_session_duration,
                 s.sessions_count,
                 s.first_played,
-                s.last_played
+                s.last_played,
+                s.crash_count,
+                s.completed_at,
+                s.imported_playtime,
+                s.imported_last_played
             FROM games g
             LEFT JOIN game_metadata m ON g.id = m.game_id
             LEFT JOIN game_media med ON g.id = med.game_id
@@ -92,6 +106,7 @@ impl<'a> GameQueries<'a> {
                     "Steam" => Platform::Steam,
                     "BattleNet" => Platform::BattleNet,
                     "Epic" => Platform::Epic,
+                    "Gog" => Platform::Gog,
                     _ => Platform::Custom,
                 };
 
@@ -135,6 +150,7 @@ impl<'a> GameQueries<'a> {
                         logo,
                     },
                     last_played: row.get("last_played"),
+                    installed: row.get("installed"),
                     stats: GameStats {
                         total_playtime: row.get::<Option<i64>, _>("total_playtime").unwrap_or(0),
                         last_session_duration: row
@@ -143,7 +159,16 @@ impl<'a> GameQueries<'a> {
                         sessions_count: row.get::<Option<i32>, _>("sessions_count").unwrap_or(0),
                         first_played: row.get("first_played"),
                         last_played: row.get("last_played"),
+                        crash_count: row.get::<Option<i32>, _>("crash_count").unwrap_or(0),
+                        completed_at: row.get("completed_at"),
+                        imported_playtime: row
+                            .get::<Option<i64>, _>("imported_playtime")
+                            .unwrap_or(0),
+                        imported_last_played: row.get("imported_last_played"),
                     },
+                    kind: game_kind(&row.get::<String, _>("kind")),
+                    parent_game_id: row.get("parent_game_id"),
+                    addons: Vec::new(),
                 }))
             }
             None => Ok(None),
@@ -164,6 +189,61 @@ impl<'a> GameQueries<'a> {
         Ok(())
     }
 
+    // None retire le jeu des jeux terminés
+    pub async fn set_completed(&self, game_id: &str, completed_at: Option<i64>) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO game_stats (game_id, completed_at)
+            VALUES (?, ?)
+            ON CONFLICT(game_id) DO UPDATE SET completed_at = excluded.completed_at
+            "#,
+        )
+        .bind(game_id)
+        .bind(completed_at)
+        .execute(self.pool)
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to update game completion: {}", e),
+        })?;
+
+        Ok(())
+    }
+
+    // Remplace le temps importé d'un jeu ; faux si le jeu n'est pas dans la bibliothèque
+    pub async fn set_imported_playtime(
+        &self,
+        game_id: &str,
+        source: &str,
+        playtime: i64,
+        last_played: Option<i64>,
+    ) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO game_stats (
+                game_id, imported_playtime, imported_last_played, imported_source, imported_at
+            )
+            SELECT id, ?, ?, ?, ? FROM games WHERE id = ?
+            ON CONFLICT(game_id) DO UPDATE SET
+                imported_playtime = excluded.imported_playtime,
+                imported_last_played = excluded.imported_last_played,
+                imported_source = excluded.imported_source,
+                imported_at = excluded.imported_at
+            "#,
+        )
+        .bind(playtime)
+        .bind(last_played)
+        .bind(source)
+        .bind(Utc::now().timestamp())
+        .bind(game_id)
+        .execute(self.pool)
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to import playtime: {}", e),
+        })?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
     pub async fn delete_game(&self, game_id: &str) -> Result<(), AppError> {
         sqlx::query("DELETE FROM games WHERE id = ?")
             .bind(game_id)
@@ -214,6 +294,14 @@ impl<'a> GameQueries<'a> {
                 message: format!("Failed to delete game screenshots: {}", e),
             })?;
 
+        sqlx::query("DELETE FROM game_runners WHERE game_id = ?")
+            .bind(game_id)
+            .execute(self.pool)
+            .await
+            .map_err(|e| AppError {
+                message: format!("Failed to delete game runner: {}", e),
+            })?;
+
         Ok(())
     }
 
@@ -237,7 +325,11 @@ impl<'a> GameQueries<'a> {
                 s.last_session_duration,
                 s.sessions_count,
                 s.first_played,
-                s.last_played
+                s.last_played,
+                s.crash_count,
+                s.completed_at,
+                s.imported_playtime,
+                s.imported_last_played
             FROM games g
             LEFT JOIN game_metadata m ON g.id = m.game_id
             LEFT JOIN game_media med ON g.id = med.game_id
@@ -258,7 +350,7 @@ impl<'a> GameQueries<'a> {
         for row in games {
             // Récupérer les genres
             let genres = sqlx::query("SELECT genre FROM game_genres WHERE game_id = ?")
-                .bind(&row.get::<String, _>("id"))
+                .bind(row.get::<String, _>("id"))
                 .fetch_all(self.pool)
                 .await
                 .map_err(|e| AppError {
@@ -270,7 +362,7 @@ impl<'a> GameQueries<'a> {
 
             // Récupérer les tags
             let tags = sqlx::query("SELECT tag FROM game_tags WHERE game_id = ?")
-                .bind(&row.get::<String, _>("id"))
+                .bind(row.get::<String, _>("id"))
                 .fetch_all(self.pool)
                 .await
                 .map_err(|e| AppError {
@@ -282,7 +374,7 @@ impl<'a> GameQueries<'a> {
 
             // Récupérer les screenshots
             let screenshots = sqlx::query("SELECT url FROM game_screenshots WHERE game_id = ?")
-                .bind(&row.get::<String, _>("id"))
+                .bind(row.get::<String, _>("id"))
                 .fetch_all(self.pool)
                 .await
                 .map_err(|e| AppError {
@@ -296,6 +388,7 @@ impl<'a> GameQueries<'a> {
                 "Steam" => Platform::Steam,
                 "BattleNet" => Platform::BattleNet,
                 "Epic" => Platform::Epic,
+                "Gog" => Platform::Gog,
                 _ => Platform::Custom,
             };
 
@@ -331,13 +424,21 @@ impl<'a> GameQueries<'a> {
                     logo: row.get("logo"),
                 },
                 last_played: row.get("last_played"),
+                installed: row.get("installed"),
                 stats: GameStats {
                     total_playtime: row.get::<Option<i64>, _>("total_playtime").unwrap_or(0),
                     last_session_duration: row.get::<Option<i64>, _>("last_session_duration").unwrap_or(0),
                     sessions_count: row.get::<Option<i32>, _>("sessions_count").unwrap_or(0),
                     first_played: row.get("first_played"),
                     last_played: row.get("last_played"),
+                    crash_count: row.get::<Option<i32>, _>("crash_count").unwrap_or(0),
+                    completed_at: row.get("completed_at"),
+                    imported_playtime: row.get::<Option<i64>, _>("imported_playtime").unwrap_or(0),
+                    imported_last_played: row.get("imported_last_played"),
                 },
+                kind: game_kind(&row.get::<String, _>("kind")),
+                parent_game_id: row.get("parent_game_id"),
+                addons: Vec::new(),
             });
         }
 
@@ -345,6 +446,11 @@ impl<'a> GameQueries<'a> {
         Ok(result)
     }
 
+    // Jeux de base avec leurs contenus additionnels installés imbriqués
+    pub async fn get_library_games(&self) -> Result<Vec<Game>, AppError> {
+        Ok(nest_addons(self.get_all_games().await?))
+    }
+
     pub async fn upsert_game(&self, game: &Game) -> Result<(), AppError> {
         let mut tx = self.pool.begin().await.map_err(|e| AppError {
             message: format!("Failed to start transaction: {}", e),
@@ -354,6 +460,7 @@ impl<'a> GameQueries<'a> {
             Platform::Steam => "Steam",
             Platform::BattleNet => "BattleNet",
             Platform::Epic => "Epic",
+            Platform::Gog => "Gog",
             Platform::Custom => "Custom",
         };
 
@@ -383,6 +490,9 @@ impl<'a> GameQueries<'a> {
                     install_size = ?,
                     version = ?,
                     last_updated = ?,
+                    installed = ?,
+                    kind = ?,
+                    parent_game_id = ?,
                     updated_at = ?
                 WHERE id = ?
                 "#,
@@ -392,6 +502,9 @@ impl<'a> GameQueries<'a> {
             .bind(install_size)
             .bind(&game.installation.version)
             .bind(game.installation.last_updated)
+            .bind(game.installed)
+            .bind(game_kind_name(game.kind))
+            .bind(&game.parent_game_id)
             .bind(chrono::Utc::now().timestamp())
             .bind(&game.id)
             .execute(&mut *tx)
@@ -406,9 +519,9 @@ impl<'a> GameQueries<'a> {
                 INSERT INTO games (
                     id, platform_id, platform, title, install_path, executable,
                     install_size, version, last_updated, last_played, last_scan,
-                    created_at, updated_at
+                    installed, kind, parent_game_id, created_at, updated_at
                 )
-                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
                 "#,
             )
             .bind(&game.id)
@@ -422,6 +535,9 @@ impl<'a> GameQueries<'a> {
             .bind(game.installation.last_updated)
             .bind(game.last_played)
             .bind(chrono::Utc::now().timestamp())
+            .bind(game.installed)
+            .bind(game_kind_name(game.kind))
+            .bind(&game.parent_game_id)
             .bind(chrono::Utc::now().timestamp())
             .bind(chrono::Utc::now().timestamp())
             .execute(&mut *tx)
@@ -439,6 +555,56 @@ impl<'a> GameQueries<'a> {
     }
 }
 
+fn game_kind(value: &str) -> GameKind {
+    match value {
+        "Dlc" => GameKind::Dlc,
+        "Soundtrack" => GameKind::Soundtrack,
+        "Tool" => GameKind::Tool,
+        "Demo" => GameKind::Demo,
+        _ => GameKind::Base,
+    }
+}
+
+fn game_kind_name(kind: GameKind) -> &'static str {
+    match kind {
+        GameKind::Base => "Base",
+        GameKind::Dlc => "Dlc",
+        GameKind::Soundtrack => "Soundtrack",
+        GameKind::Tool => "Tool",
+        GameKind::Demo => "Demo",
+    }
+}
+
+// Range les contenus additionnels sous leur jeu de base ; ceux dont le jeu de
+// base est absent restent au premier niveau
+pub fn nest_addons(games: Vec<Game>) -> Vec<Game> {
+    let ids: HashSet<String> = games
+        .iter()
+        .filter(|game| game.parent_game_id.is_none())
+        .map(|game| game.id.clone())
+        .collect();
+
+    let mut addons: HashMap<String, Vec<Game>> = HashMap::new();
+    let mut library = Vec::new();
+    for game in games {
+        match &game.parent_game_id {
+            Some(parent_id) if ids.contains(parent_id) => {
+                if game.installed {
+                    addons.entry(parent_id.clone()).or_default().push(game);
+                }
+            }
+            _ => library.push(game),
+        }
+    }
+
+    for game in &mut library {
+        if let Some(children) = addons.remove(&game.id) {
+            game.addons = children;
+        }
+    }
+    library
+}
+
 pub struct SessionQueries<'a> {
     pool: &'a SqlitePool,
 }
@@ -469,9 +635,88 @@ impl<'a> SessionQueries<'a> {
         Ok(row.get("id"))
     }
 
-    pub async fn end_session(&self, session_id: i64, duration_secs: i64) -> Result<(), AppError> {
-        let now = Utc::now().timestamp();
+    // Supprime une session ouverte par erreur (jeu détecté à tort)
+    pub async fn discard_session(&self, session_id: i64) -> Result<(), AppError> {
+        sqlx::query("DELETE FROM game_sessions WHERE id = ? AND duration IS NULL")
+            .bind(session_id)
+            .execute(self.pool)
+            .await
+            .map_err(|e| AppError {
+                message: format!("Failed to discard game session: {}", e),
+            })?;
+
+        Ok(())
+    }
+
+    // Dernier signe de vie d'une session en cours, stocké dans end_time
+    pub async fn heartbeat(&self, session_id: i64, times: &SessionTimes) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            UPDATE game_sessions
+            SET end_time = ?, active_seconds = ?, idle_seconds = ?
+            WHERE id = ? AND duration IS NULL
+            "#,
+        )
+        .bind(times.end_time)
+        .bind(times.active_seconds)
+        .bind(times.idle_seconds)
+        .bind(session_id)
+        .execute(self.pool)
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to update session heartbeat: {}", e),
+        })?;
+
+        Ok(())
+    }
+
+    // Clôture les sessions restées ouvertes (crash ou arrêt de glaunch) à leur
+    // dernier heartbeat
+    pub async fn recover_dangling_sessions(&self, mode: PlaytimeMode) -> Result<usize, AppError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, start_time, end_time, active_seconds, idle_seconds
+            FROM game_sessions
+            WHERE duration IS NULL
+            "#,
+        )
+        .fetch_all(self.pool)
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to fetch dangling sessions: {}", e),
+        })?;
+
+        for row in &rows {
+            let session_id: i64 = row.get("id");
+            let start_time: i64 = row.get("start_time");
+            let end_time = row.get::<Option<i64>, _>("end_time").unwrap_or(start_time);
+            // Sessions antérieures au suivi de l'activité : tout est compté actif
+            let times = SessionTimes {
+                end_time,
+                active_seconds: row
+                    .get::<Option<i64>, _>("active_seconds")
+                    .unwrap_or((end_time - start_time).max(0)),
+                idle_seconds: row.get::<Option<i64>, _>("idle_seconds").unwrap_or(0),
+            };
+
+            let outcome = SessionOutcome::new(SessionEndReason::Recovered);
+            self.end_session(session_id, &times, mode, &outcome).await?;
+        }
+
+        Ok(rows.len())
+    }
 
+    // duration est le temps retenu selon le mode ; end_time est l'arrêt réel du
+    // jeu, pas le moment où la session est enregistrée. Une session déjà close
+    // est ignorée : plusieurs chemins (fin détectée, arrêt manuel, fermeture de
+    // l'app) peuvent la terminer en même temps
+    pub async fn end_session(
+        &self,
+        session_id: i64,
+        times: &SessionTimes,
+        mode: PlaytimeMode,
+        outcome: &SessionOutcome,
+    ) -> Result<(), AppError> {
         let mut tx = self.pool.begin().await.map_err(|e| AppError {
             message: format!("Failed to start transaction: {}", e),
         })?;
@@ -480,71 +725,324 @@ impl<'a> SessionQueries<'a> {
         let row = sqlx::query(
             r#"
             UPDATE game_sessions
-            SET end_time = ?, duration = ?
-            WHERE id = ?
-            RETURNING game_id, duration, start_time
+            SET end_time = ?, duration = ?, active_seconds = ?, idle_seconds = ?, end_reason = ?,
+                exit_code = ?, exit_signal = ?, killed = ?, crashed = ?
+            WHERE id = ? AND duration IS NULL
+            RETURNING game_id
             "#,
         )
-        .bind(now)
-        .bind(duration_secs)
+        .bind(times.end_time)
+        .bind(times.counted_seconds(mode))
+        .bind(times.active_seconds)
+        .bind(times.idle_seconds)
+        .bind(end_reason_to_str(outcome.reason))
+        .bind(outcome.exit_code)
+        .bind(outcome.exit_signal)
+        .bind(outcome.killed)
+        .bind(outcome.crashed)
         .bind(session_id)
-        .fetch_one(&mut *tx)
+        .fetch_optional(&mut *tx)
         .await
         .map_err(|e| AppError {
             message: format!("Failed to end game session: {}", e),
         })?;
 
+        let Some(row) = row else {
+            return Ok(());
+        };
+
         let game_id: String = row.get("game_id");
-        let duration: i64 = row.get("duration");
-        let start_time: i64 = row.get("start_time");
+        recompute_game_stats(&mut tx, &game_id).await?;
+
+        tx.commit().await.map_err(|e| AppError {
+            message: format!("Failed to commit transaction: {}", e),
+        })?;
+
+        Ok(())
+    }
+
+    // Historique paginé, du plus récent au plus ancien, pour un jeu ou pour tous
+    pub async fn list_sessions(
+        &self,
+        game_id: Option<&str>,
+        page: u32,
+        page_size: u32,
+    ) -> Result<SessionPage, AppError> {
+        let page_size = page_size.clamp(1, MAX_SESSION_PAGE_SIZE);
+        let total: i64 = sqlx::query(
+            "SELECT COUNT(*) as total FROM game_sessions WHERE ?1 IS NULL OR game_id = ?1",
+        )
+        .bind(game_id)
+        .fetch_one(self.pool)
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to count game sessions: {}", e),
+        })?
+        .get("total");
+
+        let rows = sqlx::query(
+            r#"
+            SELECT * FROM game_sessions
+            WHERE ?1 IS NULL OR game_id = ?1
+            ORDER BY start_time DESC, id DESC
+            LIMIT ?2 OFFSET ?3
+            "#,
+        )
+        .bind(game_id)
+        .bind(page_size as i64)
+        .bind(page as i64 * page_size as i64)
+        .fetch_all(self.pool)
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to fetch game sessions: {}", e),
+        })?;
+
+        Ok(SessionPage {
+            sessions: rows.iter().map(session_from_row).collect(),
+            total,
+            page,
+            page_size,
+        })
+    }
+
+    pub async fn get_session(&self, session_id: i64) -> Result<Option<SessionRecord>, AppError> {
+        let row = sqlx::query("SELECT * FROM game_sessions WHERE id = ?")
+            .bind(session_id)
+            .fetch_optional(self.pool)
+            .await
+            .map_err(|e| AppError {
+                message: format!("Failed to fetch game session: {}", e),
+            })?;
+
+        Ok(row.as_ref().map(session_from_row))
+    }
+
+    // Session jouée hors de glaunch (hors ligne, autre machine) : tout est actif
+    pub async fn add_manual_session(
+        &self,
+        game_id: &str,
+        start_time: i64,
+        end_time: i64,
+    ) -> Result<SessionRecord, AppError> {
+        validate_session_times(start_time, end_time)?;
 
-        // Vérifier les stats actuelles
-        let current_stats =
-            sqlx::query("SELECT total_playtime, sessions_count FROM game_stats WHERE game_id = ?")
-                .bind(&game_id)
+        let mut tx = self.pool.begin().await.map_err(|e| AppError {
+            message: format!("Failed to start transaction: {}", e),
+        })?;
+
+        let row = sqlx::query(
+            r#"
+            INSERT INTO game_sessions (
+                game_id, start_time, end_time, duration,
+                active_seconds, idle_seconds, end_reason
+            )
+            VALUES (?, ?, ?, ?, ?, 0, ?)
+            RETURNING *
+            "#,
+        )
+        .bind(game_id)
+        .bind(start_time)
+        .bind(end_time)
+        .bind(end_time - start_time)
+        .bind(end_time - start_time)
+        .bind(end_reason_to_str(SessionEndReason::Manual))
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to add game session: {}", e),
+        })?;
+
+        recompute_game_stats(&mut tx, game_id).await?;
+        tx.commit().await.map_err(|e| AppError {
+            message: format!("Failed to commit transaction: {}", e),
+        })?;
+
+        Ok(session_from_row(&row))
+    }
+
+    // Nouvelles bornes d'une session terminée ; le temps inactif est conservé
+    // dans la limite de la nouvelle durée
+    pub async fn update_session(
+        &self,
+        session_id: i64,
+        start_time: i64,
+        end_time: i64,
+        mode: PlaytimeMode,
+    ) -> Result<SessionRecord, AppError> {
+        validate_session_times(start_time, end_time)?;
+
+        let mut tx = self.pool.begin().await.map_err(|e| AppError {
+            message: format!("Failed to start transaction: {}", e),
+        })?;
+
+        let idle_seconds: i64 = sqlx::query(
+            "SELECT COALESCE(idle_seconds, 0) as idle FROM game_sessions WHERE id = ? AND duration IS NOT NULL",
+        )
+        .bind(session_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to fetch game session: {}", e),
+        })?
+        .ok_or_else(|| AppError {
+            message: format!("Session {} not found or still running", session_id),
+        })?
+        .get("idle");
+
+        let times = SessionTimes {
+            end_time,
+            active_seconds: (end_time - start_time - idle_seconds).max(0),
+            idle_seconds: idle_seconds.min(end_time - start_time),
+        };
+
+        let row = sqlx::query(
+            r#"
+            UPDATE game_sessions
+            SET start_time = ?, end_time = ?, duration = ?, active_seconds = ?, idle_seconds = ?
+            WHERE id = ?
+            RETURNING *
+            "#,
+        )
+        .bind(start_time)
+        .bind(times.end_time)
+        .bind(times.counted_seconds(mode))
+        .bind(times.active_seconds)
+        .bind(times.idle_seconds)
+        .bind(session_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to update game session: {}", e),
+        })?;
+
+        let session = session_from_row(&row);
+        recompute_game_stats(&mut tx, &session.game_id).await?;
+        tx.commit().await.map_err(|e| AppError {
+            message: format!("Failed to commit transaction: {}", e),
+        })?;
+
+        Ok(session)
+    }
+
+    // Fusionne des sessions d'un même jeu (typiquement coupées par un crash) dans
+    // la plus ancienne ; l'écart entre elles n'est pas compté
+    pub async fn merge_sessions(&self, session_ids: &[i64]) -> Result<SessionRecord, AppError> {
+        let mut session_ids = session_ids.to_vec();
+        session_ids.sort_unstable();
+        session_ids.dedup();
+        if session_ids.len() < 2 {
+            return Err(AppError {
+                message: "At least two distinct sessions are required to merge".to_string(),
+            });
+        }
+
+        let mut tx = self.pool.begin().await.map_err(|e| AppError {
+            message: format!("Failed to start transaction: {}", e),
+        })?;
+
+        let mut sessions = Vec::new();
+        for session_id in &session_ids {
+            let row = sqlx::query("SELECT * FROM game_sessions WHERE id = ?")
+                .bind(session_id)
                 .fetch_optional(&mut *tx)
                 .await
                 .map_err(|e| AppError {
-                    message: format!("Failed to fetch current stats: {}", e),
+                    message: format!("Failed to fetch game session: {}", e),
+                })?
+                .ok_or_else(|| AppError {
+                    message: format!("Session {} not found", session_id),
                 })?;
+            sessions.push(session_from_row(&row));
+        }
 
-        // Mettre à jour les statistiques globales
-        sqlx::query(
+        sessions.sort_by_key(|session| session.start_time);
+        let first = &sessions[0];
+        let last = &sessions[sessions.len() - 1];
+
+        if sessions.iter().any(|session| session.game_id != first.game_id) {
+            return Err(AppError {
+                message: "Cannot merge sessions of different games".to_string(),
+            });
+        }
+        if sessions.iter().any(|session| session.duration.is_none()) {
+            return Err(AppError {
+                message: "Cannot merge a session that is still running".to_string(),
+            });
+        }
+
+        let sum = |value: fn(&SessionRecord) -> Option<i64>| -> i64 {
+            sessions.iter().filter_map(value).sum()
+        };
+        let end_time = sessions
+            .iter()
+            .filter_map(|session| session.end_time)
+            .max()
+            .unwrap_or(first.start_time);
+
+        let row = sqlx::query(
             r#"
-            INSERT INTO game_stats (
-                game_id, total_playtime, last_session_duration,
-                sessions_count, first_played, last_played
-            )
-            VALUES (?, ?, ?, 1, ?, ?)
-            ON CONFLICT(game_id) DO UPDATE SET
-                total_playtime = total_playtime + excluded.last_session_duration,
-                last_session_duration = excluded.last_session_duration,
-                sessions_count = sessions_count + 1,
-                first_played = COALESCE(game_stats.first_played, excluded.first_played),
-                last_played = excluded.last_played
+            UPDATE game_sessions
+            SET end_time = ?, duration = ?, active_seconds = ?, idle_seconds = ?,
+                end_reason = ?, exit_code = ?, exit_signal = ?, killed = ?, crashed = ?
+            WHERE id = ?
+            RETURNING *
             "#,
         )
-        .bind(&game_id)
-        .bind(duration)
-        .bind(duration)
-        .bind(start_time)
-        .bind(now)
-        .execute(&mut *tx)
+        .bind(end_time)
+        .bind(sum(|session| session.duration))
+        .bind(sum(|session| session.active_seconds))
+        .bind(sum(|session| session.idle_seconds))
+        // La session fusionnée se termine comme la dernière
+        .bind(last.end_reason.map(end_reason_to_str))
+        .bind(last.exit_code)
+        .bind(last.exit_signal)
+        .bind(last.killed)
+        .bind(last.crashed)
+        .bind(first.id)
+        .fetch_one(&mut *tx)
         .await
         .map_err(|e| AppError {
-            message: format!("Failed to update game stats: {}", e),
+            message: format!("Failed to merge game sessions: {}", e),
         })?;
 
-        // Vérifier les nouvelles stats
-        let new_stats =
-            sqlx::query("SELECT total_playtime, sessions_count FROM game_stats WHERE game_id = ?")
-                .bind(&game_id)
-                .fetch_one(&mut *tx)
+        for session in &sessions[1..] {
+            sqlx::query("DELETE FROM game_sessions WHERE id = ?")
+                .bind(session.id)
+                .execute(&mut *tx)
                 .await
                 .map_err(|e| AppError {
-                    message: format!("Failed to fetch updated stats: {}", e),
+                    message: format!("Failed to delete merged session: {}", e),
                 })?;
+        }
+
+        recompute_game_stats(&mut tx, &first.game_id).await?;
+        tx.commit().await.map_err(|e| AppError {
+            message: format!("Failed to commit transaction: {}", e),
+        })?;
+
+        Ok(session_from_row(&row))
+    }
+
+    pub async fn delete_session(&self, session_id: i64) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await.map_err(|e| AppError {
+            message: format!("Failed to start transaction: {}", e),
+        })?;
 
+        let game_id: String = sqlx::query(
+            "DELETE FROM game_sessions WHERE id = ? AND duration IS NOT NULL RETURNING game_id",
+        )
+        .bind(session_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to delete game session: {}", e),
+        })?
+        .ok_or_else(|| AppError {
+            message: format!("Session {} not found or still running", session_id),
+        })?
+        .get("game_id");
+
+        recompute_game_stats(&mut tx, &game_id).await?;
         tx.commit().await.map_err(|e| AppError {
             message: format!("Failed to commit transaction: {}", e),
         })?;
@@ -552,11 +1050,56 @@ impl<'a> SessionQueries<'a> {
         Ok(())
     }
 
+    // Derniers crashs, tous jeux confondus, pour repérer une installation cassée
+    // ou une mise à jour défectueuse
+    pub async fn get_recent_crashes(
+        &self,
+        since: Option<i64>,
+        limit: i64,
+    ) -> Result<Vec<CrashRecord>, AppError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT
+                gs.id, gs.game_id, g.title, gs.start_time, gs.end_time,
+                gs.exit_code, gs.exit_signal,
+                COALESCE(st.crash_count, 0) as crash_count
+            FROM game_sessions gs
+            JOIN games g ON g.id = gs.game_id
+            LEFT JOIN game_stats st ON st.game_id = gs.game_id
+            WHERE gs.crashed = 1 AND gs.start_time >= ?
+            ORDER BY gs.start_time DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(since.unwrap_or(0))
+        .bind(limit)
+        .fetch_all(self.pool)
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to fetch recent crashes: {}", e),
+        })?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| CrashRecord {
+                session_id: row.get("id"),
+                game_id: row.get("game_id"),
+                game_title: row.get("title"),
+                start_time: row.get("start_time"),
+                end_time: row.get("end_time"),
+                exit_code: row.get("exit_code"),
+                exit_signal: row.get("exit_signal"),
+                crash_count: row.get("crash_count"),
+            })
+            .collect())
+    }
+
     pub async fn get_game_stats(&self, game_id: &str) -> Result<Option<GameStats>, AppError> {
         let row = sqlx::query(
             r#"
             SELECT total_playtime, last_session_duration,
-                   sessions_count, first_played, last_played
+                   sessions_count, first_played, last_played, crash_count, completed_at,
+                   imported_playtime, imported_last_played
             FROM game_stats
             WHERE game_id = ?
             "#,
@@ -574,41 +1117,228 @@ impl<'a> SessionQueries<'a> {
             sessions_count: r.get("sessions_count"),
             first_played: r.get("first_played"),
             last_played: r.get("last_played"),
+            crash_count: r.get("crash_count"),
+            completed_at: r.get("completed_at"),
+            imported_playtime: r.get("imported_playtime"),
+            imported_last_played: r.get("imported_last_played"),
         }))
     }
 }
 
-pub struct MetadataQueries<'a> {
+pub struct AnalyticsQueries<'a> {
     pool: &'a SqlitePool,
 }
 
-impl<'a> MetadataQueries<'a> {
+impl<'a> AnalyticsQueries<'a> {
     pub fn new(pool: &'a SqlitePool) -> Self {
         Self { pool }
     }
 
-    pub async fn get_metadata(&self, game_id: &str) -> Result<Option<GameMetadata>, AppError> {
-        let row = sqlx::query(
+    // Sessions terminées qui chevauchent l'intervalle [from, to[
+    pub async fn sessions_between(
+        &self,
+        from: Option<i64>,
+        to: Option<i64>,
+    ) -> Result<Vec<SessionRecord>, AppError> {
+        let rows = sqlx::query(
             r#"
-            SELECT
-                m.title, m.description, m.developer, m.publisher,
-                m.release_date,
-                med.thumbnail, med.cover, med.background, med.icon, med.logo
-            FROM game_metadata m
-            LEFT JOIN game_media med ON m.game_id = med.game_id
-            WHERE m.game_id = ?
+            SELECT * FROM game_sessions
+            WHERE duration IS NOT NULL
+            AND (?1 IS NULL OR end_time > ?1)
+            AND (?2 IS NULL OR start_time < ?2)
+            ORDER BY start_time
             "#,
         )
-        .bind(game_id)
-        .fetch_optional(self.pool)
+        .bind(from)
+        .bind(to)
+        .fetch_all(self.pool)
         .await
         .map_err(|e| AppError {
-            message: format!("Failed to fetch metadata: {}", e),
+            message: format!("Failed to fetch game sessions: {}", e),
         })?;
 
-        if let Some(row) = row {
-            // Récupérer les genres
-            let genres = sqlx::query("SELECT genre FROM game_genres WHERE game_id = ?")
+        Ok(rows.iter().map(session_from_row).collect())
+    }
+
+    pub async fn game_facets(&self) -> Result<HashMap<String, GameFacets>, AppError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT g.id, COALESCE(m.title, g.title) as title, g.platform, m.developer,
+                   med.cover, s.first_played, s.completed_at
+            FROM games g
+            LEFT JOIN game_metadata m ON m.game_id = g.id
+            LEFT JOIN game_media med ON med.game_id = g.id
+            LEFT JOIN game_stats s ON s.game_id = g.id
+            "#,
+        )
+        .fetch_all(self.pool)
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to fetch games: {}", e),
+        })?;
+
+        let mut facets: HashMap<String, GameFacets> = rows
+            .iter()
+            .map(|row| {
+                let game_id: String = row.get("id");
+                let facets = GameFacets {
+                    game_id: game_id.clone(),
+                    title: row.get("title"),
+                    platform: row.get("platform"),
+                    developer: row.get("developer"),
+                    genres: Vec::new(),
+                    cover: row.get("cover"),
+                    first_played: row.get("first_played"),
+                    completed_at: row.get("completed_at"),
+                };
+                (game_id, facets)
+            })
+            .collect();
+
+        let genres = sqlx::query("SELECT game_id, genre FROM game_genres")
+            .fetch_all(self.pool)
+            .await
+            .map_err(|e| AppError {
+                message: format!("Failed to fetch genres: {}", e),
+            })?;
+
+        for row in genres {
+            let game_id: String = row.get("game_id");
+            if let Some(game) = facets.get_mut(&game_id) {
+                game.genres.push(row.get("genre"));
+            }
+        }
+
+        Ok(facets)
+    }
+}
+
+// Les agrégats de game_stats sont toujours recalculés depuis les sessions
+// terminées, dans la transaction qui modifie ces sessions
+async fn recompute_game_stats(conn: &mut SqliteConnection, game_id: &str) -> Result<(), AppError> {
+    sqlx::query(
+        r#"
+        INSERT INTO game_stats (
+            game_id, total_playtime, last_session_duration,
+            sessions_count, first_played, last_played, crash_count
+        )
+        SELECT
+            ?1,
+            COALESCE(SUM(duration), 0),
+            COALESCE((
+                SELECT duration FROM game_sessions
+                WHERE game_id = ?1 AND duration IS NOT NULL
+                ORDER BY end_time DESC, id DESC
+                LIMIT 1
+            ), 0),
+            COUNT(*),
+            MIN(start_time),
+            MAX(end_time),
+            COALESCE(SUM(crashed), 0)
+        FROM game_sessions
+        WHERE game_id = ?1 AND duration IS NOT NULL
+        ON CONFLICT(game_id) DO UPDATE SET
+            total_playtime = excluded.total_playtime,
+            last_session_duration = excluded.last_session_duration,
+            sessions_count = excluded.sessions_count,
+            first_played = excluded.first_played,
+            last_played = excluded.last_played,
+            crash_count = excluded.crash_count
+        "#,
+    )
+    .bind(game_id)
+    .execute(conn)
+    .await
+    .map_err(|e| AppError {
+        message: format!("Failed to update game stats: {}", e),
+    })?;
+
+    Ok(())
+}
+
+fn validate_session_times(start_time: i64, end_time: i64) -> Result<(), AppError> {
+    if end_time <= start_time {
+        return Err(AppError {
+            message: "Session end must be after its start".to_string(),
+        });
+    }
+    if end_time > Utc::now().timestamp() {
+        return Err(AppError {
+            message: "Session cannot end in the future".to_string(),
+        });
+    }
+    Ok(())
+}
+
+fn end_reason_to_str(reason: SessionEndReason) -> &'static str {
+    match reason {
+        SessionEndReason::Exited => "Exited",
+        SessionEndReason::StoppedByUser => "StoppedByUser",
+        SessionEndReason::AppShutdown => "AppShutdown",
+        SessionEndReason::Recovered => "Recovered",
+        SessionEndReason::Manual => "Manual",
+    }
+}
+
+fn session_from_row(row: &SqliteRow) -> SessionRecord {
+    let end_reason = row
+        .get::<Option<String>, _>("end_reason")
+        .and_then(|reason| match reason.as_str() {
+            "Exited" => Some(SessionEndReason::Exited),
+            "StoppedByUser" => Some(SessionEndReason::StoppedByUser),
+            "AppShutdown" => Some(SessionEndReason::AppShutdown),
+            "Recovered" => Some(SessionEndReason::Recovered),
+            "Manual" => Some(SessionEndReason::Manual),
+            _ => None,
+        });
+
+    SessionRecord {
+        id: row.get("id"),
+        game_id: row.get("game_id"),
+        start_time: row.get("start_time"),
+        end_time: row.get("end_time"),
+        duration: row.get("duration"),
+        active_seconds: row.get("active_seconds"),
+        idle_seconds: row.get("idle_seconds"),
+        end_reason,
+        exit_code: row.get("exit_code"),
+        exit_signal: row.get("exit_signal"),
+        killed: row.get("killed"),
+        crashed: row.get("crashed"),
+    }
+}
+
+pub struct MetadataQueries<'a> {
+    pool: &'a SqlitePool,
+}
+
+impl<'a> MetadataQueries<'a> {
+    pub fn new(pool: &'a SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn get_metadata(&self, game_id: &str) -> Result<Option<GameMetadata>, AppError> {
+        let row = sqlx::query(
+            r#"
+            SELECT
+                m.title, m.description, m.developer, m.publisher,
+                m.release_date,
+                med.thumbnail, med.cover, med.background, med.icon, med.logo
+            FROM game_metadata m
+            LEFT JOIN game_media med ON m.game_id = med.game_id
+            WHERE m.game_id = ?
+            "#,
+        )
+        .bind(game_id)
+        .fetch_optional(self.pool)
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to fetch metadata: {}", e),
+        })?;
+
+        if let Some(row) = row {
+            // Récupérer les genres
+            let genres = sqlx::query("SELECT genre FROM game_genres WHERE game_id = ?")
                 .bind(game_id)
                 .fetch_all(self.pool)
                 .await
@@ -802,4 +1532,854 @@ impl<'a> MetadataQueries<'a> {
 
         Ok(())
     }
+
+    // Met à jour les images sans toucher aux métadonnées ni aux screenshots
+    pub async fn update_media(&self, game_id: &str, media: &GameMedia) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO game_media (
+                game_id, thumbnail, cover, background, icon, logo, last_fetched
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(game_id) DO UPDATE SET
+                thumbnail = excluded.thumbnail,
+                cover = excluded.cover,
+                background = excluded.background,
+                icon = excluded.icon,
+                logo = excluded.logo,
+                last_fetched = excluded.last_fetched
+            "#,
+        )
+        .bind(game_id)
+        .bind(&media.thumbnail)
+        .bind(&media.cover)
+        .bind(&media.background)
+        .bind(&media.icon)
+        .bind(&media.logo)
+        .bind(chrono::Utc::now().timestamp())
+        .execute(self.pool)
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to update media: {}", e),
+        })?;
+
+        Ok(())
+    }
+}
+
+pub struct RunnerQueries<'a> {
+    pool: &'a SqlitePool,
+}
+
+impl<'a> RunnerQueries<'a> {
+    pub fn new(pool: &'a SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn get_runner(&self, game_id: &str) -> Result<Option<RunnerConfig>, AppError> {
+        let row = sqlx::query(
+            "SELECT runner, wine_path, prefix_path, dll_overrides FROM game_runners WHERE game_id = ?",
+        )
+        .bind(game_id)
+        .fetch_optional(self.pool)
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to fetch game runner: {}", e),
+        })?;
+
+        Ok(row.map(|row| {
+            let kind = match row.get::<String, _>("runner").as_str() {
+                "Wine" => RunnerKind::Wine,
+                "Proton" => RunnerKind::Proton,
+                _ => RunnerKind::Native,
+            };
+
+            RunnerConfig {
+                kind,
+                wine_path: row.get("wine_path"),
+                prefix_path: row.get("prefix_path"),
+                dll_overrides: serde_json::from_str(&row.get::<String, _>("dll_overrides"))
+                    .unwrap_or_default(),
+            }
+        }))
+    }
+
+    pub async fn set_runner(&self, game_id: &str, config: &RunnerConfig) -> Result<(), AppError> {
+        let runner_str = match config.kind {
+            RunnerKind::Native => "Native",
+            RunnerKind::Wine => "Wine",
+            RunnerKind::Proton => "Proton",
+        };
+
+        let dll_overrides = serde_json::to_string(&config.dll_overrides).map_err(|e| AppError {
+            message: format!("Failed to serialize DLL overrides: {}", e),
+        })?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO game_runners (
+                game_id, runner, wine_path, prefix_path, dll_overrides, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?)
+            ON CONFLICT(game_id) DO UPDATE SET
+                runner = excluded.runner,
+                wine_path = excluded.wine_path,
+                prefix_path = excluded.prefix_path,
+                dll_overrides = excluded.dll_overrides,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(game_id)
+        .bind(runner_str)
+        .bind(&config.wine_path)
+        .bind(&config.prefix_path)
+        .bind(dll_overrides)
+        .bind(Utc::now().timestamp())
+        .execute(self.pool)
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to save game runner: {}", e),
+        })?;
+
+        Ok(())
+    }
+}
+
+pub struct VersionQueries<'a> {
+    pool: &'a SqlitePool,
+}
+
+impl<'a> VersionQueries<'a> {
+    pub fn new(pool: &'a SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    // Enregistre la version observée ; renvoie la mise à jour quand elle
+    // remplace une version déjà connue (la première observation sert de référence)
+    // previous : version et taille enregistrées sur l'installation avant ce scan
+    pub async fn record_version(
+        &self,
+        game_id: &str,
+        version: &str,
+        install_size: u64,
+        previous: Option<(&str, u64)>,
+    ) -> Result<Option<GameUpdate>, AppError> {
+        // Même version qu'avant : seule la ligne de référence est éventuellement créée
+        let previous = previous.filter(|(previous, _)| *previous != version);
+
+        // Un retour à une version déjà vue n'est pas une nouvelle mise à jour
+        let result = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO game_versions (
+                game_id, version, previous_version, first_seen, install_size, size_delta
+            )
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(game_id)
+        .bind(version)
+        .bind(previous.map(|(version, _)| version))
+        .bind(Utc::now().timestamp())
+        .bind(install_size as i64)
+        .bind(previous.map(|(_, size)| install_size as i64 - size as i64))
+        .execute(self.pool)
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to record game version: {}", e),
+        })?;
+
+        if previous.is_none() || result.rows_affected() == 0 {
+            return Ok(None);
+        }
+
+        self.get_update(result.last_insert_rowid()).await
+    }
+
+    pub async fn get_update(&self, id: i64) -> Result<Option<GameUpdate>, AppError> {
+        let row = sqlx::query(
+            r#"
+            SELECT v.*, COALESCE(m.title, g.title) as title
+            FROM game_versions v
+            JOIN games g ON g.id = v.game_id
+            LEFT JOIN game_metadata m ON m.game_id = v.game_id
+            WHERE v.id = ?
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(self.pool)
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to fetch game update: {}", e),
+        })?;
+
+        Ok(row.as_ref().map(update_from_row))
+    }
+
+    // Mises à jour de toute la bibliothèque, les plus récentes d'abord
+    pub async fn recent_updates(
+        &self,
+        since: Option<i64>,
+        limit: i64,
+    ) -> Result<Vec<GameUpdate>, AppError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT v.*, COALESCE(m.title, g.title) as title
+            FROM game_versions v
+            JOIN games g ON g.id = v.game_id
+            LEFT JOIN game_metadata m ON m.game_id = v.game_id
+            WHERE v.previous_version IS NOT NULL
+            AND (?1 IS NULL OR v.first_seen >= ?1)
+            ORDER BY v.first_seen DESC, v.id DESC
+            LIMIT ?2
+            "#,
+        )
+        .bind(since)
+        .bind(limit)
+        .fetch_all(self.pool)
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to fetch game updates: {}", e),
+        })?;
+
+        Ok(rows.iter().map(update_from_row).collect())
+    }
+
+    // Historique complet d'un jeu, version de référence comprise
+    pub async fn game_versions(&self, game_id: &str) -> Result<Vec<GameUpdate>, AppError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT v.*, COALESCE(m.title, g.title) as title
+            FROM game_versions v
+            JOIN games g ON g.id = v.game_id
+            LEFT JOIN game_metadata m ON m.game_id = v.game_id
+            WHERE v.game_id = ?
+            ORDER BY v.first_seen DESC, v.id DESC
+            "#,
+        )
+        .bind(game_id)
+        .fetch_all(self.pool)
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to fetch game versions: {}", e),
+        })?;
+
+        Ok(rows.iter().map(update_from_row).collect())
+    }
+}
+
+fn update_from_row(row: &SqliteRow) -> GameUpdate {
+    GameUpdate {
+        id: row.get("id"),
+        game_id: row.get("game_id"),
+        title: row.get("title"),
+        version: row.get("version"),
+        previous_version: row.get("previous_version"),
+        first_seen: row.get("first_seen"),
+        install_size: row.get::<i64, _>("install_size") as u64,
+        size_delta: row.get("size_delta"),
+        patch_notes_url: None,
+    }
+}
+
+pub struct NewsQueries<'a> {
+    pool: &'a SqlitePool,
+}
+
+impl<'a> NewsQueries<'a> {
+    pub fn new(pool: &'a SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    // ETag et date du dernier rafraîchissement du flux d'un jeu
+    pub async fn fetch_state(&self, game_id: &str) -> Result<Option<(Option<String>, i64)>, AppError> {
+        let row = sqlx::query("SELECT etag, fetched_at FROM news_fetch_state WHERE game_id = ?")
+            .bind(game_id)
+            .fetch_optional(self.pool)
+            .await
+            .map_err(|e| AppError {
+                message: format!("Failed to fetch news state: {}", e),
+            })?;
+
+        Ok(row.map(|row| (row.get("etag"), row.get("fetched_at"))))
+    }
+
+    pub async fn set_fetch_state(&self, game_id: &str, etag: Option<&str>) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO news_fetch_state (game_id, etag, fetched_at)
+            VALUES (?, ?, ?)
+            ON CONFLICT(game_id) DO UPDATE SET
+                etag = excluded.etag,
+                fetched_at = excluded.fetched_at
+            "#,
+        )
+        .bind(game_id)
+        .bind(etag)
+        .bind(Utc::now().timestamp())
+        .execute(self.pool)
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to save news state: {}", e),
+        })?;
+
+        Ok(())
+    }
+
+    // Insère ou met à jour les actualités sans toucher à l'état lu ;
+    // renvoie le nombre de nouvelles entrées
+    pub async fn upsert_news(&self, game_id: &str, items: &[SteamNewsItem]) -> Result<usize, AppError> {
+        let mut tx = self.pool.begin().await.map_err(|e| AppError {
+            message: format!("Failed to start transaction: {}", e),
+        })?;
+
+        let now = Utc::now().timestamp();
+        let mut new_items = 0;
+        for item in items {
+            let exists = sqlx::query("SELECT 1 FROM game_news WHERE game_id = ? AND gid = ?")
+                .bind(game_id)
+                .bind(&item.gid)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(|e| AppError {
+                    message: format!("Failed to fetch game news: {}", e),
+                })?
+                .is_some();
+
+            let tags = serde_json::to_string(&item.tags).unwrap_or_else(|_| "[]".to_string());
+            sqlx::query(
+                r#"
+                INSERT INTO game_news (
+                    game_id, gid, title, url, author, contents, feed_label, date, tags, fetched_at
+                )
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                ON CONFLICT(game_id, gid) DO UPDATE SET
+                    title = excluded.title,
+                    url = excluded.url,
+                    author = excluded.author,
+                    contents = excluded.contents,
+                    feed_label = excluded.feed_label,
+                    date = excluded.date,
+                    tags = excluded.tags,
+                    fetched_at = excluded.fetched_at
+                "#,
+            )
+            .bind(game_id)
+            .bind(&item.gid)
+            .bind(&item.title)
+            .bind(&item.url)
+            .bind(&item.author)
+            .bind(&item.contents)
+            .bind(&item.feedlabel)
+            .bind(item.date)
+            .bind(tags)
+            .bind(now)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError {
+                message: format!("Failed to save game news: {}", e),
+            })?;
+
+            if !exists {
+                new_items += 1;
+            }
+        }
+
+        tx.commit().await.map_err(|e| AppError {
+            message: format!("Failed to commit transaction: {}", e),
+        })?;
+
+        Ok(new_items)
+    }
+
+    pub async fn list_news(
+        &self,
+        game_id: Option<&str>,
+        unread_only: bool,
+        limit: i64,
+    ) -> Result<Vec<GameNews>, AppError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT n.*, COALESCE(m.title, g.title) as game_title
+            FROM game_news n
+            JOIN games g ON g.id = n.game_id
+            LEFT JOIN game_metadata m ON m.game_id = n.game_id
+            WHERE (?1 IS NULL OR n.game_id = ?1)
+            AND (?2 = 0 OR n.is_read = 0)
+            ORDER BY n.date DESC
+            LIMIT ?3
+            "#,
+        )
+        .bind(game_id)
+        .bind(unread_only)
+        .bind(limit)
+        .fetch_all(self.pool)
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to fetch game news: {}", e),
+        })?;
+
+        Ok(rows.iter().map(news_from_row).collect())
+    }
+
+    // Actualités publiées depuis la dernière partie de chaque jeu
+    pub async fn news_since_last_played(
+        &self,
+        game_id: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<GameNews>, AppError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT n.*, COALESCE(m.title, g.title) as game_title
+            FROM game_news n
+            JOIN games g ON g.id = n.game_id
+            LEFT JOIN game_metadata m ON m.game_id = n.game_id
+            LEFT JOIN game_stats s ON s.game_id = n.game_id
+            WHERE (?1 IS NULL OR n.game_id = ?1)
+            AND n.date > MAX(
+                COALESCE(s.last_played, 0),
+                COALESCE(s.imported_last_played, 0)
+            )
+            ORDER BY n.date DESC
+            LIMIT ?2
+            "#,
+        )
+        .bind(game_id)
+        .bind(limit)
+        .fetch_all(self.pool)
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to fetch game news: {}", e),
+        })?;
+
+        Ok(rows.iter().map(news_from_row).collect())
+    }
+
+    // Sans liste de gid, marque toutes les actualités du jeu
+    pub async fn mark_read(
+        &self,
+        game_id: &str,
+        gids: Option<&[String]>,
+        read: bool,
+    ) -> Result<u64, AppError> {
+        let result = match gids {
+            Some(gids) => {
+                let mut updated = 0;
+                for gid in gids {
+                    updated += sqlx::query(
+                        "UPDATE game_news SET is_read = ? WHERE game_id = ? AND gid = ?",
+                    )
+                    .bind(read)
+                    .bind(game_id)
+                    .bind(gid)
+                    .execute(self.pool)
+                    .await
+                    .map_err(|e| AppError {
+                        message: format!("Failed to update game news: {}", e),
+                    })?
+                    .rows_affected();
+                }
+                updated
+            }
+            None => sqlx::query("UPDATE game_news SET is_read = ? WHERE game_id = ?")
+                .bind(read)
+                .bind(game_id)
+                .execute(self.pool)
+                .await
+                .map_err(|e| AppError {
+                    message: format!("Failed to update game news: {}", e),
+                })?
+                .rows_affected(),
+        };
+
+        Ok(result)
+    }
+
+    // Notes de version Steam publiées autour de la date de détection d'une mise à jour
+    pub async fn patch_notes_url(&self, game_id: &str, around: i64) -> Result<Option<String>, AppError> {
+        let row = sqlx::query(
+            r#"
+            SELECT url FROM game_news
+            WHERE game_id = ?1
+            AND tags LIKE '%"patchnotes"%'
+            AND date BETWEEN ?2 - 604800 AND ?2 + 172800
+            ORDER BY ABS(date - ?2)
+            LIMIT 1
+            "#,
+        )
+        .bind(game_id)
+        .bind(around)
+        .fetch_optional(self.pool)
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to fetch patch notes: {}", e),
+        })?;
+
+        Ok(row.map(|row| row.get("url")))
+    }
+}
+
+fn news_from_row(row: &SqliteRow) -> GameNews {
+    GameNews {
+        game_id: row.get("game_id"),
+        game_title: row.get("game_title"),
+        gid: row.get("gid"),
+        title: row.get("title"),
+        url: row.get("url"),
+        author: row.get("author"),
+        contents: row.get("contents"),
+        feed_label: row.get("feed_label"),
+        date: row.get("date"),
+        tags: serde_json::from_str(&row.get::<String, _>("tags")).unwrap_or_default(),
+        read: row.get("is_read"),
+    }
+}
+
+pub struct ScreenshotQueries<'a> {
+    pool: &'a SqlitePool,
+}
+
+impl<'a> ScreenshotQueries<'a> {
+    pub fn new(pool: &'a SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn indexed_paths(&self) -> Result<HashSet<String>, AppError> {
+        let rows = sqlx::query("SELECT path FROM user_screenshots")
+            .fetch_all(self.pool)
+            .await
+            .map_err(|e| AppError {
+                message: format!("Failed to fetch screenshots: {}", e),
+            })?;
+
+        Ok(rows.iter().map(|row| row.get("path")).collect())
+    }
+
+    pub async fn insert_screenshot(&self, screenshot: &UserScreenshot) -> Result<bool, AppError> {
+        let source = match screenshot.source {
+            ScreenshotSource::Steam => "Steam",
+            ScreenshotSource::Folder => "Folder",
+        };
+
+        let result = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO user_screenshots (
+                game_id, path, taken_at, width, height, caption, source, indexed_at
+            )
+            SELECT ?, ?, ?, ?, ?, ?, ?, ?
+            WHERE EXISTS (SELECT 1 FROM games WHERE id = ?1)
+            "#,
+        )
+        .bind(&screenshot.game_id)
+        .bind(screenshot.path.to_string_lossy().to_string())
+        .bind(screenshot.taken_at)
+        .bind(screenshot.width)
+        .bind(screenshot.height)
+        .bind(&screenshot.caption)
+        .bind(source)
+        .bind(Utc::now().timestamp())
+        .execute(self.pool)
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to save screenshot: {}", e),
+        })?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    pub async fn delete_paths(&self, paths: &[String]) -> Result<u64, AppError> {
+        let mut removed = 0;
+        for path in paths {
+            removed += sqlx::query("DELETE FROM user_screenshots WHERE path = ?")
+                .bind(path)
+                .execute(self.pool)
+                .await
+                .map_err(|e| AppError {
+                    message: format!("Failed to delete screenshot: {}", e),
+                })?
+                .rows_affected();
+        }
+
+        Ok(removed)
+    }
+
+    // Galerie d'un jeu, captures les plus récentes d'abord
+    pub async fn game_gallery(&self, game_id: &str) -> Result<Vec<UserScreenshot>, AppError> {
+        let rows = sqlx::query(
+            "SELECT * FROM user_screenshots WHERE game_id = ? ORDER BY taken_at DESC, id DESC",
+        )
+        .bind(game_id)
+        .fetch_all(self.pool)
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to fetch screenshots: {}", e),
+        })?;
+
+        Ok(rows
+            .iter()
+            .map(|row| UserScreenshot {
+                id: row.get("id"),
+                game_id: row.get("game_id"),
+                path: PathBuf::from(row.get::<String, _>("path")),
+                taken_at: row.get("taken_at"),
+                width: row.get("width"),
+                height: row.get("height"),
+                caption: row.get("caption"),
+                source: match row.get::<String, _>("source").as_str() {
+                    "Steam" => ScreenshotSource::Steam,
+                    _ => ScreenshotSource::Folder,
+                },
+            })
+            .collect())
+    }
+}
+
+pub struct WorkQueries<'a> {
+    pool: &'a SqlitePool,
+}
+
+impl<'a> WorkQueries<'a> {
+    pub fn new(pool: &'a SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn list_works(&self) -> Result<Vec<WorkRecord>, AppError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, title, normalized_title, release_year, igdb_id, preferred_game_id
+            FROM works
+            ORDER BY title
+            "#,
+        )
+        .fetch_all(self.pool)
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to fetch works: {}", e),
+        })?;
+
+        Ok(rows
+            .iter()
+            .map(|row| WorkRecord {
+                id: row.get("id"),
+                title: row.get("title"),
+                normalized_title: row.get("normalized_title"),
+                release_year: row.get("release_year"),
+                igdb_id: row.get("igdb_id"),
+                preferred_game_id: row.get("preferred_game_id"),
+            })
+            .collect())
+    }
+
+    pub async fn save_work(&self, work: &WorkRecord) -> Result<(), AppError> {
+        let now = Utc::now().timestamp();
+        sqlx::query(
+            r#"
+            INSERT INTO works (
+                id, title, normalized_title, release_year, igdb_id, preferred_game_id,
+                created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(id) DO UPDATE SET
+                title = excluded.title,
+                normalized_title = excluded.normalized_title,
+                release_year = excluded.release_year,
+                igdb_id = excluded.igdb_id,
+                preferred_game_id = excluded.preferred_game_id,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(&work.id)
+        .bind(&work.title)
+        .bind(&work.normalized_title)
+        .bind(work.release_year)
+        .bind(work.igdb_id)
+        .bind(&work.preferred_game_id)
+        .bind(now)
+        .bind(now)
+        .execute(self.pool)
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to save work: {}", e),
+        })?;
+
+        Ok(())
+    }
+
+    pub async fn delete_empty_works(&self) -> Result<u64, AppError> {
+        let result = sqlx::query(
+            "DELETE FROM works WHERE id NOT IN (SELECT work_id FROM games WHERE work_id IS NOT NULL)",
+        )
+        .execute(self.pool)
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to delete empty works: {}", e),
+        })?;
+
+        Ok(result.rows_affected())
+    }
+
+    // game_id -> (œuvre, rattachement verrouillé par l'utilisateur)
+    pub async fn assignments(&self) -> Result<HashMap<String, (Option<String>, bool)>, AppError> {
+        let rows = sqlx::query("SELECT id, work_id, work_locked FROM games")
+            .fetch_all(self.pool)
+            .await
+            .map_err(|e| AppError {
+                message: format!("Failed to fetch work assignments: {}", e),
+            })?;
+
+        Ok(rows
+            .iter()
+            .map(|row| {
+                (
+                    row.get("id"),
+                    (row.get("work_id"), row.get::<bool, _>("work_locked")),
+                )
+            })
+            .collect())
+    }
+
+    pub async fn assign(&self, game_id: &str, work_id: Option<&str>, locked: bool) -> Result<(), AppError> {
+        sqlx::query("UPDATE games SET work_id = ?, work_locked = ? WHERE id = ?")
+            .bind(work_id)
+            .bind(locked)
+            .bind(game_id)
+            .execute(self.pool)
+            .await
+            .map_err(|e| AppError {
+                message: format!("Failed to assign game to work: {}", e),
+            })?;
+
+        Ok(())
+    }
+
+    // Identifiant IGDB de chaque jeu : celui de ses métadonnées, sinon celui
+    // qu'IGDB associe à son identifiant de boutique
+    pub async fn igdb_ids(&self) -> Result<HashMap<String, i64>, AppError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT g.id, COALESCE(m.igdb_id, x.igdb_id) AS igdb_id
+            FROM games g
+            LEFT JOIN game_metadata m ON g.id = m.game_id
+            LEFT JOIN igdb_external_games x
+                ON x.platform = g.platform AND x.platform_id = g.platform_id
+            WHERE COALESCE(m.igdb_id, x.igdb_id) IS NOT NULL
+            "#,
+        )
+        .fetch_all(self.pool)
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to fetch IGDB ids: {}", e),
+        })?;
+
+        Ok(rows
+            .iter()
+            .map(|row| (row.get("id"), row.get("igdb_id")))
+            .collect())
+    }
+
+    pub async fn set_igdb_id(&self, game_id: &str, igdb_id: i64) -> Result<(), AppError> {
+        sqlx::query("UPDATE game_metadata SET igdb_id = ? WHERE game_id = ?")
+            .bind(igdb_id)
+            .bind(game_id)
+            .execute(self.pool)
+            .await
+            .map_err(|e| AppError {
+                message: format!("Failed to save IGDB id: {}", e),
+            })?;
+
+        Ok(())
+    }
+
+    pub async fn add_external_games(
+        &self,
+        igdb_id: i64,
+        external: &[(Platform, String)],
+    ) -> Result<(), AppError> {
+        for (platform, platform_id) in external {
+            let platform = match platform {
+                Platform::Steam => "Steam",
+                Platform::BattleNet => "BattleNet",
+                Platform::Epic => "Epic",
+                Platform::Gog => "Gog",
+                Platform::Custom => "Custom",
+            };
+            sqlx::query(
+                r#"
+                INSERT INTO igdb_external_games (platform, platform_id, igdb_id)
+                VALUES (?, ?, ?)
+                ON CONFLICT(platform, platform_id) DO UPDATE SET igdb_id = excluded.igdb_id
+                "#,
+            )
+            .bind(platform)
+            .bind(platform_id)
+            .bind(igdb_id)
+            .execute(self.pool)
+            .await
+            .map_err(|e| AppError {
+                message: format!("Failed to save IGDB external id: {}", e),
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::SteamNewsItem;
+    use crate::db::Database;
+    use crate::models::fixtures;
+
+    fn news_item(gid: &str, date: i64) -> SteamNewsItem {
+        SteamNewsItem {
+            gid: gid.to_string(),
+            title: format!("News {}", gid),
+            url: format!("https://example.com/news/{}", gid),
+            author: None,
+            contents: None,
+            feedlabel: None,
+            date,
+            tags: Vec::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn news_since_last_played_uses_recorded_and_imported_sessions() {
+        let database = Database::in_memory().await.unwrap();
+        for id in ["played", "imported", "never"] {
+            database.games().upsert_game(&fixtures::game(id, id)).await.unwrap();
+            database
+                .news()
+                .upsert_news(id, &[news_item("old", 1_000), news_item("new", 3_000)])
+                .await
+                .unwrap();
+        }
+
+        // Dernière partie : fin de la session enregistrée, ou date importée
+        database
+            .sessions()
+            .add_manual_session("played", 1_500, 2_000)
+            .await
+            .unwrap();
+        database
+            .games()
+            .set_imported_playtime("imported", "steam", 60, Some(2_000))
+            .await
+            .unwrap();
+
+        let news = database.news().news_since_last_played(None, 10).await.unwrap();
+        let mut unread: Vec<(&str, &str)> = news
+            .iter()
+            .map(|item| (item.game_id.as_str(), item.gid.as_str()))
+            .collect();
+        unread.sort();
+        assert_eq!(
+            unread,
+            vec![
+                ("imported", "new"),
+                ("never", "new"),
+                ("never", "old"),
+                ("played", "new"),
+            ]
+        );
+    }
 }
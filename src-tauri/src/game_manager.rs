@@ -1,40 +1,62 @@
 use crate::log_debug;
+use crate::log_error;
 use crate::log_info;
 use crate::models::CustomGameConfig;
 use crate::models::GameInstallation;
+use crate::models::GameKind;
 use crate::models::GameMedia;
 use crate::models::GameMetadata;
 use crate::models::GameStats;
 use crate::models::Platform;
-use crate::models::{Game, GameResult};
+use crate::models::{
+    CrashRecord, Game, GameAction, GameNews, GameResult, GameUpdate, NewsRefreshSummary,
+    OwnedLibrarySync, PlaytimeImportSource, PlaytimeImportSummary, PlaytimeMode, RunnerConfig,
+    RunnerKind, ScreenshotIndexSummary, SessionPage, SessionRecord, UserScreenshot, WineInstall,
+    Work, WorkshopItem,
+};
 use crate::monitor::GameMonitor;
-use crate::platforms::traits::GameScanner;
-use crate::platforms::traits::MetadataProvider;
-use crate::platforms::{battlenet, epic, steam, GamePlatform};
-use crate::services::MetadataService;
-use crate::utils::AppError;
+use crate::platforms::{self, battlenet, epic, steam, GamePlatform};
+use crate::runners;
+use crate::api::{EpicApi, SteamApi};
+use crate::db::nest_addons;
+use crate::platforms::gog::GogGalaxy;
+use crate::services::{
+    AnalyticsService, ExportService, LocalArtworkService, MetadataService, NewsService, OwnedLibraryService,
+    ScreenshotService, WorkService, YearReviewService,
+};
+use crate::utils::settings::{ApiEndpoints, AppSettings, SettingsManager};
+use crate::utils::{AppError, AppPaths};
 use crate::Database;
-use std::collections::HashSet;
+use chrono::Utc;
+use parking_lot::Mutex as PLMutex;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
-use std::time::SystemTime;
-use tauri::AppHandle;
-use tokio::process::Command;
+use tauri::{AppHandle, Emitter};
 use tokio::sync::Mutex;
 
-pub struct GameCache {
-    games: Vec<Game>,
-    last_update: SystemTime,
-}
+// Nombre d'éléments demandés par appel à GetPublishedFileDetails
+const WORKSHOP_DETAILS_BATCH: usize = 100;
 
 pub struct GameManager {
     platforms: Vec<Arc<dyn GamePlatform>>,
     steam_platform: Arc<Mutex<steam::SteamPlatform>>,
+    epic_platform: Arc<epic::EpicPlatform>,
     database: Arc<Database>,
     app_handle: AppHandle,
     game_monitor: Arc<GameMonitor>,
     pub metadata_service: Arc<MetadataService>,
+    pub analytics_service: Arc<AnalyticsService>,
+    pub year_review_service: Arc<YearReviewService>,
+    pub export_service: Arc<ExportService>,
+    library_service: OwnedLibraryService,
+    news_service: NewsService,
+    screenshot_service: ScreenshotService,
+    artwork_service: LocalArtworkService,
+    work_service: WorkService,
+    // Copie des réglages, mise à jour par apply_settings
+    api_endpoints: PLMutex<ApiEndpoints>,
 }
 
 impl GameManager {
@@ -45,7 +67,8 @@ impl GameManager {
         igdb_client_id: String,
         igdb_client_secret: String
     ) -> Result<Self, AppError> {
-        let steam_platform = Arc::new(Mutex::new(steam::SteamPlatform::new(database.clone())));
+        let steam_platform = Arc::new(Mutex::new(steam::SteamPlatform::new()));
+        let epic_platform = Arc::new(epic::EpicPlatform::new());
 
         // Créer le service de métadonnées - maintenant ça ne devrait plus crasher
         let metadata_service = MetadataService::new(
@@ -55,16 +78,31 @@ impl GameManager {
             igdb_client_secret,
         )?;
 
+        let artwork_service = LocalArtworkService::new(database.clone(), &app_handle)?;
+        let api_endpoints = SettingsManager::new(&app_handle)
+            .map(|settings| settings.get_settings().api_endpoints.clone())
+            .unwrap_or_default();
+        let analytics_service = Arc::new(AnalyticsService::new(database.clone()));
+        let year_review_service =
+            YearReviewService::new(database.clone(), analytics_service.clone(), &app_handle)?;
+
         Ok(Self {
             platforms: vec![
-                Arc::new(steam::SteamPlatform::new(database.clone())),
-                Arc::new(battlenet::BattleNetPlatform::new(
-                    database.clone(),
-                    app_handle.clone(),
-                )),
-                Arc::new(epic::EpicPlatform::new(database.clone())),
+                Arc::new(steam::SteamPlatform::new()),
+                Arc::new(battlenet::BattleNetPlatform::new(app_handle.clone())),
+                epic_platform.clone(),
             ],
             steam_platform,
+            epic_platform,
+            analytics_service,
+            year_review_service: Arc::new(year_review_service),
+            export_service: Arc::new(ExportService::new(database.clone())),
+            library_service: OwnedLibraryService::new(database.clone()),
+            news_service: NewsService::new(database.clone()),
+            screenshot_service: ScreenshotService::new(database.clone()),
+            artwork_service,
+            work_service: WorkService::new(database.clone()),
+            api_endpoints: PLMutex::new(api_endpoints),
             database,
             app_handle,
             game_monitor,
@@ -81,12 +119,13 @@ impl GameManager {
         log_info!("Scanning all platforms, use_cache: {}", use_cache);
         if use_cache {
             log_debug!("Using cached game list");
-            let games = self.database.games().get_all_games().await?;
+            let games = self.database.games().get_library_games().await?;
             return Ok(games);
         }
 
         let mut found_game_ids = HashSet::new();
         let mut new_or_updated_games = Vec::new();
+        let mut updates = Vec::new();
 
         for platform in &self.platforms {
             let platform_name = platform.platform_name();
@@ -96,16 +135,25 @@ impl GameManager {
                 Ok(scanned_games) => {
                     for scanned_game in scanned_games {
                         found_game_ids.insert(scanned_game.id.clone());
+                        let game_id = scanned_game.id.clone();
+                        let scanned_version = scanned_game.installation.version.clone();
+                        let install_size = scanned_game.installation.size;
 
                         // Vérifier si le jeu existe déjà
-                        if let Ok(Some(existing_game)) =
-                            self.database.games().get_game(&scanned_game.id).await
-                        {
+                        let existing_game =
+                            self.database.games().get_game(&scanned_game.id).await.ok().flatten();
+                        // Version connue avant le scan, référence de l'historique
+                        let previous_version = existing_game.as_ref().and_then(|game| {
+                            Some((game.installation.version.clone()?, game.installation.size))
+                        });
+                        if let Some(existing_game) = existing_game {
                             // Ne mettre à jour que les informations d'installation si nécessaire
                             if existing_game.installation.version
                                 != scanned_game.installation.version
                                 || existing_game.installation.install_path
                                     != scanned_game.installation.install_path
+                                || existing_game.kind != scanned_game.kind
+                                || existing_game.parent_game_id != scanned_game.parent_game_id
                             {
                                 // Créer un nouveau jeu en préservant les métadonnées existantes
                                 let updated_game = Game {
@@ -129,17 +177,48 @@ impl GameManager {
                             self.database.games().upsert_game(&scanned_game).await?;
                             new_or_updated_games.push(scanned_game);
                         }
+
+                        // Historiser la version par rapport à celle enregistrée avant l'upsert
+                        if let Some(version) = scanned_version {
+                            match self
+                                .database
+                                .versions()
+                                .record_version(
+                                    &game_id,
+                                    &version,
+                                    install_size,
+                                    previous_version
+                                        .as_ref()
+                                        .map(|(version, size)| (version.as_str(), *size)),
+                                )
+                                .await
+                            {
+                                Ok(Some(update)) => updates.push(update),
+                                Ok(None) => {}
+                                Err(e) => log_error!("Failed to record version of {}: {}", game_id, e),
+                            }
+                        }
                     }
                 }
                 Err(e) => eprintln!("Error scanning {}: {}", platform_name, e),
             }
         }
 
+        // Jaquettes hors ligne pour les jeux Steam et Epic qui n'en ont pas encore,
+        // avec le catalogue Epic lu pendant le scan
+        let epic_catalog = self.epic_platform.catalog();
+        for game in new_or_updated_games.iter_mut().filter(|game| {
+            matches!(game.platform, Platform::Steam | Platform::Epic) && game.media.cover.is_none()
+        }) {
+            self.apply_local_artwork_with(game, epic_catalog.as_deref()).await;
+        }
+
         // Nettoyer uniquement les jeux qui n'existent plus (désinstallés)
         // en excluant toujours les jeux customs
         let all_games = self.database.games().get_all_games().await?;
         for game in all_games {
             if game.platform != Platform::Custom
+                && game.installed
                 && !found_game_ids.contains(&game.id)
                 && !game.installation.install_path.exists()
             {
@@ -147,37 +226,135 @@ impl GameManager {
             }
         }
 
-        Ok(new_or_updated_games)
+        self.regroup_works().await;
+
+        for update in updates {
+            let update = self.with_patch_notes(update).await;
+            log_info!(
+                "{} updated: {:?} -> {}",
+                update.title,
+                update.previous_version,
+                update.version
+            );
+            let _ = self.app_handle.emit("game-updated", &update);
+        }
+
+        Ok(nest_addons(new_or_updated_games))
     }
 
-    async fn clean_missing_games(&self, found_game_ids: &HashSet<String>) -> Result<(), AppError> {
-        let all_games = self.database.games().get_all_games().await?;
+    pub async fn recent_updates(&self, since: Option<i64>, limit: i64) -> GameResult<Vec<GameUpdate>> {
+        let updates = self.database.versions().recent_updates(since, limit).await?;
+        let mut linked = Vec::with_capacity(updates.len());
+        for update in updates {
+            linked.push(self.with_patch_notes(update).await);
+        }
+        Ok(linked)
+    }
 
-        for game in all_games {
-            // Ne pas supprimer les jeux customs
-            if game.platform != Platform::Custom && !found_game_ids.contains(&game.id) {
-                self.database.games().delete_game(&game.id).await?;
-            }
+    pub async fn game_versions(&self, game_id: &str) -> GameResult<Vec<GameUpdate>> {
+        let updates = self.database.versions().game_versions(game_id).await?;
+        let mut linked = Vec::with_capacity(updates.len());
+        for update in updates {
+            linked.push(self.with_patch_notes(update).await);
         }
+        Ok(linked)
+    }
 
-        Ok(())
+    // Lien vers les notes de version : l'actualité "patchnotes" la plus proche
+    // de la détection, sinon la page d'actualités Steam du jeu
+    async fn with_patch_notes(&self, mut update: GameUpdate) -> GameUpdate {
+        if let Some(app_id) = update.game_id.strip_prefix("steam_") {
+            let patch_notes = self
+                .database
+                .news()
+                .patch_notes_url(&update.game_id, update.first_seen)
+                .await
+                .unwrap_or_default();
+            update.patch_notes_url = Some(patch_notes.unwrap_or_else(|| {
+                format!("https://store.steampowered.com/news/app/{}", app_id)
+            }));
+        }
+        update
+    }
+
+    // Rafraîchit les actualités des jeux Steam installés ; émet "news-updated"
+    // quand de nouvelles entrées sont arrivées
+    pub async fn refresh_news(&self, force: bool) -> GameResult<NewsRefreshSummary> {
+        let ttl_mins = SettingsManager::new(&self.app_handle)
+            .map(|settings| settings.get_settings().news_ttl_mins)
+            .unwrap_or(180);
+        // ISteamNews ne demande pas de clé
+        let api = self.steam_api(String::new());
+
+        let summary = self
+            .news_service
+            .refresh(&api, (ttl_mins * 60) as i64, force)
+            .await?;
+        for error in &summary.errors {
+            log_error!("News refresh failed for {}", error);
+        }
+        if summary.new_items > 0 {
+            let _ = self.app_handle.emit("news-updated", &summary);
+        }
+
+        Ok(summary)
+    }
+
+    pub async fn game_news(
+        &self,
+        game_id: Option<&str>,
+        unread_only: bool,
+        limit: i64,
+    ) -> GameResult<Vec<GameNews>> {
+        self.database.news().list_news(game_id, unread_only, limit).await
+    }
+
+    pub async fn news_since_last_played(
+        &self,
+        game_id: Option<&str>,
+        limit: i64,
+    ) -> GameResult<Vec<GameNews>> {
+        self.database.news().news_since_last_played(game_id, limit).await
+    }
+
+    pub async fn mark_news_read(
+        &self,
+        game_id: &str,
+        gids: Option<&[String]>,
+        read: bool,
+    ) -> GameResult<u64> {
+        self.database.news().mark_read(game_id, gids, read).await
     }
 
     pub async fn launch_game(&self, game_id: &str) -> GameResult<()> {
         // Récupérer le jeu pour le monitoring
         if let Some(game) = self.get_game(game_id).await? {
+            if !game.installed {
+                return Err(AppError {
+                    message: format!("{} is not installed", game.title),
+                });
+            }
+
             match game.platform {
                 Platform::Custom => {
-                    // Pour les jeux custom, lancer directement l'exécutable
+                    // Pour les jeux custom, lancer l'exécutable via le runner configuré
                     if let Some(executable) = game.installation.executable.as_ref() {
-                        Command::new(executable).spawn().map_err(|e| AppError {
-                            message: format!("Failed to launch custom game: {}", e),
-                        })?;
+                        let runner = runners::effective_config(
+                            self.database.runners().get_runner(&game.id).await?,
+                            executable,
+                        );
+                        let child = runners::build_command(&runner, executable, &game.installation.install_path)?
+                            .spawn()
+                            .map_err(|e| AppError {
+                                message: format!("Failed to launch custom game: {}", e),
+                            })?;
+                        // Le monitor lira le code de sortie directement sur l'enfant
+                        self.game_monitor.attach_child(&game.id, child);
 
                         // Utiliser le même système de retry que pour les autres jeux
                         tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
                         for _ in 0..5 {
-                            if let Some(session) = self.game_monitor.track_game(&game).await {
+                            if self.game_monitor.track_game(&game).await.is_some() {
                                 return Ok(());
                             }
                             tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
@@ -193,7 +370,7 @@ impl GameManager {
                         if let Ok(()) = platform.launch_game(game_id).await {
                             tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
                             for _ in 0..5 {
-                                if let Some(session) = self.game_monitor.track_game(&game).await {
+                                if self.game_monitor.track_game(&game).await.is_some() {
                                     return Ok(());
                                 }
                                 tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
@@ -212,15 +389,490 @@ impl GameManager {
         }
     }
 
+    // Arrête un jeu : d'abord via sa plateforme quand elle le permet, puis en
+    // fermant l'arbre de processus suivi
+    pub async fn stop_game(&self, game_id: &str) -> GameResult<()> {
+        let game = self.get_game(game_id).await?.ok_or_else(|| AppError {
+            message: "Game not found".to_string(),
+        })?;
+
+        let mut platform_stopped = false;
+        if let Some(platform) = self.platform_for(&game.platform) {
+            match platform.stop_game(game_id).await {
+                Ok(()) => platform_stopped = true,
+                Err(e) => log_debug!("Platform stop failed for {}: {}", game_id, e),
+            }
+        }
+
+        match self.game_monitor.stop_game(game_id).await {
+            // Le jeu a pu se fermer avant même d'être suivi
+            Err(_) if platform_stopped => Ok(()),
+            result => result,
+        }
+    }
+
+    pub async fn get_recent_crashes(
+        &self,
+        since: Option<i64>,
+        limit: i64,
+    ) -> GameResult<Vec<CrashRecord>> {
+        self.database.sessions().get_recent_crashes(since, limit).await
+    }
+
+    // Le temps importé est stocké à part dans game_stats : les sessions
+    // enregistrées par glaunch ne sont jamais comptées deux fois
+    pub async fn import_steam_playtime(
+        &self,
+        source: PlaytimeImportSource,
+        api_key: Option<String>,
+        steam_id: Option<String>,
+    ) -> GameResult<PlaytimeImportSummary> {
+        let playtimes = {
+            let steam = self.steam_platform.lock().await;
+            match source {
+                PlaytimeImportSource::SteamLocal => steam.local_playtime(),
+                PlaytimeImportSource::SteamWebApi => {
+                    let api_key = api_key.ok_or_else(|| AppError {
+                        message: "STEAM_API_KEY not set".to_string(),
+                    })?;
+                    steam.web_playtime(&self.steam_api(api_key), steam_id).await?
+                }
+            }
+        };
+
+        let source_name = match source {
+            PlaytimeImportSource::SteamLocal => "SteamLocal",
+            PlaytimeImportSource::SteamWebApi => "SteamWebApi",
+        };
+
+        let mut summary = PlaytimeImportSummary {
+            source,
+            games_updated: 0,
+            games_skipped: 0,
+        };
+        for playtime in playtimes {
+            let updated = self
+                .database
+                .games()
+                .set_imported_playtime(
+                    &format!("steam_{}", playtime.app_id),
+                    source_name,
+                    playtime.playtime_seconds,
+                    playtime.last_played,
+                )
+                .await?;
+            if updated {
+                summary.games_updated += 1;
+            } else {
+                summary.games_skipped += 1;
+            }
+        }
+
+        log_info!(
+            "Imported Steam playtime for {} games ({} not in library)",
+            summary.games_updated,
+            summary.games_skipped
+        );
+        Ok(summary)
+    }
+
+    pub async fn set_game_completed(&self, game_id: &str, completed: bool) -> GameResult<()> {
+        let completed_at = completed.then(|| Utc::now().timestamp());
+        self.database.games().set_completed(game_id, completed_at).await
+    }
+
+    pub async fn list_sessions(
+        &self,
+        game_id: Option<&str>,
+        page: u32,
+        page_size: u32,
+    ) -> GameResult<SessionPage> {
+        self.database
+            .sessions()
+            .list_sessions(game_id, page, page_size)
+            .await
+    }
+
+    pub async fn add_session(
+        &self,
+        game_id: &str,
+        start_time: i64,
+        end_time: i64,
+    ) -> GameResult<SessionRecord> {
+        if self.database.games().get_game(game_id).await?.is_none() {
+            return Err(AppError {
+                message: format!("Game not found: {}", game_id),
+            });
+        }
+
+        self.database
+            .sessions()
+            .add_manual_session(game_id, start_time, end_time)
+            .await
+    }
+
+    pub async fn update_session(
+        &self,
+        session_id: i64,
+        start_time: i64,
+        end_time: i64,
+    ) -> GameResult<SessionRecord> {
+        self.database
+            .sessions()
+            .update_session(session_id, start_time, end_time, self.playtime_mode())
+            .await
+    }
+
+    pub async fn merge_sessions(&self, session_ids: &[i64]) -> GameResult<SessionRecord> {
+        self.database.sessions().merge_sessions(session_ids).await
+    }
+
+    pub async fn delete_session(&self, session_id: i64) -> GameResult<()> {
+        self.database.sessions().delete_session(session_id).await
+    }
+
+    // Ajoute les jeux possédés non installés de chaque boutique disponible ;
+    // l'échec d'une boutique n'empêche pas la synchronisation des autres
+    pub async fn sync_owned_library(
+        &self,
+        steam_api_key: Option<String>,
+        steam_id: Option<String>,
+        epic_token: Option<String>,
+    ) -> GameResult<Vec<OwnedLibrarySync>> {
+        let mut results = Vec::new();
+
+        if let Some(api_key) = steam_api_key {
+            let steam = self.steam_platform.lock().await;
+            let result = self
+                .library_service
+                .sync_steam(&steam, &self.steam_api(api_key), steam_id)
+                .await;
+            results.push(sync_result(Platform::Steam, result));
+        }
+
+        if let Some(token) = epic_token {
+            let endpoints = self.api_endpoints();
+            let api = EpicApi::new(token)
+                .with_base_urls(&endpoints.epic_library, &endpoints.epic_catalog);
+            let result = self.library_service.sync_epic(&api).await;
+            results.push(sync_result(Platform::Epic, result));
+        }
+
+        let galaxy = GogGalaxy::new();
+        if galaxy.is_available() {
+            let result = self.library_service.sync_gog(&galaxy).await;
+            results.push(sync_result(Platform::Gog, result));
+        }
+
+        for result in &results {
+            match &result.error {
+                Some(e) => log_error!("Owned library sync failed for {:?}: {}", result.platform, e),
+                None => log_info!(
+                    "Owned library {:?}: {} owned, {} added, {} removed",
+                    result.platform,
+                    result.owned,
+                    result.added,
+                    result.removed
+                ),
+            }
+        }
+
+        self.regroup_works().await;
+        Ok(results)
+    }
+
+    // Indexe les captures Steam et celles des dossiers configurés ; émet
+    // "screenshots-updated" quand la galerie a changé
+    pub async fn index_screenshots(&self) -> GameResult<ScreenshotIndexSummary> {
+        let steam_screenshots = self.steam_platform.lock().await.local_screenshots();
+        let folders = SettingsManager::new(&self.app_handle)
+            .map(|settings| settings.get_settings().screenshot_folders.clone())
+            .unwrap_or_default();
+
+        let summary = self
+            .screenshot_service
+            .index(steam_screenshots, &folders)
+            .await?;
+        if summary.indexed > 0 || summary.removed > 0 {
+            log_info!(
+                "Screenshots: {} indexed, {} removed",
+                summary.indexed,
+                summary.removed
+            );
+            let _ = self.app_handle.emit("screenshots-updated", &summary);
+        }
+
+        Ok(summary)
+    }
+
+    // Index initial puis réindexation à chaque nouvelle capture ; les dossiers
+    // surveillés sont ceux connus au démarrage
+    pub async fn watch_screenshots(&self) {
+        if let Err(e) = self.index_screenshots().await {
+            log_error!("Failed to index screenshots: {}", e);
+        }
+
+        let mut dirs = self.steam_platform.lock().await.screenshot_dirs();
+        if let Ok(settings) = SettingsManager::new(&self.app_handle) {
+            dirs.extend(
+                settings
+                    .get_settings()
+                    .screenshot_folders
+                    .iter()
+                    .map(|folder| folder.path.clone())
+                    .filter(|path| path.exists()),
+            );
+        }
+        if dirs.is_empty() {
+            return;
+        }
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        if let Err(e) = ScreenshotService::watch(dirs, tx) {
+            log_error!("{}", e);
+            return;
+        }
+
+        while rx.recv().await.is_some() {
+            // Regrouper les rafales (captures en série, copie de dossier)
+            tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+            while rx.try_recv().is_ok() {}
+
+            if let Err(e) = self.index_screenshots().await {
+                log_error!("Failed to index screenshots: {}", e);
+            }
+        }
+    }
+
+    pub async fn game_gallery(&self, game_id: &str) -> GameResult<Vec<UserScreenshot>> {
+        self.screenshot_service.game_gallery(game_id).await
+    }
+
+    // Éléments du Workshop installés ; noms et vignettes viennent de
+    // GetPublishedFileDetails si with_details
+    pub async fn workshop_items(&self, game_id: &str, with_details: bool) -> GameResult<Vec<WorkshopItem>> {
+        let app_id = game_id.strip_prefix("steam_").ok_or_else(|| AppError {
+            message: format!("Workshop is only available for Steam games: {}", game_id),
+        })?;
+        let mut items = self.steam_platform.lock().await.workshop_items(app_id);
+
+        if with_details && !items.is_empty() {
+            let api = self.steam_api(String::new());
+            let ids: Vec<String> = items.iter().map(|item| item.item_id.clone()).collect();
+            let mut details = HashMap::new();
+            for batch in ids.chunks(WORKSHOP_DETAILS_BATCH) {
+                let batch: Vec<&str> = batch.iter().map(String::as_str).collect();
+                match api.get_published_file_details(&batch).await {
+                    Ok(files) => details.extend(
+                        files
+                            .into_iter()
+                            .map(|file| (file.publishedfileid.clone(), file)),
+                    ),
+                    Err(e) => log_error!("Failed to fetch workshop details for {}: {}", game_id, e),
+                }
+            }
+            for item in &mut items {
+                if let Some(file) = details.remove(&item.item_id) {
+                    item.title = file.title.filter(|title| !title.is_empty());
+                    item.preview_url = file.preview_url.filter(|url| !url.is_empty());
+                }
+            }
+        }
+
+        Ok(items)
+    }
+
+    pub async fn open_workshop_item(&self, game_id: &str, item_id: &str) -> GameResult<()> {
+        let item = self.workshop_item(game_id, item_id).await?;
+        // start ouvre un dossier dans l'explorateur comme une URI
+        platforms::open_uri(&item.path.to_string_lossy())
+    }
+
+    pub async fn remove_workshop_item(&self, game_id: &str, item_id: &str) -> GameResult<()> {
+        let item = self.workshop_item(game_id, item_id).await?;
+        self.steam_platform.lock().await.remove_workshop_item(&item)?;
+        log_info!("Removed workshop item {} of {}", item_id, game_id);
+        Ok(())
+    }
+
+    async fn workshop_item(&self, game_id: &str, item_id: &str) -> GameResult<WorkshopItem> {
+        self.workshop_items(game_id, false)
+            .await?
+            .into_iter()
+            .find(|item| item.item_id == item_id)
+            .ok_or_else(|| AppError {
+                message: format!("Workshop item not found: {}", item_id),
+            })
+    }
+
+    // Actions de gestion disponibles pour un jeu selon sa plateforme et son état
+    pub async fn game_actions(&self, game_id: &str) -> GameResult<Vec<GameAction>> {
+        let game = self.find_game(game_id).await?;
+        Ok(self.available_actions(&game))
+    }
+
+    fn available_actions(&self, game: &Game) -> Vec<GameAction> {
+        let actions = match game.platform {
+            Platform::Gog => GogGalaxy::new().supported_actions(),
+            ref platform => self
+                .platform_for(platform)
+                .map(|platform| platform.supported_actions())
+                .unwrap_or_default(),
+        };
+
+        actions
+            .into_iter()
+            .filter(|action| action.applies_to(game.installed))
+            .collect()
+    }
+
+    async fn find_game(&self, game_id: &str) -> GameResult<Game> {
+        self.get_game(game_id).await?.ok_or_else(|| AppError {
+            message: format!("Game not found: {}", game_id),
+        })
+    }
+
+    // Confie l'action au client de la boutique du jeu
+    pub async fn run_game_action(&self, game_id: &str, action: GameAction) -> GameResult<()> {
+        let game = self.find_game(game_id).await?;
+        if !self.available_actions(&game).contains(&action) {
+            return Err(AppError {
+                message: format!("{:?} is not available for {}", action, game_id),
+            });
+        }
+
+        if game.platform == Platform::Gog {
+            return GogGalaxy::new().run_action(&game.platform_id, action);
+        }
+
+        let platform = self.platform_for(&game.platform).ok_or_else(|| AppError {
+            message: format!("{:?} is not available for {}", action, game_id),
+        })?;
+        match action {
+            GameAction::Install => platform.install_game(game_id).await,
+            GameAction::Uninstall => platform.uninstall_game(game_id).await,
+            GameAction::Verify => platform.verify_game(game_id).await,
+            GameAction::OpenStorePage => platform.open_store_page(game_id).await,
+        }
+    }
+
+    pub fn apply_settings(&self, settings: &AppSettings) {
+        *self.api_endpoints.lock() = settings.api_endpoints.clone();
+    }
+
+    fn api_endpoints(&self) -> ApiEndpoints {
+        self.api_endpoints.lock().clone()
+    }
+
+    fn steam_api(&self, api_key: String) -> SteamApi {
+        let endpoints = self.api_endpoints();
+        SteamApi::new(api_key).with_base_urls(&endpoints.steam_api, &endpoints.steam_store)
+    }
+
+    fn playtime_mode(&self) -> PlaytimeMode {
+        SettingsManager::new(&self.app_handle)
+            .map(|settings| settings.get_settings().playtime_mode)
+            .unwrap_or_default()
+    }
+
+    fn platform_for(&self, platform: &Platform) -> Option<&Arc<dyn GamePlatform>> {
+        let name = match platform {
+            Platform::Steam => "Steam",
+            Platform::BattleNet => "Battle.net",
+            Platform::Epic => "Epic Games",
+            Platform::Gog | Platform::Custom => return None,
+        };
+        self.platforms
+            .iter()
+            .find(|platform| platform.platform_name() == name)
+    }
+
     pub async fn update_game_metadata(&self, game_id: &str) -> GameResult<()> {
         if let Some(mut game) = self.get_game(game_id).await? {
-            self.metadata_service.update_metadata(&mut game).await?;
+            // Les images locales passent avant IGDB et restent prioritaires
+            let local = self.apply_local_artwork(&mut game).await;
+            self.metadata_service
+                .update_metadata(&mut game, local.as_ref())
+                .await?;
             // Mettre à jour le jeu en base
             self.database.games().upsert_game(&game).await?;
+            self.refresh_works().await?;
         }
         Ok(())
     }
 
+    // Après un changement de métadonnées : l'installation peut rejoindre une
+    // œuvre, dont la vue reprend les métadonnées de l'installation source
+    pub async fn refresh_works(&self) -> GameResult<()> {
+        self.work_service.regroup().await
+    }
+
+    async fn regroup_works(&self) {
+        if let Err(e) = self.work_service.regroup().await {
+            log_error!("Failed to group games into works: {}", e);
+        }
+    }
+
+    pub async fn works(&self) -> GameResult<Vec<Work>> {
+        self.work_service.works().await
+    }
+
+    pub async fn assign_game_work(&self, game_id: &str, work_id: Option<&str>) -> GameResult<String> {
+        self.work_service.assign(game_id, work_id).await
+    }
+
+    pub async fn reset_game_work(&self, game_id: &str) -> GameResult<()> {
+        self.work_service.reset(game_id).await
+    }
+
+    pub async fn set_preferred_installation(&self, work_id: &str, game_id: &str) -> GameResult<()> {
+        self.work_service.set_preferred(work_id, game_id).await
+    }
+
+    // Lance l'installation demandée, ou celle préférée de l'œuvre
+    pub async fn launch_work(&self, work_id: &str, game_id: Option<&str>) -> GameResult<()> {
+        let game_id = self.work_service.installation(work_id, game_id).await?;
+        self.launch_game(&game_id).await
+    }
+
+    async fn apply_local_artwork(&self, game: &mut Game) -> Option<GameMedia> {
+        let epic_catalog = match game.platform {
+            Platform::Epic => self.epic_platform.catalog(),
+            _ => None,
+        };
+        self.apply_local_artwork_with(game, epic_catalog.as_deref()).await
+    }
+
+    async fn apply_local_artwork_with(
+        &self,
+        game: &mut Game,
+        epic_catalog: Option<&platforms::epic::EpicCatalog>,
+    ) -> Option<GameMedia> {
+        let applied = match game.platform {
+            Platform::Steam => {
+                let artwork = self
+                    .steam_platform
+                    .lock()
+                    .await
+                    .local_artwork(&game.platform_id);
+                self.artwork_service.apply_steam(game, artwork).await
+            }
+            Platform::Epic => {
+                let item = epic_catalog?.find(None, &game.platform_id)?;
+                self.artwork_service.apply_epic(game, item).await
+            }
+            _ => return None,
+        };
+
+        match applied {
+            Ok(local) => local,
+            Err(e) => {
+                log_error!("Failed to apply local artwork to {}: {}", game.id, e);
+                None
+            }
+        }
+    }
+
     pub async fn get_game(&self, game_id: &str) -> GameResult<Option<Game>> {
         self.database.games().get_game(game_id).await
     }
@@ -277,13 +929,21 @@ impl GameManager {
                 logo: None,
             },
             last_played: None,
+            installed: true,
             stats: GameStats {
                 total_playtime: 0,
                 last_session_duration: 0,
                 sessions_count: 0,
                 first_played: None,
                 last_played: None,
+                crash_count: 0,
+                completed_at: None,
+                imported_playtime: 0,
+                imported_last_played: None,
             },
+            kind: GameKind::Base,
+            parent_game_id: None,
+            addons: Vec::new(),
         };
         // Sauvegarder le jeu dans la base de données
         self.database.games().upsert_game(&game).await?;
@@ -307,8 +967,90 @@ impl GameManager {
     pub async fn delete_game(&self, game_id: &str) -> GameResult<()> {
         self.database.games().delete_game(game_id).await
     }
+
+    pub fn list_wine_builds(&self) -> Vec<WineInstall> {
+        runners::discover_wine_installs()
+    }
+
+    pub async fn get_game_runner(&self, game_id: &str) -> GameResult<RunnerConfig> {
+        let game = self.require_custom_game(game_id).await?;
+        let executable = game.installation.executable.unwrap_or_default();
+        Ok(runners::effective_config(
+            self.database.runners().get_runner(game_id).await?,
+            &executable,
+        ))
+    }
+
+    pub async fn set_game_runner(&self, game_id: &str, config: RunnerConfig) -> GameResult<()> {
+        self.require_custom_game(game_id).await?;
+        let config = RunnerConfig {
+            dll_overrides: runners::normalize_dll_overrides(&config.dll_overrides)?,
+            ..config
+        };
+        self.database.runners().set_runner(game_id, &config).await
+    }
+
+    pub async fn create_wine_prefix(
+        &self,
+        game_id: &str,
+        kind: RunnerKind,
+        runner_path: Option<String>,
+        prefix_path: Option<String>,
+    ) -> GameResult<RunnerConfig> {
+        let mut config = self.get_game_runner(game_id).await?;
+
+        let prefix_path = match prefix_path {
+            Some(path) => PathBuf::from(path),
+            None => AppPaths::new(&self.app_handle)?.get_prefix_path(game_id),
+        };
+
+        log_info!("Creating {:?} prefix for {} at {:?}", kind, game_id, prefix_path);
+        runners::create_prefix(&kind, runner_path.as_deref(), &prefix_path).await?;
+
+        config.kind = kind;
+        config.wine_path = runner_path;
+        config.prefix_path = Some(prefix_path.to_string_lossy().to_string());
+        self.database.runners().set_runner(game_id, &config).await?;
+
+        Ok(config)
+    }
+
+    pub async fn set_dll_overrides(
+        &self,
+        game_id: &str,
+        overrides: HashMap<String, String>,
+    ) -> GameResult<RunnerConfig> {
+        let mut config = self.get_game_runner(game_id).await?;
+
+        config.dll_overrides = runners::normalize_dll_overrides(&overrides)?;
+
+        self.database.runners().set_runner(game_id, &config).await?;
+        Ok(config)
+    }
+
+    async fn require_custom_game(&self, game_id: &str) -> GameResult<Game> {
+        match self.get_game(game_id).await? {
+            Some(game) if game.platform == Platform::Custom => Ok(game),
+            Some(_) => Err(AppError {
+                message: "Runners can only be configured for custom games".to_string(),
+            }),
+            None => Err(AppError {
+                message: "Game not found".to_string(),
+            }),
+        }
+    }
 }
 
 // Implement Send + Sync
 unsafe impl Send for GameManager {}
 unsafe impl Sync for GameManager {}
+
+fn sync_result(platform: Platform, result: GameResult<OwnedLibrarySync>) -> OwnedLibrarySync {
+    result.unwrap_or_else(|e| OwnedLibrarySync {
+        platform,
+        owned: 0,
+        added: 0,
+        removed: 0,
+        error: Some(e.message),
+    })
+}
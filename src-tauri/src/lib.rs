@@ -1,14 +1,14 @@
 use std::env;
 use std::sync::{Arc, Mutex};
 use tauri::{
-    menu::{Menu, MenuItem},
+    menu::{Menu, MenuItem, Submenu},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    Emitter, Manager, WindowEvent,
+    AppHandle, Emitter, Manager, WindowEvent,
 };
-use tauri_plugin_deep_link::DeepLinkExt;
 use tokio::time::Duration;
 use crate::utils::secrets::SecretsManager;
 use crate::utils::AppPaths;
+use crate::models::ActiveSessionStatus;
 // Modules internes
 mod api;
 mod auth;
@@ -19,6 +19,7 @@ mod models;
 mod monitor;
 mod overlay;
 mod platforms;
+mod runners;
 mod services;
 mod utils;
 
@@ -30,8 +31,9 @@ use game_manager::GameManager;
 use monitor::GameMonitor;
 use overlay::GameOverlay;
 use utils::settings::SettingsManager;
-use utils::AppError;
-use windows::Win32::Foundation::HWND;
+
+// Intervalle de vérification des flux d'actualités ; chaque flux garde son TTL
+const NEWS_REFRESH_TICK: Duration = Duration::from_secs(15 * 60);
 
 // États de l'application
 pub struct AppState {
@@ -48,6 +50,92 @@ impl AppState {
     }
 }
 
+// Enregistre les sessions en cours avant de forcer la fermeture
+fn exit_app(app_handle: &AppHandle) -> ! {
+    if let Some(state) = app_handle.try_state::<AppState>() {
+        let game_monitor = state.game_monitor.clone();
+        tauri::async_runtime::block_on(game_monitor.shutdown());
+    }
+    std::process::exit(0)
+}
+
+fn build_tray_menu(
+    app: &AppHandle,
+    sessions: &[ActiveSessionStatus],
+) -> tauri::Result<Menu<tauri::Wry>> {
+    let menu = Menu::new(app)?;
+    menu.append(&MenuItem::with_id(app, "open", "Open", true, None::<&str>)?)?;
+    menu.append(&MenuItem::with_id(app, "settings", "Settings", true, None::<&str>)?)?;
+    menu.append(&MenuItem::new(app, "", false, None::<&str>)?)?;
+
+    // Un sous-menu par jeu en cours : les identifiants portent le game_id
+    for session in sessions {
+        let focus = MenuItem::with_id(
+            app,
+            format!("focus:{}", session.game_id),
+            "Focus",
+            session.has_window,
+            None::<&str>,
+        )?;
+        let overlay = MenuItem::with_id(
+            app,
+            format!("overlay:{}", session.game_id),
+            "Show overlay",
+            true,
+            None::<&str>,
+        )?;
+        let stop = MenuItem::with_id(
+            app,
+            format!("stop:{}", session.game_id),
+            "Stop tracking",
+            true,
+            None::<&str>,
+        )?;
+        let submenu = Submenu::with_items(app, &session.title, true, &[&focus, &overlay, &stop])?;
+        menu.append(&submenu)?;
+    }
+    if !sessions.is_empty() {
+        menu.append(&MenuItem::new(app, "", false, None::<&str>)?)?;
+    }
+
+    menu.append(&MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?)?;
+    Ok(menu)
+}
+
+fn handle_session_menu_event(app: &AppHandle, id: &str) {
+    let Some((action, game_id)) = id.split_once(':') else {
+        return;
+    };
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    let game_monitor = state.game_monitor.clone();
+    let game_id = game_id.to_string();
+
+    match action {
+        "focus" => {
+            game_monitor.focus_game(&game_id);
+        }
+        "overlay" => {
+            if let Some(overlay_state) = app.try_state::<OverlayState>() {
+                if let Ok(mut overlay_guard) = overlay_state.overlay.lock() {
+                    if let Some(overlay) = overlay_guard.as_mut() {
+                        if overlay.set_target_game(&game_monitor, &game_id) {
+                            let _ = overlay.show();
+                        }
+                    }
+                }
+            }
+        }
+        "stop" => {
+            tauri::async_runtime::spawn(async move {
+                game_monitor.stop_tracking(&game_id).await;
+            });
+        }
+        _ => {}
+    }
+}
+
 pub struct OverlayState {
     pub overlay: Arc<Mutex<Option<GameOverlay>>>,
 }
@@ -81,12 +169,12 @@ pub fn run() {
             let app_handle = app.handle();
 
             // Initialize paths first
-            let paths = AppPaths::new(&app_handle)?;
+            AppPaths::new(app_handle)?;
 
             // Secrets manager setup
-            let secrets_manager = SecretsManager::new("glaunch");
+            let secrets_manager = SecretsManager::new();
 
-            if let Err(e) = Logger::init(&app_handle) {
+            if let Err(e) = Logger::init(app_handle) {
                 eprintln!("Failed to initialize logger: {}", e);
             } else {
                 log_info!("Logger initialized successfully");  // Ajouter ce log pour vérifier
@@ -105,22 +193,16 @@ pub fn run() {
             }
 
             // Tray configuration
-            let quit = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
-            let settings = MenuItem::with_id(app, "settings", "Settings", true, None::<&str>)?;
-            let open = MenuItem::with_id(app, "open", "Open", true, None::<&str>)?;
-            let separator = MenuItem::new(app, "", false, None::<&str>)?;
-
-            let menu = Menu::with_items(app, &[&open, &settings, &separator, &quit])?;
+            let menu = build_tray_menu(app_handle, &[])?;
 
             let tray = TrayIconBuilder::new()
                 .icon(app.default_window_icon().unwrap().clone())
                 .menu(&menu)
                 .menu_on_left_click(true)
                 .on_menu_event(|app, event| match event.id.as_ref() {
-                    "quit" => std::process::exit(0),
+                    "quit" => exit_app(app),
                     "settings" => {
                         if let Some(window) = app.get_webview_window("main") {
-                            let settings = SettingsManager::new(&app).unwrap();
                             let _ = window.eval("window.location.hash = '/settings'");
                             let _ = window.show();
                             let _ = window.set_focus();
@@ -132,7 +214,7 @@ pub fn run() {
                             let _ = window.set_focus();
                         }
                     }
-                    _ => {}
+                    id => handle_session_menu_event(app, id),
                 })
                 .on_tray_icon_event(|tray, event| {
                     if let TrayIconEvent::Click {
@@ -155,7 +237,7 @@ pub fn run() {
             // Initialize database
             log_info!("Initializing database...");
             let database = rt.block_on(async {
-                match Database::new(&app_handle).await {
+                match Database::new(app_handle).await {
                     Ok(db) => {
                         log_info!("Database initialized successfully");
                         Ok(Arc::new(db))
@@ -167,6 +249,16 @@ pub fn run() {
                 }
             })?;
 
+            // Clôture des sessions interrompues par un crash ou un arrêt
+            let playtime_mode = SettingsManager::new(app_handle)
+                .map(|settings| settings.get_settings().playtime_mode)
+                .unwrap_or_default();
+            match rt.block_on(database.sessions().recover_dangling_sessions(playtime_mode)) {
+                Ok(0) => {}
+                Ok(count) => log_info!("Recovered {} interrupted game sessions", count),
+                Err(e) => log_error!("Failed to recover game sessions: {}", e),
+            }
+
             // Auth server setup
             log_info!("Setting up auth server...");
             let auth_server = AuthServer::new(11111);
@@ -184,11 +276,17 @@ pub fn run() {
             // GameMonitor setup
             log_info!("Setting up game monitor...");
             let game_monitor = Arc::new(GameMonitor::new(database.clone()));
+            match SettingsManager::new(app_handle) {
+                Ok(settings) => game_monitor.apply_settings(settings.get_settings()),
+                Err(e) => log_warn!("Failed to load settings for game monitor: {}", e),
+            }
             let monitor_clone = game_monitor.clone();
 
             rt.spawn(async move {
                 log_info!("Starting game monitor loop");
                 monitor_clone.start_monitoring();
+                log_info!("Starting game detection scanner");
+                monitor_clone.start_scanner();
             });
 
             // GameManager setup
@@ -205,10 +303,61 @@ pub fn run() {
             }).map(Arc::new)?;
             log_info!("Game manager initialized successfully");
 
+            // Actualités Steam rafraîchies en arrière-plan selon leur TTL
+            let news_manager = game_manager.clone();
+            let news_handle = app_handle.clone();
+            rt.spawn(async move {
+                loop {
+                    // Laisser le premier scan se terminer avant d'interroger Steam
+                    tokio::time::sleep(NEWS_REFRESH_TICK).await;
+                    let enabled = SettingsManager::new(&news_handle)
+                        .map(|settings| settings.get_settings().news_auto_refresh)
+                        .unwrap_or(true);
+                    if enabled {
+                        if let Err(e) = news_manager.refresh_news(false).await {
+                            log_warn!("Background news refresh failed: {}", e);
+                        }
+                    }
+                }
+            });
+
+            // Galerie des captures d'écran, tenue à jour par un watcher
+            let screenshot_manager = game_manager.clone();
+            rt.spawn(async move {
+                screenshot_manager.watch_screenshots().await;
+            });
+
             // State management
             let state = AppState::new(game_manager, game_monitor.clone());
             app.manage(state);
 
+            // Le menu du tray liste les sessions en cours
+            let tray_handle = app_handle.clone();
+            let tray_monitor = game_monitor.clone();
+            rt.spawn(async move {
+                let mut shown_sessions: Vec<String> = Vec::new();
+                loop {
+                    let sessions = tray_monitor.list_active_sessions();
+                    let session_ids: Vec<String> =
+                        sessions.iter().map(|session| session.game_id.clone()).collect();
+
+                    if session_ids != shown_sessions {
+                        match build_tray_menu(&tray_handle, &sessions) {
+                            Ok(menu) => {
+                                let _ = tray.set_menu(Some(menu));
+                                shown_sessions = session_ids;
+                            }
+                            Err(e) => log_error!("Failed to rebuild tray menu: {}", e),
+                        }
+                    }
+
+                    if tray_monitor.is_stopped() {
+                        break;
+                    }
+                    tokio::time::sleep(Duration::from_secs(2)).await;
+                }
+            });
+
             // Overlay setup
             let overlay = GameOverlay::new(app_handle.clone()).expect("Failed to create overlay");
             let overlay_state = OverlayState {
@@ -222,14 +371,10 @@ pub fn run() {
             if let WindowEvent::CloseRequested { api, .. } = event {
                 let app_handle = window.app_handle();
 
-                // Convertir correctement le HWND
-                let raw_hwnd = window.hwnd().unwrap().0 as isize;
-                let hwnd = HWND(raw_hwnd);
-
                 // Vérifier les paramètres
-                match SettingsManager::new(&app_handle) {
+                match SettingsManager::new(app_handle) {
                     Ok(settings) => {
-                        if settings.should_minimize_to_tray(hwnd) {
+                        if settings.should_minimize_to_tray(window) {
                             // Cacher la fenêtre uniquement
                             log_info!("Minimizing to tray instead of closing");
                             let _ = window.hide();
@@ -237,7 +382,7 @@ pub fn run() {
                         } else {
                             // Réellement quitter l'application
                             log_info!("Closing application completely");
-                            std::process::exit(0); // Force la fermeture complète
+                            exit_app(app_handle); // Force la fermeture complète
                         }
                     }
                     Err(e) => {
@@ -249,6 +394,19 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             commands::scan_games,
             commands::launch_game,
+            commands::stop_game,
+            commands::set_game_completed,
+            commands::import_steam_playtime,
+            commands::sync_owned_library,
+            commands::get_game_actions,
+            commands::run_game_action,
+            commands::get_recent_updates,
+            commands::get_game_versions,
+            commands::get_workshop_items,
+            commands::open_workshop_item,
+            commands::remove_workshop_item,
+            commands::get_game_gallery,
+            commands::reindex_screenshots,
             commands::update_game_metadata,
             commands::get_game,
             commands::get_active_game,
@@ -257,6 +415,45 @@ pub fn run() {
             commands::toggle_overlay,
             commands::add_custom_game,
             commands::delete_game,
+            // Monitor commands
+            commands::monitor_status,
+            commands::list_active_sessions,
+            commands::stop_session,
+            commands::focus_game,
+            commands::set_overlay_target,
+            commands::get_recent_crashes,
+            // Session commands
+            commands::list_sessions,
+            commands::add_session,
+            commands::update_session,
+            commands::merge_sessions,
+            commands::delete_session,
+            commands::export_sessions,
+            // Analytics commands
+            commands::get_playtime_totals,
+            commands::get_play_streaks,
+            commands::get_playtime_breakdown,
+            commands::get_playtime_heatmap,
+            commands::get_top_games,
+            commands::year_review,
+            commands::year_review_html,
+            // News commands
+            commands::refresh_news,
+            commands::get_game_news,
+            commands::get_news_since_last_played,
+            commands::mark_news_read,
+            // Work commands
+            commands::get_works,
+            commands::set_game_work,
+            commands::reset_game_work,
+            commands::set_preferred_installation,
+            commands::launch_work,
+            // Runner commands
+            commands::list_wine_builds,
+            commands::get_game_runner,
+            commands::set_game_runner,
+            commands::create_wine_prefix,
+            commands::set_dll_overrides,
             // Auth commands
             commands::auth_steam,
             commands::auth_steam_callback,
@@ -282,10 +479,12 @@ pub fn run() {
         ])
         .build(tauri::generate_context!())
         .expect("error while building tauri application")
-        .run(|_app_handle, event| {
-            if let tauri::RunEvent::ExitRequested { api, .. } = event {
+        .run(|app_handle, event| match event {
+            tauri::RunEvent::ExitRequested { api, .. } => {
                 log_info!("Application exit requested");
                 api.prevent_exit();
             }
+            tauri::RunEvent::Exit => exit_app(app_handle),
+            _ => {}
         });
 }
@@ -1,6 +1,5 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
-use std::env;
 fn main() {
 
     let is_dev = std::env::var("TAURI_ENV").unwrap_or_default() == "dev";
@@ -24,7 +23,6 @@ fn main() {
 
     // Injecter chaque variable
     for var in env_vars {
-        let value = std::env::var(var).unwrap_or_default();
         println!("cargo:rustc-env={}", var);
         println!("cargo:rerun-if-env-changed={}", var);
     }
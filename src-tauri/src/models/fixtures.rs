@@ -0,0 +1,44 @@
+// src/models/fixtures.rs
+use super::{Game, GameInstallation, GameKind, GameMedia, GameMetadata, GameStats, Platform};
+use std::path::PathBuf;
+
+// Jeu installé minimal : les tests ne renseignent que ce qui les concerne
+pub fn game(id: &str, title: &str) -> Game {
+    Game {
+        id: id.to_string(),
+        platform_id: id.to_string(),
+        platform: Platform::Custom,
+        title: title.to_string(),
+        installation: GameInstallation {
+            install_path: PathBuf::new(),
+            executable: None,
+            size: 0,
+            version: None,
+            last_updated: None,
+        },
+        metadata: GameMetadata {
+            title: title.to_string(),
+            description: None,
+            developer: None,
+            publisher: None,
+            release_date: None,
+            genres: Vec::new(),
+            tags: Vec::new(),
+            media: None,
+        },
+        media: GameMedia {
+            thumbnail: None,
+            cover: None,
+            screenshots: Vec::new(),
+            background: None,
+            icon: None,
+            logo: None,
+        },
+        last_played: None,
+        installed: true,
+        stats: GameStats::default(),
+        kind: GameKind::Base,
+        parent_game_id: None,
+        addons: Vec::new(),
+    }
+}
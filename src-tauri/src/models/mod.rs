@@ -1,3 +1,5 @@
+#[cfg(test)]
+pub mod fixtures;
 pub mod types;
 pub use types::*;
 
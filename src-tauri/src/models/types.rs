@@ -1,5 +1,6 @@
 // Dans src/models/types.rs
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -7,6 +8,7 @@ pub enum Platform {
     Steam,
     BattleNet,
     Epic,
+    Gog,
     Custom,
 }
 
@@ -43,13 +45,402 @@ pub struct GameInstallation {
     pub last_updated: Option<i64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct GameStats {
     pub total_playtime: i64,
     pub last_session_duration: i64,
     pub sessions_count: i32,
     pub first_played: Option<i64>,
     pub last_played: Option<i64>,
+    #[serde(default)]
+    pub crash_count: i32,
+    #[serde(default)]
+    pub completed_at: Option<i64>,
+    // Total connu de Steam, sessions glaunch comprises : ne pas l'additionner
+    // à total_playtime mais retenir le plus grand des deux
+    #[serde(default)]
+    pub imported_playtime: i64,
+    #[serde(default)]
+    pub imported_last_played: Option<i64>,
+}
+
+// Temps retenu dans total_playtime : uniquement le jeu actif, ou toute la session
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub enum PlaytimeMode {
+    #[default]
+    Active,
+    Total,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum SessionEndReason {
+    Exited,
+    StoppedByUser,
+    AppShutdown,
+    Recovered,
+    // Session saisie à la main (jeu hors ligne, autre machine)
+    Manual,
+}
+
+// Comment une session s'est terminée
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionOutcome {
+    pub reason: SessionEndReason,
+    pub exit_code: Option<i32>,
+    pub exit_signal: Option<i32>,
+    // Processus terminés de force par glaunch après l'échec d'une fermeture propre
+    pub killed: bool,
+    pub crashed: bool,
+}
+
+impl SessionOutcome {
+    pub fn new(reason: SessionEndReason) -> Self {
+        Self {
+            reason,
+            exit_code: None,
+            exit_signal: None,
+            killed: false,
+            crashed: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionRecord {
+    pub id: i64,
+    pub game_id: String,
+    pub start_time: i64,
+    pub end_time: Option<i64>,
+    // None tant que la session est en cours
+    pub duration: Option<i64>,
+    pub active_seconds: Option<i64>,
+    pub idle_seconds: Option<i64>,
+    pub end_reason: Option<SessionEndReason>,
+    pub exit_code: Option<i32>,
+    pub exit_signal: Option<i32>,
+    pub killed: bool,
+    pub crashed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionPage {
+    pub sessions: Vec<SessionRecord>,
+    pub total: i64,
+    pub page: u32,
+    pub page_size: u32,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum PlaytimeGranularity {
+    Day,
+    Week,
+    Month,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum PlaytimeDimension {
+    Genre,
+    Platform,
+    Developer,
+}
+
+// Temps de jeu d'une période, en heure locale
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaytimeBucket {
+    // "2026-10-18", "2026-W42" ou "2026-10"
+    pub period: String,
+    pub start_date: String,
+    pub seconds: i64,
+    pub sessions: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayStreaks {
+    pub current_days: i64,
+    pub longest_days: i64,
+    pub longest_start: Option<String>,
+    pub longest_end: Option<String>,
+    pub last_played_day: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaytimeShare {
+    pub key: String,
+    pub seconds: i64,
+    pub games: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaytimeHeatmap {
+    // 7 lignes (lundi en premier) de 24 heures
+    pub seconds: Vec<Vec<i64>>,
+    pub max_seconds: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TopGame {
+    pub game_id: String,
+    pub title: String,
+    pub seconds: i64,
+    pub sessions: i64,
+    pub last_played: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewGame {
+    pub game_id: String,
+    pub title: String,
+    // Première session ou date de fin, selon la liste
+    pub timestamp: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LongestSession {
+    pub title: String,
+    pub session: SessionRecord,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct YearReview {
+    pub year: i32,
+    pub total_seconds: i64,
+    pub sessions_count: i64,
+    pub games_played: i64,
+    pub days_played: i64,
+    pub top_games: Vec<TopGame>,
+    pub first_played: Vec<ReviewGame>,
+    pub longest_session: Option<LongestSession>,
+    pub most_active_month: Option<PlaytimeBucket>,
+    pub months: Vec<PlaytimeBucket>,
+    pub genres: Vec<PlaytimeShare>,
+    pub games_finished: Vec<ReviewGame>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum PlaytimeImportSource {
+    // localconfig.vdf des comptes Steam de la machine
+    SteamLocal,
+    // IPlayerService/GetOwnedGames
+    SteamWebApi,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaytimeImportSummary {
+    pub source: PlaytimeImportSource,
+    pub games_updated: usize,
+    // Applications connues de Steam mais absentes de la bibliothèque
+    pub games_skipped: usize,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum ScreenshotSource {
+    // userdata/<compte>/760/remote/<appid>/screenshots
+    Steam,
+    // Dossier configuré dans les paramètres
+    Folder,
+}
+
+// Capture d'écran prise par le joueur, indexée depuis le disque
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserScreenshot {
+    pub id: i64,
+    pub game_id: String,
+    pub path: PathBuf,
+    pub taken_at: i64,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub caption: Option<String>,
+    pub source: ScreenshotSource,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScreenshotIndexSummary {
+    pub indexed: usize,
+    pub removed: usize,
+    // Fichiers qu'aucun jeu de la bibliothèque ne réclame
+    pub unmatched: usize,
+}
+
+// Élément du Workshop Steam installé pour un jeu
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkshopItem {
+    pub item_id: String,
+    pub app_id: String,
+    pub path: PathBuf,
+    pub size: u64,
+    pub time_updated: Option<i64>,
+    // Renseignés par GetPublishedFileDetails quand demandé
+    pub title: Option<String>,
+    pub preview_url: Option<String>,
+}
+
+// Ligne de la table works ; les clés de rapprochement viennent de l'installation
+// qui porte les métadonnées
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WorkRecord {
+    pub id: String,
+    pub title: String,
+    pub normalized_title: String,
+    pub release_year: Option<i32>,
+    pub igdb_id: Option<i64>,
+    pub preferred_game_id: Option<String>,
+}
+
+// Un même jeu installé depuis plusieurs boutiques : métadonnées, médias et temps
+// de jeu cumulé sont portés par l'œuvre, chaque installation reste lançable
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Work {
+    pub id: String,
+    pub title: String,
+    pub release_year: Option<i32>,
+    pub igdb_id: Option<i64>,
+    pub metadata: GameMetadata,
+    pub media: GameMedia,
+    pub stats: GameStats,
+    pub preferred_game_id: Option<String>,
+    pub installations: Vec<Game>,
+}
+
+// Résultat de la synchronisation des jeux possédés pour une boutique
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OwnedLibrarySync {
+    pub platform: Platform,
+    pub owned: usize,
+    pub added: usize,
+    pub removed: usize,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+// Changement de version détecté par un scan
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameUpdate {
+    pub id: i64,
+    pub game_id: String,
+    pub title: String,
+    pub version: String,
+    pub previous_version: Option<String>,
+    pub first_seen: i64,
+    pub install_size: u64,
+    // Variation de taille par rapport à la version précédente, en octets
+    pub size_delta: Option<i64>,
+    pub patch_notes_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameNews {
+    pub game_id: String,
+    pub game_title: String,
+    pub gid: String,
+    pub title: String,
+    pub url: String,
+    pub author: Option<String>,
+    pub contents: Option<String>,
+    pub feed_label: Option<String>,
+    pub date: i64,
+    pub tags: Vec<String>,
+    pub read: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NewsRefreshSummary {
+    pub games_checked: usize,
+    pub games_fetched: usize,
+    // Flux inchangés d'après l'ETag
+    pub games_not_modified: usize,
+    pub new_items: usize,
+    pub errors: Vec<String>,
+}
+
+// Actions de gestion déléguées au client de la boutique
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum GameAction {
+    Install,
+    Uninstall,
+    Verify,
+    OpenStorePage,
+}
+
+impl GameAction {
+    // Installer n'a de sens que pour un jeu absent, les autres actions
+    // (hors page boutique) que pour un jeu installé
+    pub fn applies_to(&self, installed: bool) -> bool {
+        match self {
+            GameAction::Install => !installed,
+            GameAction::Uninstall | GameAction::Verify => installed,
+            GameAction::OpenStorePage => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum SessionExportFormat {
+    Csv,
+    JsonLines,
+    ICalendar,
+}
+
+// Attributs d'un jeu utilisés pour ventiler le temps de jeu
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameFacets {
+    pub game_id: String,
+    pub title: String,
+    pub platform: String,
+    pub developer: Option<String>,
+    pub genres: Vec<String>,
+    pub cover: Option<String>,
+    pub first_played: Option<i64>,
+    pub completed_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrashRecord {
+    pub session_id: i64,
+    pub game_id: String,
+    pub game_title: String,
+    pub start_time: i64,
+    pub end_time: Option<i64>,
+    pub exit_code: Option<i32>,
+    pub exit_signal: Option<i32>,
+    pub crash_count: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionTimes {
+    pub end_time: i64,
+    pub active_seconds: i64,
+    pub idle_seconds: i64,
+}
+
+impl SessionTimes {
+    pub fn counted_seconds(&self, mode: PlaytimeMode) -> i64 {
+        match mode {
+            PlaytimeMode::Active => self.active_seconds,
+            PlaytimeMode::Total => self.active_seconds + self.idle_seconds,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActiveSessionStatus {
+    pub session_id: Option<i64>,
+    pub game_id: String,
+    pub title: String,
+    pub start_time: i64,
+    pub elapsed_seconds: i64,
+    pub process_id: u32,
+    pub process_ids: Vec<u32>,
+    pub has_window: bool,
+    pub active_seconds: i64,
+    pub idle_seconds: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonitorStatus {
+    pub running: bool,
+    pub last_tick: Option<i64>,
+    pub scanner_enabled: bool,
+    pub sessions: Vec<ActiveSessionStatus>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -63,7 +454,32 @@ pub struct Game {
     pub metadata: GameMetadata,
     pub media: GameMedia,
     pub last_played: Option<i64>,
+    // Faux pour un jeu possédé sur un compte mais absent de la machine
+    #[serde(default = "default_installed")]
+    pub installed: bool,
     pub stats: GameStats,
+    #[serde(default)]
+    pub kind: GameKind,
+    // Jeu de base d'un contenu additionnel
+    #[serde(default)]
+    pub parent_game_id: Option<String>,
+    // Contenus additionnels installés, renseignés seulement pour la bibliothèque
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub addons: Vec<Game>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub enum GameKind {
+    #[default]
+    Base,
+    Dlc,
+    Soundtrack,
+    Tool,
+    Demo,
+}
+
+fn default_installed() -> bool {
+    true
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -75,6 +491,42 @@ pub struct CustomGameConfig {
     pub icon_path: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum RunnerKind {
+    Native,
+    Wine,
+    Proton,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunnerConfig {
+    pub kind: RunnerKind,
+    // Binaire wine pour Wine, dossier Proton (PROTONPATH) pour Proton
+    pub wine_path: Option<String>,
+    pub prefix_path: Option<String>,
+    pub dll_overrides: HashMap<String, String>,
+}
+
+impl Default for RunnerConfig {
+    fn default() -> Self {
+        Self {
+            kind: RunnerKind::Native,
+            wine_path: None,
+            prefix_path: None,
+            dll_overrides: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WineInstall {
+    pub name: String,
+    pub kind: RunnerKind,
+    pub path: String,
+    pub version: Option<String>,
+    pub source: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpdateStatus {
     pub current_version: String,
@@ -0,0 +1,50 @@
+// src/monitor/activity.rs
+// Comptabilité du temps de session : jeu actif, inactivité et mises en veille.
+use std::time::{Duration, Instant, SystemTime};
+
+// Au-delà, un tick de la boucle n'est plus un simple retard mais une veille
+const MAX_TICK: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Debug, Default)]
+pub struct SessionActivity {
+    pub active: Duration,
+    pub idle: Duration,
+}
+
+impl SessionActivity {
+    pub fn record(&mut self, elapsed: Duration, is_active: bool) {
+        if is_active {
+            self.active += elapsed;
+        } else {
+            self.idle += elapsed;
+        }
+    }
+}
+
+// Compare l'horloge murale et l'horloge monotone entre deux ticks : sous Linux
+// l'horloge monotone s'arrête pendant la veille, sous Windows la boucle est
+// simplement gelée ; dans les deux cas l'écart n'est pas du temps de jeu
+pub struct TickClock {
+    instant: Instant,
+    wall: SystemTime,
+}
+
+impl TickClock {
+    pub fn new() -> Self {
+        Self {
+            instant: Instant::now(),
+            wall: SystemTime::now(),
+        }
+    }
+
+    // Retourne (temps éveillé, temps passé en veille) depuis le dernier tick
+    pub fn tick(&mut self) -> (Duration, Duration) {
+        let monotonic = self.instant.elapsed();
+        let wall = self.wall.elapsed().unwrap_or_default();
+        *self = Self::new();
+
+        let awake = monotonic.min(MAX_TICK);
+        let suspended = wall.max(monotonic).saturating_sub(awake);
+        (awake, suspended)
+    }
+}
@@ -0,0 +1,60 @@
+// src/monitor/exit.rs
+// Codes de sortie des processus d'une session : handle de l'enfant lancé par
+// glaunch quand on l'a, sinon ProcessProbe::watch_exit.
+use super::probe::{ExitInfo, ExitWatch, ProcessProbe};
+use tokio::process::Child;
+
+enum ExitSource {
+    Child(Box<Child>),
+    Probe(Box<dyn ExitWatch>),
+}
+
+impl ExitSource {
+    fn poll(&mut self) -> Option<ExitInfo> {
+        match self {
+            ExitSource::Child(child) => child.try_wait().ok().flatten().map(ExitInfo::from),
+            ExitSource::Probe(watch) => watch.poll_exit(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ExitTracker {
+    watches: Vec<(u32, ExitSource)>,
+    // Le dernier processus à sortir est celui du jeu (le launcher sort avant)
+    last_exit: Option<ExitInfo>,
+}
+
+impl ExitTracker {
+    pub fn attach_child(&mut self, child: Child) {
+        if let Some(pid) = child.id() {
+            self.watches.retain(|(watched, _)| *watched != pid);
+            self.watches.push((pid, ExitSource::Child(Box::new(child))));
+        }
+    }
+
+    pub fn watch(&mut self, probe: &dyn ProcessProbe, pid: u32) {
+        if self.watches.iter().any(|(watched, _)| *watched == pid) {
+            return;
+        }
+        if let Some(watch) = probe.watch_exit(pid) {
+            self.watches.push((pid, ExitSource::Probe(watch)));
+        }
+    }
+
+    pub fn poll(&mut self) {
+        let mut last_exit = self.last_exit;
+        self.watches.retain_mut(|(_, source)| match source.poll() {
+            Some(exit) => {
+                last_exit = Some(exit);
+                false
+            }
+            None => true,
+        });
+        self.last_exit = last_exit;
+    }
+
+    pub fn last_exit(&self) -> Option<ExitInfo> {
+        self.last_exit
+    }
+}
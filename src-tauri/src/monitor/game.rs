@@ -1,243 +1,346 @@
+use super::activity::SessionActivity;
+use super::exit::ExitTracker;
+use super::probe::{self, ProcessProbe, WindowHandle};
+use super::scanner::ScannerConfig;
+use super::supervisor::{MonitorCommand, Supervisor};
+use super::tree::ProcessGroup;
 use crate::db::Database;
-use crate::models::{Game, Platform};
-use crate::AppError;
+use crate::log_info;
+use crate::log_warn;
+use crate::models::{
+    ActiveSessionStatus, Game, GameResult, MonitorStatus, PlaytimeMode, SessionEndReason,
+    SessionTimes,
+};
+use crate::utils::settings::AppSettings;
+use crate::utils::AppError;
+use chrono::Utc;
 use parking_lot::Mutex as PLMutex;
 use std::collections::HashMap;
 use std::sync::Arc;
-use std::time::{Duration, SystemTime};
-use windows::Win32::Foundation::*;
-use windows::Win32::System::ProcessStatus::*;
-use windows::Win32::System::Threading::*;
-use windows::Win32::UI::WindowsAndMessaging::*;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::process::Child;
+use tokio::sync::{mpsc, oneshot};
 
-#[derive(Clone, Debug)]
-pub struct ProcessInfo {
-    pub process_name: String,
-    pub process_id: u32,
-    pub window_handle: HWND,
-}
+// Délai maximal accordé à la boucle pour enregistrer les sessions à la fermeture
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
 
 #[derive(Clone, Debug)]
 pub struct GameSession {
+    pub session_id: Option<i64>,
     pub game_id: String,
+    pub title: String,
     pub start_time: SystemTime,
     pub process_id: u32,
-    pub window_handle: HWND,
+    pub process_ids: Vec<u32>,
+    pub window_handle: Option<WindowHandle>,
+}
+
+pub(super) struct TrackedGame {
+    pub session: GameSession,
+    pub group: ProcessGroup,
+    pub activity: SessionActivity,
+    // Fixée quand glaunch provoque lui-même la fin de la session
+    pub end_reason: Option<SessionEndReason>,
+    pub exits: ExitTracker,
+    pub killed: bool,
+}
+
+impl TrackedGame {
+    // end_time correspond au dernier processus vu, pas à la fin de la période de grâce
+    pub fn times(&self) -> SessionTimes {
+        SessionTimes {
+            end_time: Utc::now().timestamp() - self.group.last_seen().elapsed().as_secs() as i64,
+            active_seconds: self.activity.active.as_secs() as i64,
+            idle_seconds: self.activity.idle.as_secs() as i64,
+        }
+    }
+}
+
+pub(super) type GameMap = PLMutex<HashMap<String, TrackedGame>>;
+
+#[derive(Clone, Debug)]
+pub(super) struct MonitorConfig {
+    pub grace_period: Duration,
+    pub idle_threshold: Duration,
+    pub playtime_mode: PlaytimeMode,
+    pub stop_timeout: Duration,
+    // Un jeu qui s'arrête avant ce délai est compté comme un crash au lancement
+    pub crash_on_start: Duration,
+}
+
+impl Default for MonitorConfig {
+    fn default() -> Self {
+        Self {
+            grace_period: Duration::from_secs(15),
+            idle_threshold: Duration::from_secs(300),
+            playtime_mode: PlaytimeMode::default(),
+            stop_timeout: Duration::from_secs(10),
+            crash_on_start: Duration::from_secs(30),
+        }
+    }
 }
 
-pub type GameMap = PLMutex<HashMap<String, GameSession>>;
+// État de la boucle, mis à jour par le superviseur
+#[derive(Default)]
+pub(super) struct MonitorState {
+    pub running: bool,
+    pub stopped: bool,
+    pub last_tick: Option<SystemTime>,
+}
 
 pub struct GameMonitor {
     active_games: Arc<GameMap>,
     database: Arc<Database>,
-    known_games: Arc<GameMap>,
+    probe: Arc<dyn ProcessProbe>,
+    config: Arc<PLMutex<MonitorConfig>>,
+    scanner_config: PLMutex<ScannerConfig>,
+    state: Arc<PLMutex<MonitorState>>,
+    // Processus lancés par glaunch, en attente de l'ouverture de leur session
+    pending_children: Arc<PLMutex<HashMap<String, Child>>>,
+    commands: mpsc::UnboundedSender<MonitorCommand>,
+    // Pris par start_monitoring : une seule boucle par GameMonitor
+    receiver: PLMutex<Option<mpsc::UnboundedReceiver<MonitorCommand>>>,
 }
 
 impl GameMonitor {
     pub fn new(database: Arc<Database>) -> Self {
+        Self::with_probe(database, probe::system_probe())
+    }
+
+    pub fn with_probe(database: Arc<Database>, probe: Arc<dyn ProcessProbe>) -> Self {
+        let (commands, receiver) = mpsc::unbounded_channel();
+
         Self {
             active_games: Arc::new(PLMutex::new(HashMap::new())),
             database,
-            known_games: Arc::new(PLMutex::new(HashMap::new())),
+            probe,
+            config: Arc::new(PLMutex::new(MonitorConfig::default())),
+            scanner_config: PLMutex::new(ScannerConfig::default()),
+            state: Arc::new(PLMutex::new(MonitorState::default())),
+            pending_children: Arc::new(PLMutex::new(HashMap::new())),
+            commands,
+            receiver: PLMutex::new(Some(receiver)),
         }
     }
 
-    fn sanitize_command(executable: &str) -> String {
-        executable
-            .split("--")
-            .next()
-            .unwrap_or(executable)
-            .split('\"')
-            .next()
-            .unwrap_or(executable)
-            .trim()
-            .to_string()
+    pub fn apply_settings(&self, settings: &AppSettings) {
+        *self.config.lock() = MonitorConfig {
+            grace_period: settings.session_grace_period(),
+            idle_threshold: Duration::from_secs(settings.idle_threshold_secs),
+            playtime_mode: settings.playtime_mode,
+            stop_timeout: Duration::from_secs(settings.stop_game_timeout_secs),
+            crash_on_start: Duration::from_secs(settings.crash_on_start_secs),
+        };
+        *self.scanner_config.lock() = ScannerConfig::from(settings);
+    }
+
+    pub(super) fn scanner_config(&self) -> ScannerConfig {
+        self.scanner_config.lock().clone()
+    }
+
+    pub(super) fn database(&self) -> &Database {
+        &self.database
+    }
+
+    pub(super) fn probe(&self) -> &dyn ProcessProbe {
+        self.probe.as_ref()
     }
 
+    // Lance la boucle de suivi ; sans effet si elle tourne déjà
     pub fn start_monitoring(&self) {
-        let active_games = self.active_games.clone();
-        let database = self.database.clone();
-
-        tokio::spawn(async move {
-            loop {
-                let games_to_check = {
-                    let games = active_games.lock();
-                    if games.is_empty() {
-                        break;
-                    }
-                    games
-                        .iter()
-                        .map(|(k, v)| (k.clone(), v.clone()))
-                        .collect::<Vec<_>>()
-                };
-
-                for (game_id, session) in games_to_check {
-                    let is_running = unsafe {
-                        if let Ok(handle) =
-                            OpenProcess(PROCESS_QUERY_INFORMATION, false, session.process_id)
-                        {
-                            let mut exit_code = 0u32;
-                            GetExitCodeProcess(handle, &mut exit_code).as_bool() && exit_code == 259
-                        } else {
-                            false
-                        }
-                    };
-
-                    if !is_running {
-                        if let Ok(duration) = session.start_time.elapsed() {
-                            let duration_secs = duration.as_secs() as i64;
-                            if let Ok(session_id) =
-                                database.sessions().start_session(&game_id).await
-                            {
-                                // Passer la durée calculée directement
-                                match database
-                                    .sessions()
-                                    .end_session(session_id, duration_secs)
-                                    .await
-                                {
-                                    // Nouveau paramètre
-                                    Ok(_) => println!("✅ Session recorded successfully"),
-                                    Err(e) => println!("❌ Error ending session: {}", e),
-                                }
-                            }
-                        }
-                        active_games.lock().remove(&game_id);
-                    }
-                }
-
-                tokio::time::sleep(Duration::from_secs(1)).await;
-            }
-        });
-    }
-
-    unsafe extern "system" fn enum_window_proc(hwnd: HWND, lparam: LPARAM) -> BOOL {
-        let data = &mut *(lparam.0 as *mut CallbackData);
-        let mut process_id: u32 = 0;
-        GetWindowThreadProcessId(hwnd, Some(&mut process_id));
-
-        if let Ok(process) = OpenProcess(
-            PROCESS_QUERY_INFORMATION | PROCESS_VM_READ,
-            false,
-            process_id,
-        ) {
-            let mut buffer = [0u16; MAX_PATH as usize];
-
-            if GetModuleBaseNameW(process, None, &mut buffer) > 0 {
-                let current_name = String::from_utf16_lossy(&buffer)
-                    .trim_matches('\0')
-                    .to_lowercase();
-
-                let is_main_window = IsWindowVisible(hwnd).as_bool()
-                    && GetWindow(hwnd, GW_OWNER).0 == 0
-                    && GetWindowTextLengthW(hwnd) > 0;
-
-                if is_main_window && current_name.contains(&data.process_name) {
-                    data.result = Some((hwnd, process_id));
-                    return BOOL(0);
-                }
-            }
-        }
-        BOOL(1)
+        let Some(receiver) = self.receiver.lock().take() else {
+            return;
+        };
+
+        let supervisor = Supervisor {
+            active_games: self.active_games.clone(),
+            database: self.database.clone(),
+            probe: self.probe.clone(),
+            config: self.config.clone(),
+            state: self.state.clone(),
+            pending_children: self.pending_children.clone(),
+        };
+        tokio::spawn(supervisor.run(receiver));
+    }
+
+    // Confie au monitor le processus lancé pour un jeu : son code de sortie sera
+    // lu directement plutôt que via le ProcessProbe
+    pub fn attach_child(&self, game_id: &str, child: Child) {
+        self.pending_children.lock().insert(game_id.to_string(), child);
     }
 
     pub async fn track_game(&self, game: &Game) -> Option<GameSession> {
-        let mut possible_names = Vec::new();
-        possible_names.push(game.title.replace(" ", "").to_lowercase() + ".exe");
-
-        if let Some(exe_path) = &game.installation.executable {
-            let clean_exe = Self::sanitize_command(exe_path);
-            if let Some(exe_name) = std::path::Path::new(&clean_exe)
-                .file_name()
-                .and_then(|n| n.to_str())
-            {
-                possible_names.push(exe_name.to_lowercase());
-            }
+        let (reply, response) = oneshot::channel();
+        self.commands
+            .send(MonitorCommand::Track {
+                game: Box::new(game.clone()),
+                reply,
+            })
+            .ok()?;
+        response.await.ok().flatten()
+    }
+
+    // Abandonne le suivi d'un jeu sans enregistrer sa session
+    pub async fn untrack_game(&self, game_id: &str) -> bool {
+        let (reply, response) = oneshot::channel();
+        let command = MonitorCommand::Untrack {
+            game_id: game_id.to_string(),
+            reply,
+        };
+        self.commands.send(command).is_ok() && response.await.unwrap_or(false)
+    }
+
+    // Termine et enregistre immédiatement la session d'un jeu
+    pub async fn stop_tracking(&self, game_id: &str) -> bool {
+        let (reply, response) = oneshot::channel();
+        let command = MonitorCommand::Stop {
+            game_id: game_id.to_string(),
+            reply,
+        };
+        self.commands.send(command).is_ok() && response.await.unwrap_or(false)
+    }
+
+    // Ferme tout l'arbre de processus du jeu : fermeture propre, puis arrêt
+    // forcé des processus restants après le délai configuré
+    pub async fn stop_game(&self, game_id: &str) -> GameResult<()> {
+        {
+            let mut games = self.active_games.lock();
+            let tracked = games.get_mut(game_id).ok_or_else(|| AppError {
+                message: format!("No active session for game {}", game_id),
+            })?;
+            // Si la boucle détecte la fin avant nous, la raison est conservée
+            tracked.end_reason = Some(SessionEndReason::StoppedByUser);
         }
 
-        if game.platform == Platform::BattleNet {
-            match game.id.as_str() {
-                "battlenet_overwatch" => possible_names.push("overwatch.exe".to_string()),
-                "battlenet_wow" => possible_names.push("wow.exe".to_string()),
-                "battlenet_d4" => possible_names.push("diablo iv.exe".to_string()),
-                _ => {}
-            }
+        for pid in self.group_members(game_id) {
+            self.probe.request_close(pid);
         }
 
-        if game.platform == Platform::Steam {
-            if let Ok(entries) = std::fs::read_dir(&game.installation.install_path) {
-                for entry in entries.flatten() {
-                    if let Some(ext) = entry.path().extension() {
-                        if ext == "exe" {
-                            if let Some(name) = entry.path().file_name().and_then(|n| n.to_str()) {
-                                if !name.to_lowercase().contains("unins")
-                                    && !name.to_lowercase().contains("crash")
-                                    && !name.to_lowercase().contains("unity")
-                                    && !name.to_lowercase().contains("ue4")
-                                {
-                                    possible_names.push(name.to_lowercase());
-                                }
-                            }
-                        }
-                    }
-                }
-            }
+        let deadline = Instant::now() + self.config.lock().stop_timeout;
+        while Instant::now() < deadline && !self.group_members(game_id).is_empty() {
+            tokio::time::sleep(Duration::from_millis(250)).await;
         }
 
-        for process_name in possible_names {
-            let mut data = CallbackData {
-                process_name: process_name.clone(),
-                result: None,
-            };
-
-            unsafe {
-                EnumWindows(
-                    Some(Self::enum_window_proc),
-                    LPARAM(&mut data as *mut _ as isize),
-                );
+        // Groupe relu juste avant l'arrêt forcé : il a pu gagner des processus
+        // entre-temps, et un pid réutilisé par un autre programme n'en fait plus partie
+        let mut killed = false;
+        for pid in self.group_members(game_id) {
+            log_info!("Killing process {} of game {}", pid, game_id);
+            killed |= self.probe.kill(pid);
+        }
+        if killed {
+            if let Some(tracked) = self.active_games.lock().get_mut(game_id) {
+                tracked.killed = true;
             }
+        }
 
-            if let Some((window_handle, process_id)) = data.result {
-                let session = GameSession {
-                    game_id: game.id.clone(),
-                    start_time: SystemTime::now(),
-                    process_id,
-                    window_handle,
-                };
-
-                {
-                    let mut games = self.active_games.lock();
-                    let was_empty = games.is_empty();
-                    games.insert(game.id.clone(), session.clone());
-
-                    if was_empty {
-                        self.start_monitoring();
-                    }
-                }
-
-                return Some(session);
-            }
+        self.stop_tracking(game_id).await;
+        Ok(())
+    }
+
+    // Pids des processus vivants du groupe, identifiés par (pid, start_time)
+    fn group_members(&self, game_id: &str) -> Vec<u32> {
+        // Énumération faite hors du verrou : elle peut prendre du temps
+        let processes = self.probe.processes();
+        let mut games = self.active_games.lock();
+        let Some(tracked) = games.get_mut(game_id) else {
+            return Vec::new();
+        };
+
+        tracked.group.refresh(&processes);
+        processes
+            .iter()
+            .filter(|process| tracked.group.contains(process))
+            .map(|process| process.pid)
+            .collect()
+    }
+
+    // Enregistre les sessions en cours et arrête la boucle
+    pub async fn shutdown(&self) {
+        let (reply, response) = oneshot::channel();
+        if self.commands.send(MonitorCommand::Shutdown { reply }).is_err() {
+            return;
+        }
+        if tokio::time::timeout(SHUTDOWN_TIMEOUT, response).await.is_err() {
+            log_warn!("Game monitor did not stop in time");
         }
-        None
     }
 
-    fn is_process_running(process_id: u32) -> bool {
-        unsafe {
-            if let Ok(handle) = OpenProcess(PROCESS_QUERY_INFORMATION, false, process_id) {
-                let mut exit_code = 0u32;
-                if GetExitCodeProcess(handle, &mut exit_code).as_bool() {
-                    return exit_code == 259;
-                }
-            }
-            false
+    // Vrai une fois la boucle arrêtée par shutdown
+    pub fn is_stopped(&self) -> bool {
+        self.state.lock().stopped
+    }
+
+    pub fn status(&self) -> MonitorStatus {
+        let (running, last_tick) = {
+            let state = self.state.lock();
+            (state.running, state.last_tick)
+        };
+
+        MonitorStatus {
+            running,
+            last_tick: last_tick.map(unix_timestamp),
+            scanner_enabled: self.scanner_config.lock().enabled,
+            sessions: self.list_active_sessions(),
         }
     }
 
+    // Sessions en cours, de la plus récente à la plus ancienne
+    pub fn list_active_sessions(&self) -> Vec<ActiveSessionStatus> {
+        let mut sessions: Vec<ActiveSessionStatus> = self
+            .active_games
+            .lock()
+            .values()
+            .map(|tracked| ActiveSessionStatus {
+                session_id: tracked.session.session_id,
+                game_id: tracked.session.game_id.clone(),
+                title: tracked.session.title.clone(),
+                start_time: unix_timestamp(tracked.session.start_time),
+                elapsed_seconds: tracked
+                    .session
+                    .start_time
+                    .elapsed()
+                    .map(|elapsed| elapsed.as_secs() as i64)
+                    .unwrap_or_default(),
+                process_id: tracked.session.process_id,
+                process_ids: tracked.session.process_ids.clone(),
+                has_window: tracked.session.window_handle.is_some(),
+                active_seconds: tracked.activity.active.as_secs() as i64,
+                idle_seconds: tracked.activity.idle.as_secs() as i64,
+            })
+            .collect();
+
+        sessions.sort_by_key(|session| std::cmp::Reverse(session.start_time));
+        sessions
+    }
+
+    pub fn get_session(&self, game_id: &str) -> Option<GameSession> {
+        self.active_games
+            .lock()
+            .get(game_id)
+            .map(|tracked| tracked.session.clone())
+    }
+
+    // Session la plus récente dont le jeu tourne encore
     pub fn get_active_game(&self) -> Option<GameSession> {
         let games = self.active_games.lock();
         games
             .values()
-            .find(|session| Self::is_process_running(session.process_id))
-            .cloned()
+            .filter(|tracked| !tracked.group.is_empty())
+            .max_by_key(|tracked| tracked.session.start_time)
+            .map(|tracked| tracked.session.clone())
+    }
+
+    // Ramène la fenêtre du jeu au premier plan
+    pub fn focus_game(&self, game_id: &str) -> bool {
+        let Some(session) = self.get_session(game_id) else {
+            return false;
+        };
+
+        session
+            .window_handle
+            .or_else(|| self.probe.main_window(session.process_id))
+            .is_some_and(|handle| self.probe.focus_window(handle))
     }
 
     pub fn is_game_running(&self, game_id: &str) -> bool {
@@ -245,7 +348,43 @@ impl GameMonitor {
     }
 }
 
-struct CallbackData {
-    process_name: String,
-    result: Option<(HWND, u32)>,
+pub(super) fn unix_timestamp(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::probe::fake::{exe, game, FakeProcessProbe};
+
+    #[tokio::test]
+    async fn stop_game_spares_a_reused_pid() {
+        let database = Arc::new(Database::in_memory().await.expect("in-memory database"));
+        let probe = Arc::new(FakeProcessProbe::new());
+        let monitor = GameMonitor::with_probe(database.clone(), probe.clone());
+        monitor.config.lock().stop_timeout = Duration::from_millis(600);
+        monitor.start_monitoring();
+
+        let game = game("g1", "Foo");
+        database.games().upsert_game(&game).await.unwrap();
+        probe.spawn(10, None, &exe("Foo/Foo.exe"));
+        probe.set_main_window(10, 42);
+        // Sans fenêtre, l'enfant ignore la fermeture propre
+        probe.spawn(11, Some(10), &exe("Tools/helper.exe"));
+        monitor.track_game(&game).await.expect("session");
+
+        // Le jeu se ferme et le système réattribue son pid pendant l'attente
+        let reuse = async {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            probe.spawn(10, None, &exe("Other/Other.exe"));
+        };
+        let (stopped, _) = tokio::join!(monitor.stop_game("g1"), reuse);
+        stopped.expect("stop game");
+
+        assert!(probe.is_alive(10));
+        assert!(!probe.is_alive(11));
+        assert!(monitor.get_session("g1").is_none());
+    }
 }
@@ -1,2 +1,8 @@
+mod activity;
+mod exit;
 mod game;
-pub use game::{GameMonitor, GameSession};
+pub mod probe;
+mod scanner;
+mod supervisor;
+mod tree;
+pub use game::GameMonitor;
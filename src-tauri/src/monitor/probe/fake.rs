@@ -0,0 +1,182 @@
+// src/monitor/probe/fake.rs
+use super::{ExitInfo, ExitWatch, ProcessEntry, ProcessProbe, WindowHandle};
+use crate::models::{fixtures, Game};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+// Dossier absolu sur la plateforme qui exécute les tests
+#[cfg(windows)]
+pub const GAMES_ROOT: &str = "C:/Games";
+#[cfg(not(windows))]
+pub const GAMES_ROOT: &str = "/games";
+
+// Codes de sortie par (pid, start_time), partagés avec les ExitWatch rendus
+type ExitMap = Arc<Mutex<HashMap<(u32, Option<u64>), ExitInfo>>>;
+
+// Probe en mémoire : les tests décident quels processus existent
+#[derive(Default)]
+pub struct FakeProcessProbe {
+    processes: Mutex<Vec<ProcessEntry>>,
+    windows: Mutex<HashMap<u32, WindowHandle>>,
+    foreground: Mutex<Option<u32>>,
+    idle_time: Mutex<Option<Duration>>,
+    exits: ExitMap,
+    // Chaque processus reçoit une date de démarrage distincte, comme un pid réutilisé
+    started: Mutex<u64>,
+}
+
+impl FakeProcessProbe {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn spawn(&self, pid: u32, parent_pid: Option<u32>, exe_path: &str) -> ProcessEntry {
+        let entry = ProcessEntry {
+            pid,
+            parent_pid,
+            exe_path: Some(PathBuf::from(exe_path)),
+            cmdline: vec![exe_path.to_string()],
+            start_time: Some(self.next_start_time()),
+        };
+        self.processes.lock().push(entry.clone());
+        entry
+    }
+
+    pub fn kill(&self, pid: u32) {
+        self.processes.lock().retain(|process| process.pid != pid);
+        self.windows.lock().remove(&pid);
+    }
+
+    // Termine le processus avec le code de sortie donné
+    pub fn exit(&self, pid: u32, exit: ExitInfo) {
+        let start_time = self
+            .processes
+            .lock()
+            .iter()
+            .find(|process| process.pid == pid)
+            .map(|process| process.start_time);
+        if let Some(start_time) = start_time {
+            self.exits.lock().insert((pid, start_time), exit);
+        }
+        self.kill(pid);
+    }
+
+    fn next_start_time(&self) -> u64 {
+        let mut started = self.started.lock();
+        *started += 1;
+        chrono::Utc::now().timestamp() as u64 + *started
+    }
+
+    pub fn set_main_window(&self, pid: u32, handle: WindowHandle) {
+        self.windows.lock().insert(pid, handle);
+    }
+
+    pub fn set_foreground(&self, pid: Option<u32>) {
+        *self.foreground.lock() = pid;
+    }
+
+    pub fn set_idle_time(&self, idle_time: Option<Duration>) {
+        *self.idle_time.lock() = idle_time;
+    }
+}
+
+impl ProcessProbe for FakeProcessProbe {
+    fn processes(&self) -> Vec<ProcessEntry> {
+        self.processes.lock().clone()
+    }
+
+    fn is_alive(&self, pid: u32) -> bool {
+        self.processes.lock().iter().any(|process| process.pid == pid)
+    }
+
+    fn main_window(&self, pid: u32) -> Option<WindowHandle> {
+        self.windows.lock().get(&pid).copied()
+    }
+
+    fn on_battery(&self) -> bool {
+        false
+    }
+
+    fn request_close(&self, pid: u32) -> bool {
+        // Seuls les processus avec une fenêtre répondent à une fermeture propre
+        let has_window = self.windows.lock().contains_key(&pid);
+        if has_window {
+            self.kill(pid);
+        }
+        has_window
+    }
+
+    fn watch_exit(&self, pid: u32) -> Option<Box<dyn ExitWatch>> {
+        let start_time = self
+            .processes
+            .lock()
+            .iter()
+            .find(|process| process.pid == pid)?
+            .start_time;
+
+        Some(Box::new(FakeExitWatch {
+            process: (pid, start_time),
+            exits: self.exits.clone(),
+        }))
+    }
+
+    fn kill(&self, pid: u32) -> bool {
+        let alive = self.is_alive(pid);
+        FakeProcessProbe::kill(self, pid);
+        alive
+    }
+
+    fn focus_window(&self, handle: WindowHandle) -> bool {
+        let focused = self
+            .windows
+            .lock()
+            .iter()
+            .find(|(_, window)| **window == handle)
+            .map(|(pid, _)| *pid);
+        if focused.is_some() {
+            *self.foreground.lock() = focused;
+        }
+        focused.is_some()
+    }
+
+    fn foreground_process(&self) -> Option<u32> {
+        *self.foreground.lock()
+    }
+
+    fn idle_time(&self) -> Option<Duration> {
+        *self.idle_time.lock()
+    }
+}
+
+struct FakeExitWatch {
+    process: (u32, Option<u64>),
+    exits: ExitMap,
+}
+
+impl ExitWatch for FakeExitWatch {
+    fn poll_exit(&mut self) -> Option<ExitInfo> {
+        self.exits.lock().get(&self.process).copied()
+    }
+}
+
+// Jeu installé dans GAMES_ROOT/<folder>, lancé par <folder>/<folder>.exe
+pub fn game(id: &str, folder: &str) -> Game {
+    let install_path = PathBuf::from(GAMES_ROOT).join(folder);
+    let mut game = fixtures::game(id, folder);
+    game.installation.executable = Some(
+        install_path
+            .join(format!("{}.exe", folder))
+            .to_string_lossy()
+            .to_string(),
+    );
+    game.installation.install_path = install_path;
+    game
+}
+
+// Chemin d'un exécutable sous GAMES_ROOT
+pub fn exe(relative: &str) -> String {
+    format!("{}/{}", GAMES_ROOT, relative)
+}
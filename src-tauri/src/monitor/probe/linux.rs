@@ -0,0 +1,251 @@
+// src/monitor/probe/linux.rs
+use super::{ExitWatch, ProcessEntry, ProcessProbe, WindowHandle};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use x11rb::connection::Connection;
+use x11rb::protocol::screensaver::ConnectionExt as _;
+use x11rb::protocol::xproto::{Atom, AtomEnum, ConnectionExt as _, Window};
+use x11rb::rust_connection::RustConnection;
+
+// Valeur de sysconf(_SC_CLK_TCK) sur toutes les architectures Linux courantes
+const CLOCK_TICKS_PER_SECOND: u64 = 100;
+
+pub struct LinuxProcessProbe {
+    boot_time: Option<u64>,
+    // Absent hors d'une session X11 ou XWayland
+    display: Option<X11Display>,
+}
+
+impl LinuxProcessProbe {
+    pub fn new() -> Self {
+        Self {
+            boot_time: Self::read_boot_time(),
+            display: X11Display::connect(),
+        }
+    }
+
+    fn read_boot_time() -> Option<u64> {
+        fs::read_to_string("/proc/stat")
+            .ok()?
+            .lines()
+            .find_map(|line| line.strip_prefix("btime "))
+            .and_then(|value| value.trim().parse().ok())
+    }
+
+    fn read_entry(&self, pid: u32) -> Option<ProcessEntry> {
+        let stat = fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+        // Les champs suivent le nom entre parenthèses, qui peut contenir des espaces
+        let fields: Vec<&str> = stat.rsplit_once(')')?.1.split_whitespace().collect();
+
+        let cmdline: Vec<String> = fs::read(format!("/proc/{}/cmdline", pid))
+            .unwrap_or_default()
+            .split(|byte| *byte == 0)
+            .filter(|arg| !arg.is_empty())
+            .map(|arg| String::from_utf8_lossy(arg).to_string())
+            .collect();
+
+        let exe_path = fs::read_link(format!("/proc/{}/exe", pid)).ok();
+        let exe_path = match exe_path {
+            Some(path) if Self::is_wine_binary(&path) => {
+                Self::wine_executable_path(pid, &cmdline).or(Some(path))
+            }
+            other => other,
+        };
+
+        Some(ProcessEntry {
+            pid,
+            parent_pid: fields.get(1).and_then(|ppid| ppid.parse().ok()),
+            exe_path,
+            cmdline,
+            start_time: fields
+                .get(19)
+                .and_then(|ticks| ticks.parse::<u64>().ok())
+                .zip(self.boot_time)
+                .map(|(ticks, boot)| boot + ticks / CLOCK_TICKS_PER_SECOND),
+        })
+    }
+
+    fn is_wine_binary(path: &Path) -> bool {
+        path.file_name()
+            .map(|name| {
+                let name = name.to_string_lossy();
+                name.starts_with("wine") && name.contains("preloader")
+                    || name == "wine"
+                    || name == "wine64"
+            })
+            .unwrap_or(false)
+    }
+
+    // Sous Wine, argv[0] contient le chemin Windows du jeu ("Z:\home\...\game.exe"
+    // ou "C:\Program Files\...") qu'on ramène au chemin réel dans le préfixe
+    fn wine_executable_path(pid: u32, cmdline: &[String]) -> Option<PathBuf> {
+        let windows_path = cmdline.first()?;
+        let mut chars = windows_path.chars();
+        let drive = chars.next()?.to_ascii_lowercase();
+        if !drive.is_ascii_alphabetic() || chars.next()? != ':' {
+            return None;
+        }
+        let relative = windows_path[2..].replace('\\', "/");
+        let relative = relative.trim_start_matches('/');
+
+        let drive_root = if drive == 'z' {
+            PathBuf::from("/")
+        } else {
+            Self::wine_prefix(pid)?
+                .join("dosdevices")
+                .join(format!("{}:", drive))
+        };
+
+        let path = drive_root.join(relative);
+        Some(fs::canonicalize(&path).unwrap_or(path))
+    }
+
+    fn wine_prefix(pid: u32) -> Option<PathBuf> {
+        let environ = fs::read(format!("/proc/{}/environ", pid)).ok()?;
+        environ
+            .split(|byte| *byte == 0)
+            .find_map(|var| var.strip_prefix(b"WINEPREFIX="))
+            .map(|value| PathBuf::from(String::from_utf8_lossy(value).to_string()))
+            .or_else(|| dirs::home_dir().map(|home| home.join(".wine")))
+    }
+}
+
+impl ProcessProbe for LinuxProcessProbe {
+    fn processes(&self) -> Vec<ProcessEntry> {
+        fs::read_dir("/proc")
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter_map(|entry| entry.file_name().to_str()?.parse::<u32>().ok())
+                    .filter_map(|pid| self.read_entry(pid))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn is_alive(&self, pid: u32) -> bool {
+        match fs::read_to_string(format!("/proc/{}/stat", pid)) {
+            Ok(stat) => !matches!(
+                stat.rsplit_once(')')
+                    .and_then(|(_, rest)| rest.trim_start().chars().next()),
+                Some('Z') | Some('X')
+            ),
+            Err(_) => false,
+        }
+    }
+
+    // Pas de handle de fenêtre exploitable sans dépendre de X11/Wayland
+    fn main_window(&self, _pid: u32) -> Option<WindowHandle> {
+        None
+    }
+
+    // Sur batterie si aucun secteur n'est branché et qu'une batterie se décharge
+    fn on_battery(&self) -> bool {
+        let Ok(supplies) = fs::read_dir("/sys/class/power_supply") else {
+            return false;
+        };
+
+        let mut discharging = false;
+        for supply in supplies.flatten() {
+            let path = supply.path();
+            let read = |name: &str| {
+                fs::read_to_string(path.join(name))
+                    .map(|value| value.trim().to_string())
+                    .unwrap_or_default()
+            };
+
+            match read("type").as_str() {
+                "Mains" if read("online") == "1" => return false,
+                "Battery" if read("status") == "Discharging" => discharging = true,
+                _ => {}
+            }
+        }
+        discharging
+    }
+
+    // Sans serveur X, la session est comptée comme active
+    fn foreground_process(&self) -> Option<u32> {
+        self.display.as_ref()?.foreground_pid()
+    }
+
+    fn focus_window(&self, _handle: WindowHandle) -> bool {
+        false
+    }
+
+    // waitpid n'est possible que sur nos propres enfants, gérés par le monitor
+    fn watch_exit(&self, _pid: u32) -> Option<Box<dyn ExitWatch>> {
+        None
+    }
+
+    fn request_close(&self, pid: u32) -> bool {
+        unsafe { libc::kill(pid as libc::pid_t, libc::SIGTERM) == 0 }
+    }
+
+    fn kill(&self, pid: u32) -> bool {
+        unsafe { libc::kill(pid as libc::pid_t, libc::SIGKILL) == 0 }
+    }
+
+    fn idle_time(&self) -> Option<Duration> {
+        self.display.as_ref()?.idle_time()
+    }
+}
+
+// Fenêtre active et inactivité de l'utilisateur, lues sur le serveur X ; les
+// jeux Wine et Proton y ont leurs fenêtres même sous Wayland, via XWayland
+struct X11Display {
+    connection: RustConnection,
+    root: Window,
+    active_window: Atom,
+    wm_pid: Atom,
+}
+
+impl X11Display {
+    fn connect() -> Option<Self> {
+        let (connection, screen) = x11rb::connect(None).ok()?;
+        let root = connection.setup().roots.get(screen)?.root;
+        let active_window = Self::atom(&connection, b"_NET_ACTIVE_WINDOW")?;
+        let wm_pid = Self::atom(&connection, b"_NET_WM_PID")?;
+
+        Some(Self {
+            connection,
+            root,
+            active_window,
+            wm_pid,
+        })
+    }
+
+    fn atom(connection: &RustConnection, name: &[u8]) -> Option<Atom> {
+        Some(connection.intern_atom(false, name).ok()?.reply().ok()?.atom)
+    }
+
+    fn property(&self, window: Window, property: Atom, kind: AtomEnum) -> Option<u32> {
+        self.connection
+            .get_property(false, window, property, kind, 0, 1)
+            .ok()?
+            .reply()
+            .ok()?
+            .value32()?
+            .next()
+    }
+
+    // _NET_WM_PID de la fenêtre désignée par _NET_ACTIVE_WINDOW ; Wine le
+    // renseigne avec le pid du processus du jeu
+    fn foreground_pid(&self) -> Option<u32> {
+        let window = self
+            .property(self.root, self.active_window, AtomEnum::WINDOW)
+            .filter(|window| *window != x11rb::NONE)?;
+        self.property(window, self.wm_pid, AtomEnum::CARDINAL)
+    }
+
+    // Extension MIT-SCREEN-SAVER : temps écoulé depuis la dernière saisie
+    fn idle_time(&self) -> Option<Duration> {
+        let info = self
+            .connection
+            .screensaver_query_info(self.root)
+            .ok()?
+            .reply()
+            .ok()?;
+        Some(Duration::from_millis(info.ms_since_user_input as u64))
+    }
+}
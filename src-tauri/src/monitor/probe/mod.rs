@@ -0,0 +1,122 @@
+// src/monitor/probe/mod.rs
+// Abstraction des appels système utilisés par le monitor : énumération des
+// processus, vérification de leur état, recherche de leur fenêtre principale,
+// activité de l'utilisateur et état de l'alimentation.
+#[cfg(test)]
+pub mod fake;
+#[cfg(target_os = "linux")]
+mod linux;
+#[cfg(windows)]
+mod windows;
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+// Valeur brute du HWND sous Windows
+pub type WindowHandle = isize;
+
+#[derive(Debug, Clone)]
+pub struct ProcessEntry {
+    pub pid: u32,
+    pub parent_pid: Option<u32>,
+    // Sous Wine, le chemin Windows du jeu converti en chemin Unix
+    pub exe_path: Option<PathBuf>,
+    pub cmdline: Vec<String>,
+    // Secondes depuis l'epoch Unix
+    pub start_time: Option<u64>,
+}
+
+impl ProcessEntry {
+    pub fn executable_name(&self) -> Option<String> {
+        self.exe_path
+            .as_ref()
+            .and_then(|path| path.file_name())
+            .map(|name| name.to_string_lossy().to_lowercase())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExitInfo {
+    pub code: Option<i32>,
+    pub signal: Option<i32>,
+}
+
+impl ExitInfo {
+    // Sortie anormale : signal fatal sous Unix, code NTSTATUS d'erreur sous
+    // Windows (0xC0000005 et voisins, négatifs une fois convertis en i32)
+    pub fn is_abnormal(&self) -> bool {
+        const CRASH_SIGNALS: &[i32] = &[4, 6, 7, 8, 11]; // SIGILL, SIGABRT, SIGBUS, SIGFPE, SIGSEGV
+        self.signal.is_some_and(|signal| CRASH_SIGNALS.contains(&signal))
+            || self.code.is_some_and(|code| code < 0)
+    }
+}
+
+impl From<std::process::ExitStatus> for ExitInfo {
+    fn from(status: std::process::ExitStatus) -> Self {
+        #[cfg(unix)]
+        let signal = std::os::unix::process::ExitStatusExt::signal(&status);
+        #[cfg(not(unix))]
+        let signal = None;
+
+        Self {
+            code: status.code(),
+            signal,
+        }
+    }
+}
+
+// Handle conservé sur un processus pour lire son code de sortie une fois terminé
+pub trait ExitWatch: Send {
+    fn poll_exit(&mut self) -> Option<ExitInfo>;
+}
+
+pub trait ProcessProbe: Send + Sync {
+    fn processes(&self) -> Vec<ProcessEntry>;
+    fn is_alive(&self, pid: u32) -> bool;
+    fn main_window(&self, pid: u32) -> Option<WindowHandle>;
+    // Surveille la sortie d'un processus, si le système le permet
+    fn watch_exit(&self, pid: u32) -> Option<Box<dyn ExitWatch>>;
+    // Vrai si la machine fonctionne sur batterie (portable débranché)
+    fn on_battery(&self) -> bool;
+    // Demande la fermeture propre d'un processus (WM_CLOSE ou SIGTERM)
+    fn request_close(&self, pid: u32) -> bool;
+    // Termine un processus sans délai
+    fn kill(&self, pid: u32) -> bool;
+    // Restaure et active une fenêtre ; faux si impossible
+    fn focus_window(&self, handle: WindowHandle) -> bool;
+    // Processus propriétaire de la fenêtre au premier plan, si connu
+    fn foreground_process(&self) -> Option<u32>;
+    // Temps écoulé depuis la dernière saisie clavier/souris, si connu
+    fn idle_time(&self) -> Option<Duration>;
+}
+
+#[cfg(windows)]
+pub fn system_probe() -> Arc<dyn ProcessProbe> {
+    Arc::new(windows::WindowsProcessProbe::new())
+}
+
+#[cfg(target_os = "linux")]
+pub fn system_probe() -> Arc<dyn ProcessProbe> {
+    Arc::new(linux::LinuxProcessProbe::new())
+}
+
+// Forme comparable d'un chemin : séparateurs unifiés, casse ignorée sous Windows
+pub fn normalize_path(path: &Path) -> String {
+    let normalized = path
+        .to_string_lossy()
+        .replace('\\', "/")
+        .trim_end_matches('/')
+        .to_string();
+
+    if cfg!(windows) {
+        normalized.to_lowercase()
+    } else {
+        normalized
+    }
+}
+
+pub fn is_under(path: &Path, directory: &Path) -> bool {
+    let directory = normalize_path(directory);
+    !directory.is_empty() && normalize_path(path).starts_with(&format!("{}/", directory))
+}
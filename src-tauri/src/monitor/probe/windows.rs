@@ -0,0 +1,282 @@
+// src/monitor/probe/windows.rs
+use super::{ExitInfo, ExitWatch, ProcessEntry, ProcessProbe, WindowHandle};
+use std::path::PathBuf;
+use std::time::Duration;
+use windows::core::PWSTR;
+use windows::Win32::Foundation::*;
+use windows::Win32::System::Diagnostics::ToolHelp::*;
+use windows::Win32::System::Power::*;
+use windows::Win32::System::SystemInformation::GetTickCount;
+use windows::Win32::System::Threading::*;
+use windows::Win32::UI::Input::KeyboardAndMouse::*;
+use windows::Win32::UI::WindowsAndMessaging::*;
+
+// Code de sortie renvoyé par GetExitCodeProcess tant que le processus tourne
+const STILL_ACTIVE: u32 = 259;
+// Écart entre l'epoch FILETIME (1601) et l'epoch Unix, en secondes
+const FILETIME_UNIX_OFFSET: u64 = 11_644_473_600;
+
+pub struct WindowsProcessProbe;
+
+impl WindowsProcessProbe {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn process_details(pid: u32) -> (Option<PathBuf>, Option<u64>) {
+        unsafe {
+            let Ok(handle) = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) else {
+                return (None, None);
+            };
+
+            let mut buffer = [0u16; 1024];
+            let mut size = buffer.len() as u32;
+            let exe_path = QueryFullProcessImageNameW(
+                handle,
+                PROCESS_NAME_WIN32,
+                PWSTR(buffer.as_mut_ptr()),
+                &mut size,
+            )
+            .as_bool()
+            .then(|| PathBuf::from(String::from_utf16_lossy(&buffer[..size as usize])));
+
+            let mut creation = FILETIME::default();
+            let mut exit = FILETIME::default();
+            let mut kernel = FILETIME::default();
+            let mut user = FILETIME::default();
+            let start_time = GetProcessTimes(handle, &mut creation, &mut exit, &mut kernel, &mut user)
+                .as_bool()
+                .then(|| {
+                    let ticks = ((creation.dwHighDateTime as u64) << 32)
+                        | creation.dwLowDateTime as u64;
+                    (ticks / 10_000_000).saturating_sub(FILETIME_UNIX_OFFSET)
+                });
+
+            CloseHandle(handle);
+            (exe_path, start_time)
+        }
+    }
+
+    unsafe extern "system" fn enum_window_proc(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        let data = &mut *(lparam.0 as *mut WindowSearch);
+        let mut process_id: u32 = 0;
+        GetWindowThreadProcessId(hwnd, Some(&mut process_id));
+
+        let is_main_window = IsWindowVisible(hwnd).as_bool()
+            && GetWindow(hwnd, GW_OWNER).0 == 0
+            && GetWindowTextLengthW(hwnd) > 0;
+
+        if process_id == data.process_id && is_main_window {
+            data.result = Some(hwnd.0);
+            return BOOL(0);
+        }
+        BOOL(1)
+    }
+
+    // Envoie WM_CLOSE à toutes les fenêtres visibles du processus
+    unsafe extern "system" fn close_window_proc(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        let data = &mut *(lparam.0 as *mut CloseRequest);
+        let mut process_id: u32 = 0;
+        GetWindowThreadProcessId(hwnd, Some(&mut process_id));
+
+        if process_id == data.process_id
+            && IsWindowVisible(hwnd).as_bool()
+            && PostMessageW(hwnd, WM_CLOSE, WPARAM(0), LPARAM(0)).as_bool()
+        {
+            data.closed += 1;
+        }
+        BOOL(1)
+    }
+}
+
+impl ProcessProbe for WindowsProcessProbe {
+    fn processes(&self) -> Vec<ProcessEntry> {
+        let mut processes = Vec::new();
+
+        unsafe {
+            let Ok(snapshot) = CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) else {
+                return processes;
+            };
+
+            let mut entry = PROCESSENTRY32W {
+                dwSize: std::mem::size_of::<PROCESSENTRY32W>() as u32,
+                ..Default::default()
+            };
+
+            let mut has_entry = Process32FirstW(snapshot, &mut entry).as_bool();
+            while has_entry {
+                let pid = entry.th32ProcessID;
+                let (exe_path, start_time) = Self::process_details(pid);
+                let exe_name = String::from_utf16_lossy(&entry.szExeFile)
+                    .trim_matches('\0')
+                    .to_string();
+
+                processes.push(ProcessEntry {
+                    pid,
+                    parent_pid: Some(entry.th32ParentProcessID),
+                    // Sans droits suffisants, seul le nom de base est disponible
+                    exe_path: exe_path.or_else(|| Some(PathBuf::from(&exe_name))),
+                    // La ligne de commande nécessite de lire le PEB du processus
+                    cmdline: Vec::new(),
+                    start_time,
+                });
+
+                has_entry = Process32NextW(snapshot, &mut entry).as_bool();
+            }
+
+            CloseHandle(snapshot);
+        }
+
+        processes
+    }
+
+    fn is_alive(&self, pid: u32) -> bool {
+        unsafe {
+            if let Ok(handle) = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) {
+                let mut exit_code = 0u32;
+                let running =
+                    GetExitCodeProcess(handle, &mut exit_code).as_bool() && exit_code == STILL_ACTIVE;
+                CloseHandle(handle);
+                return running;
+            }
+            false
+        }
+    }
+
+    fn main_window(&self, pid: u32) -> Option<WindowHandle> {
+        let mut data = WindowSearch {
+            process_id: pid,
+            result: None,
+        };
+
+        unsafe {
+            EnumWindows(
+                Some(Self::enum_window_proc),
+                LPARAM(&mut data as *mut _ as isize),
+            );
+        }
+
+        data.result
+    }
+
+    fn on_battery(&self) -> bool {
+        let mut status = SYSTEM_POWER_STATUS::default();
+        unsafe {
+            // ACLineStatus : 0 = débranché, 1 = branché, 255 = inconnu
+            GetSystemPowerStatus(&mut status).as_bool() && status.ACLineStatus == 0
+        }
+    }
+
+    fn request_close(&self, pid: u32) -> bool {
+        let mut data = CloseRequest {
+            process_id: pid,
+            closed: 0,
+        };
+
+        unsafe {
+            EnumWindows(
+                Some(Self::close_window_proc),
+                LPARAM(&mut data as *mut _ as isize),
+            );
+        }
+
+        data.closed > 0
+    }
+
+    fn watch_exit(&self, pid: u32) -> Option<Box<dyn ExitWatch>> {
+        let handle = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()? };
+        Some(Box::new(WindowsExitWatch { handle }))
+    }
+
+    fn kill(&self, pid: u32) -> bool {
+        unsafe {
+            let Ok(handle) = OpenProcess(PROCESS_TERMINATE, false, pid) else {
+                return false;
+            };
+            let terminated = TerminateProcess(handle, 1).as_bool();
+            CloseHandle(handle);
+            terminated
+        }
+    }
+
+    fn focus_window(&self, handle: WindowHandle) -> bool {
+        let hwnd = HWND(handle);
+        unsafe {
+            if IsIconic(hwnd).as_bool() {
+                ShowWindow(hwnd, SW_RESTORE);
+            }
+            SetForegroundWindow(hwnd).as_bool()
+        }
+    }
+
+    fn foreground_process(&self) -> Option<u32> {
+        unsafe {
+            let hwnd = GetForegroundWindow();
+            if hwnd.0 == 0 {
+                return None;
+            }
+
+            let mut process_id: u32 = 0;
+            GetWindowThreadProcessId(hwnd, Some(&mut process_id));
+            (process_id != 0).then_some(process_id)
+        }
+    }
+
+    fn idle_time(&self) -> Option<Duration> {
+        let mut info = LASTINPUTINFO {
+            cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+            dwTime: 0,
+        };
+
+        unsafe {
+            if !GetLastInputInfo(&mut info).as_bool() {
+                return None;
+            }
+            // Les deux compteurs bouclent après ~49 jours
+            let idle_ms = GetTickCount().wrapping_sub(info.dwTime);
+            Some(Duration::from_millis(idle_ms as u64))
+        }
+    }
+}
+
+// Le handle garde l'objet processus en vie : le code de sortie reste lisible
+// même après la fin du processus
+struct WindowsExitWatch {
+    handle: HANDLE,
+}
+
+unsafe impl Send for WindowsExitWatch {}
+
+impl ExitWatch for WindowsExitWatch {
+    fn poll_exit(&mut self) -> Option<ExitInfo> {
+        let mut exit_code = 0u32;
+        unsafe {
+            if !GetExitCodeProcess(self.handle, &mut exit_code).as_bool() || exit_code == STILL_ACTIVE {
+                return None;
+            }
+        }
+
+        Some(ExitInfo {
+            code: Some(exit_code as i32),
+            signal: None,
+        })
+    }
+}
+
+impl Drop for WindowsExitWatch {
+    fn drop(&mut self) {
+        unsafe {
+            CloseHandle(self.handle);
+        }
+    }
+}
+
+struct CloseRequest {
+    process_id: u32,
+    closed: usize,
+}
+
+struct WindowSearch {
+    process_id: u32,
+    result: Option<WindowHandle>,
+}
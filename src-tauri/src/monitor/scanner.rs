@@ -0,0 +1,157 @@
+// src/monitor/scanner.rs
+// Détection des jeux lancés hors de glaunch (Steam, raccourcis, Battle.net) :
+// un seul instantané des processus par passage, comparé aux jeux connus.
+use super::game::GameMonitor;
+use super::probe::{self, ProcessEntry};
+use super::tree::{self, GameMatcher};
+use crate::log_error;
+use crate::log_info;
+use crate::models::{Game, GameKind};
+use crate::utils::settings::AppSettings;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+// Fréquence de rechargement de la liste des jeux depuis la base
+const GAMES_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Debug)]
+pub struct ScannerConfig {
+    pub enabled: bool,
+    pub interval: Duration,
+    pub pause_on_battery: bool,
+}
+
+impl Default for ScannerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval: Duration::from_secs(10),
+            pause_on_battery: true,
+        }
+    }
+}
+
+impl From<&AppSettings> for ScannerConfig {
+    fn from(settings: &AppSettings) -> Self {
+        Self {
+            enabled: settings.auto_detect_games,
+            interval: Duration::from_secs(settings.auto_detect_interval_secs.max(1)),
+            pause_on_battery: settings.auto_detect_pause_on_battery,
+        }
+    }
+}
+
+// Jeux détectables indexés par exécutable et par dossier d'installation, pour
+// ne normaliser chaque chemin de processus qu'une fois par passage
+#[derive(Default)]
+struct GameIndex {
+    games: Vec<Game>,
+    executables: HashMap<String, usize>,
+    install_roots: HashMap<String, Vec<usize>>,
+}
+
+impl GameIndex {
+    fn new(games: Vec<Game>) -> Self {
+        let mut executables = HashMap::new();
+        let mut install_roots: HashMap<String, Vec<usize>> = HashMap::new();
+
+        for (index, game) in games.iter().enumerate() {
+            let matcher = GameMatcher::for_game(game);
+            for executable in matcher.executables() {
+                executables.entry(executable.clone()).or_insert(index);
+            }
+            if let Some(root) = matcher.install_root() {
+                install_roots.entry(root).or_default().push(index);
+            }
+        }
+
+        Self {
+            games,
+            executables,
+            install_roots,
+        }
+    }
+
+    // Jeux dont au moins un processus correspond, dans l'ordre de la liste
+    fn detect(&self, processes: &[ProcessEntry]) -> Vec<&Game> {
+        let mut matches = BTreeSet::new();
+
+        for process in processes {
+            let Some(exe_path) = &process.exe_path else {
+                continue;
+            };
+            let path = probe::normalize_path(exe_path);
+
+            if let Some(index) = self.executables.get(&path) {
+                matches.insert(*index);
+            }
+            if tree::is_ignored(process) {
+                continue;
+            }
+            // Chaque dossier parent du chemin est une racine d'installation possible
+            for (position, _) in path.match_indices('/') {
+                if let Some(indices) = self.install_roots.get(&path[..position]) {
+                    matches.extend(indices);
+                }
+            }
+        }
+
+        matches.into_iter().map(|index| &self.games[index]).collect()
+    }
+}
+
+impl GameMonitor {
+    pub fn start_scanner(self: &Arc<Self>) {
+        let monitor = self.clone();
+
+        tokio::spawn(async move {
+            let mut index = GameIndex::default();
+            let mut games_loaded_at: Option<Instant> = None;
+
+            loop {
+                if monitor.is_stopped() {
+                    break;
+                }
+                let config = monitor.scanner_config();
+
+                let paused = !config.enabled
+                    || (config.pause_on_battery && monitor.probe().on_battery());
+                if !paused {
+                    if games_loaded_at.is_none_or(|at| at.elapsed() >= GAMES_REFRESH_INTERVAL) {
+                        match monitor.database().games().get_all_games().await {
+                            Ok(all_games) => {
+                                index = GameIndex::new(
+                                    all_games
+                                        .into_iter()
+                                        // Les DLC partagent le dossier de leur jeu parent
+                                        .filter(|game| {
+                                            game.kind == GameKind::Base
+                                                && game.parent_game_id.is_none()
+                                                && game.installation.install_path.exists()
+                                        })
+                                        .collect(),
+                                );
+                                games_loaded_at = Some(Instant::now());
+                            }
+                            Err(e) => log_error!("Error loading games for detection: {}", e),
+                        }
+                    }
+
+                    let processes = monitor.probe().processes();
+                    for game in index.detect(&processes) {
+                        if monitor.is_game_running(&game.id) {
+                            continue;
+                        }
+
+                        if let Some(session) = monitor.track_game(game).await {
+                            log_info!("Detected {} (pid {})", game.title, session.process_id);
+                        }
+                    }
+                }
+
+                tokio::time::sleep(config.interval).await;
+            }
+        });
+    }
+}
@@ -0,0 +1,531 @@
+// src/monitor/supervisor.rs
+// Tâche unique qui possède la boucle de suivi : elle reçoit les commandes du
+// GameMonitor et échantillonne les processus à chaque tick.
+use super::activity::{SessionActivity, TickClock};
+use super::exit::ExitTracker;
+use super::game::{unix_timestamp, GameMap, GameSession, MonitorConfig, MonitorState, TrackedGame};
+use super::probe::{ProcessEntry, ProcessProbe, WindowHandle};
+use super::tree::{GameMatcher, ProcessGroup};
+use crate::db::Database;
+use crate::log_error;
+use crate::log_info;
+use crate::log_warn;
+use crate::models::{Game, SessionEndReason, SessionOutcome, SessionTimes};
+use parking_lot::Mutex as PLMutex;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+use tokio::process::Child;
+use tokio::sync::{mpsc, oneshot};
+
+// L'énumération complète des processus coûte cher sous Windows (chemin de
+// l'exécutable et date de création ouverts processus par processus)
+const TICK_INTERVAL: Duration = Duration::from_secs(5);
+// Intervalle d'écriture de end_time pour survivre à un crash de glaunch
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+
+pub enum MonitorCommand {
+    // Commence le suivi d'un jeu (ou renvoie la session déjà en cours)
+    Track {
+        game: Box<Game>,
+        reply: oneshot::Sender<Option<GameSession>>,
+    },
+    // Abandonne le suivi sans enregistrer la session
+    Untrack {
+        game_id: String,
+        reply: oneshot::Sender<bool>,
+    },
+    // Termine et enregistre la session immédiatement
+    Stop {
+        game_id: String,
+        reply: oneshot::Sender<bool>,
+    },
+    // Enregistre toutes les sessions en cours puis arrête la boucle
+    Shutdown { reply: oneshot::Sender<()> },
+}
+
+pub struct Supervisor {
+    pub(super) active_games: Arc<GameMap>,
+    pub(super) database: Arc<Database>,
+    pub(super) probe: Arc<dyn ProcessProbe>,
+    pub(super) config: Arc<PLMutex<MonitorConfig>>,
+    pub(super) state: Arc<PLMutex<MonitorState>>,
+    pub(super) pending_children: Arc<PLMutex<HashMap<String, Child>>>,
+}
+
+impl Supervisor {
+    pub async fn run(self, mut commands: mpsc::UnboundedReceiver<MonitorCommand>) {
+        let mut interval = tokio::time::interval(TICK_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let mut clock = TickClock::new();
+        let mut last_heartbeat = Instant::now();
+
+        self.state.lock().running = true;
+
+        loop {
+            tokio::select! {
+                command = commands.recv() => match command {
+                    Some(MonitorCommand::Track { game, reply }) => {
+                        let _ = reply.send(self.track(&game).await);
+                    }
+                    Some(MonitorCommand::Untrack { game_id, reply }) => {
+                        let _ = reply.send(self.untrack(&game_id).await);
+                    }
+                    Some(MonitorCommand::Stop { game_id, reply }) => {
+                        let tracked = self.active_games.lock().remove(&game_id);
+                        let found = tracked.is_some();
+                        if let Some(tracked) = tracked {
+                            self.end_session(tracked, SessionEndReason::StoppedByUser).await;
+                        }
+                        let _ = reply.send(found);
+                    }
+                    Some(MonitorCommand::Shutdown { reply }) => {
+                        self.flush().await;
+                        let _ = reply.send(());
+                        break;
+                    }
+                    // Plus aucun GameMonitor : rien ne pourra arrêter la boucle
+                    None => {
+                        self.flush().await;
+                        break;
+                    }
+                },
+                _ = interval.tick() => {
+                    // L'horloge avance même sans jeu pour ne pas imputer
+                    // l'attente au prochain jeu détecté
+                    let (elapsed, suspended) = clock.tick();
+                    self.state.lock().last_tick = Some(SystemTime::now());
+
+                    if self.active_games.lock().is_empty() {
+                        continue;
+                    }
+                    if !suspended.is_zero() {
+                        log_info!("System suspended for {}s", suspended.as_secs());
+                    }
+
+                    self.tick(elapsed).await;
+
+                    if last_heartbeat.elapsed() >= HEARTBEAT_INTERVAL {
+                        last_heartbeat = Instant::now();
+                        self.heartbeat().await;
+                    }
+                }
+            }
+        }
+
+        {
+            let mut state = self.state.lock();
+            state.running = false;
+            state.stopped = true;
+        }
+        log_info!("Game monitor stopped");
+    }
+
+    async fn track(&self, game: &Game) -> Option<GameSession> {
+        if let Some(tracked) = self.active_games.lock().get_mut(&game.id) {
+            if let Some(child) = self.pending_children.lock().remove(&game.id) {
+                tracked.exits.attach_child(child);
+            }
+            return Some(tracked.session.clone());
+        }
+
+        let matcher = GameMatcher::for_game(game);
+        // Énumération faite hors du verrou : elle peut prendre du temps
+        let snapshot = self.probe.processes();
+        // Un processus déjà suivi pour un autre jeu (DLC installé dans le même
+        // dossier, jeu lancé par un autre) ne peut pas ouvrir une seconde session
+        let processes: Vec<ProcessEntry> = {
+            let games = self.active_games.lock();
+            snapshot
+                .into_iter()
+                .filter(|process| !games.values().any(|tracked| tracked.group.contains(process)))
+                .collect()
+        };
+        let (process_id, window_handle) = self.find_game_process(&matcher, &processes)?;
+        let root = processes.iter().find(|process| process.pid == process_id)?;
+
+        let mut group = ProcessGroup::new(matcher, root);
+        group.refresh(&processes);
+
+        // Le processus lancé par glaunch n'est pas forcément celui du jeu. Il
+        // n'est retiré qu'une fois le jeu trouvé : launch_game rappelle track
+        // tant que le jeu n'est pas apparu.
+        let mut exits = ExitTracker::default();
+        if let Some(child) = self.pending_children.lock().remove(&game.id) {
+            exits.attach_child(child);
+        }
+        exits.watch(self.probe.as_ref(), process_id);
+
+        // La session est écrite dès la détection pour ne pas être perdue
+        // si glaunch s'arrête pendant la partie
+        let session_id = match self.database.sessions().start_session(&game.id).await {
+            Ok(session_id) => Some(session_id),
+            Err(e) => {
+                log_error!("Error starting session: {}", e);
+                None
+            }
+        };
+
+        let session = GameSession {
+            session_id,
+            game_id: game.id.clone(),
+            title: game.title.clone(),
+            start_time: SystemTime::now(),
+            process_id,
+            process_ids: group.pids(),
+            window_handle,
+        };
+
+        self.active_games.lock().insert(
+            game.id.clone(),
+            TrackedGame {
+                session: session.clone(),
+                group,
+                activity: SessionActivity::default(),
+                end_reason: None,
+                exits,
+                killed: false,
+            },
+        );
+
+        Some(session)
+    }
+
+    async fn untrack(&self, game_id: &str) -> bool {
+        let Some(tracked) = self.active_games.lock().remove(game_id) else {
+            return false;
+        };
+
+        if let Some(session_id) = tracked.session.session_id {
+            if let Err(e) = self.database.sessions().discard_session(session_id).await {
+                log_error!("Error discarding session: {}", e);
+            }
+        }
+        true
+    }
+
+    fn find_game_process(
+        &self,
+        matcher: &GameMatcher,
+        processes: &[ProcessEntry],
+    ) -> Option<(u32, Option<WindowHandle>)> {
+        processes
+            .iter()
+            .filter_map(|process| {
+                let score = matcher.score(process)?;
+                // Bonus pour les processus qui possèdent une fenêtre
+                let has_window = self.probe.main_window(process.pid).is_some();
+                Some((score * 2 + has_window as u8, process))
+            })
+            // Meilleur score d'abord, puis le processus le plus ancien
+            .max_by(|(score_a, a), (score_b, b)| {
+                score_a
+                    .cmp(score_b)
+                    .then_with(|| b.start_time.cmp(&a.start_time))
+            })
+            .map(|(_, process)| (process.pid, self.probe.main_window(process.pid)))
+    }
+
+    async fn tick(&self, elapsed: Duration) {
+        let processes = self.probe.processes();
+        let config = self.config.lock().clone();
+
+        let foreground = self.probe.foreground_process();
+        let user_idle = self
+            .probe
+            .idle_time()
+            .is_some_and(|idle_time| idle_time >= config.idle_threshold);
+
+        let ended_games = {
+            let mut games = self.active_games.lock();
+            for tracked in games.values_mut() {
+                tracked.group.refresh(&processes);
+                tracked.session.process_ids = tracked.group.pids();
+                if let Some(primary) = tracked.group.primary(&processes) {
+                    if primary.pid != tracked.session.process_id {
+                        tracked.session.process_id = primary.pid;
+                        tracked.session.window_handle = self.probe.main_window(primary.pid);
+                        tracked.exits.watch(self.probe.as_ref(), primary.pid);
+                    }
+                }
+                tracked.exits.poll();
+
+                if !tracked.group.is_empty() {
+                    // Jeu minimisé ou en arrière-plan : compté comme inactif
+                    let in_foreground = foreground
+                        .is_none_or(|pid| tracked.session.process_ids.contains(&pid));
+                    tracked.activity.record(elapsed, in_foreground && !user_idle);
+                }
+            }
+
+            let ended: Vec<String> = games
+                .iter()
+                .filter(|(_, tracked)| tracked.group.has_ended(config.grace_period))
+                .map(|(game_id, _)| game_id.clone())
+                .collect();
+            ended
+                .into_iter()
+                .filter_map(|game_id| games.remove(&game_id))
+                .collect::<Vec<_>>()
+        };
+
+        for tracked in ended_games {
+            self.end_session(tracked, SessionEndReason::Exited).await;
+        }
+    }
+
+    async fn heartbeat(&self) {
+        let heartbeats: Vec<(i64, SessionTimes)> = self
+            .active_games
+            .lock()
+            .values()
+            .filter_map(|tracked| Some((tracked.session.session_id?, tracked.times())))
+            .collect();
+
+        for (session_id, times) in heartbeats {
+            if let Err(e) = self.database.sessions().heartbeat(session_id, &times).await {
+                log_error!("Error updating session heartbeat: {}", e);
+            }
+        }
+    }
+
+    async fn end_session(&self, mut tracked: TrackedGame, reason: SessionEndReason) {
+        let Some(session_id) = tracked.session.session_id else {
+            return;
+        };
+
+        let config = self.config.lock().clone();
+        let times = tracked.times();
+        tracked.exits.poll();
+        let exit = tracked.exits.last_exit();
+
+        let reason = tracked.end_reason.unwrap_or(reason);
+        let lifetime = times.end_time - unix_timestamp(tracked.session.start_time);
+        // Seule une sortie spontanée du jeu peut être un crash
+        let crashed = reason == SessionEndReason::Exited
+            && (lifetime < config.crash_on_start.as_secs() as i64
+                || exit.is_some_and(|exit| exit.is_abnormal()));
+        if crashed {
+            log_warn!("Game {} crashed ({:?})", tracked.session.game_id, exit);
+        }
+
+        let outcome = SessionOutcome {
+            reason,
+            exit_code: exit.and_then(|exit| exit.code),
+            exit_signal: exit.and_then(|exit| exit.signal),
+            killed: tracked.killed,
+            crashed,
+        };
+
+        match self
+            .database
+            .sessions()
+            .end_session(session_id, &times, config.playtime_mode, &outcome)
+            .await
+        {
+            Ok(_) => log_info!("Session recorded for {}", tracked.session.game_id),
+            Err(e) => log_error!("Error ending session: {}", e),
+        }
+    }
+
+    // Les sessions encore ouvertes se terminent maintenant
+    async fn flush(&self) {
+        let sessions: Vec<TrackedGame> = self
+            .active_games
+            .lock()
+            .drain()
+            .map(|(_, tracked)| tracked)
+            .collect();
+
+        for tracked in sessions {
+            self.end_session(tracked, SessionEndReason::AppShutdown).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::GameKind;
+    use crate::monitor::probe::fake::{exe, game, FakeProcessProbe};
+    use crate::monitor::probe::ExitInfo;
+
+    async fn supervisor(probe: Arc<FakeProcessProbe>, grace_period: Duration) -> Supervisor {
+        let database = Database::in_memory().await.expect("in-memory database");
+        let config = MonitorConfig {
+            grace_period,
+            crash_on_start: Duration::ZERO,
+            ..MonitorConfig::default()
+        };
+
+        Supervisor {
+            active_games: Arc::new(PLMutex::new(HashMap::new())),
+            database: Arc::new(database),
+            probe,
+            config: Arc::new(PLMutex::new(config)),
+            state: Arc::new(PLMutex::new(MonitorState::default())),
+            pending_children: Arc::new(PLMutex::new(HashMap::new())),
+        }
+    }
+
+    // Le jeu doit exister en base pour que sa session soit enregistrée
+    async fn stored_game(supervisor: &Supervisor, id: &str, folder: &str) -> Game {
+        let game = game(id, folder);
+        supervisor.database.games().upsert_game(&game).await.unwrap();
+        game
+    }
+
+    #[tokio::test]
+    async fn session_ends_after_the_grace_period_with_the_exit_code() {
+        let probe = Arc::new(FakeProcessProbe::new());
+        let supervisor = supervisor(probe.clone(), Duration::from_secs(60)).await;
+
+        probe.spawn(10, None, &exe("Foo/Foo.exe"));
+        probe.set_main_window(10, 42);
+        let game = stored_game(&supervisor, "g1", "Foo").await;
+        let session = supervisor.track(&game).await.expect("session");
+        assert_eq!(session.process_id, 10);
+        assert_eq!(session.window_handle, Some(42));
+        let session_id = session.session_id.expect("session recorded at detection");
+
+        probe.exit(10, ExitInfo { code: Some(3), signal: None });
+        supervisor.tick(Duration::from_secs(1)).await;
+        assert!(supervisor.active_games.lock().contains_key("g1"));
+
+        supervisor.config.lock().grace_period = Duration::ZERO;
+        supervisor.tick(Duration::from_secs(1)).await;
+        assert!(supervisor.active_games.lock().is_empty());
+
+        let record = supervisor
+            .database
+            .sessions()
+            .get_session(session_id)
+            .await
+            .unwrap()
+            .expect("recorded session");
+        assert_eq!(record.end_reason, Some(SessionEndReason::Exited));
+        assert_eq!(record.exit_code, Some(3));
+        assert!(!record.crashed);
+    }
+
+    #[tokio::test]
+    async fn launched_child_is_kept_until_the_game_appears() {
+        let probe = Arc::new(FakeProcessProbe::new());
+        let supervisor = supervisor(probe.clone(), Duration::from_secs(60)).await;
+
+        #[cfg(windows)]
+        let child = tokio::process::Command::new("cmd").args(["/C", "exit 7"]).spawn();
+        #[cfg(not(windows))]
+        let child = tokio::process::Command::new("sh").args(["-c", "exit 7"]).spawn();
+        supervisor
+            .pending_children
+            .lock()
+            .insert("g1".to_string(), child.expect("spawn child"));
+
+        // Le jeu n'est pas encore lancé : le Child attend la tentative suivante
+        let game = stored_game(&supervisor, "g1", "Foo").await;
+        assert!(supervisor.track(&game).await.is_none());
+        assert!(supervisor.pending_children.lock().contains_key("g1"));
+
+        probe.spawn(10, None, &exe("Foo/Foo.exe"));
+        let session = supervisor.track(&game).await.expect("session");
+        assert!(supervisor.pending_children.lock().is_empty());
+
+        for _ in 0..100 {
+            let exited = {
+                let mut games = supervisor.active_games.lock();
+                let exits = &mut games.get_mut("g1").expect("session").exits;
+                exits.poll();
+                exits.last_exit().is_some()
+            };
+            if exited {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+
+        probe.kill(10);
+        supervisor.config.lock().grace_period = Duration::ZERO;
+        supervisor.tick(Duration::from_secs(1)).await;
+
+        let record = supervisor
+            .database
+            .sessions()
+            .get_session(session.session_id.expect("session recorded"))
+            .await
+            .unwrap()
+            .expect("recorded session");
+        assert_eq!(record.exit_code, Some(7));
+    }
+
+    #[tokio::test]
+    async fn relaunch_during_the_grace_period_keeps_the_session() {
+        let probe = Arc::new(FakeProcessProbe::new());
+        let supervisor = supervisor(probe.clone(), Duration::from_secs(60)).await;
+
+        probe.spawn(10, None, &exe("Foo/launcher.exe"));
+        let session = supervisor.track(&game("g1", "Foo")).await.expect("session");
+
+        probe.kill(10);
+        supervisor.tick(Duration::from_secs(1)).await;
+        probe.spawn(20, None, &exe("Foo/Foo.exe"));
+        supervisor.tick(Duration::from_secs(1)).await;
+
+        let games = supervisor.active_games.lock();
+        let tracked = games.get("g1").expect("session still tracked");
+        assert_eq!(tracked.session.session_id, session.session_id);
+        assert_eq!(tracked.session.process_id, 20);
+    }
+
+    #[tokio::test]
+    async fn reused_pid_does_not_extend_the_session() {
+        let probe = Arc::new(FakeProcessProbe::new());
+        let supervisor = supervisor(probe.clone(), Duration::ZERO).await;
+
+        probe.spawn(10, None, &exe("Foo/Foo.exe"));
+        supervisor.track(&game("g1", "Foo")).await.expect("session");
+
+        probe.kill(10);
+        probe.spawn(10, None, &exe("Other/Other.exe"));
+        supervisor.tick(Duration::from_secs(1)).await;
+
+        assert!(supervisor.active_games.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn process_of_a_tracked_game_cannot_start_another_session() {
+        let probe = Arc::new(FakeProcessProbe::new());
+        let supervisor = supervisor(probe.clone(), Duration::from_secs(60)).await;
+
+        probe.spawn(10, None, &exe("Foo/Foo.exe"));
+        supervisor.track(&game("g1", "Foo")).await.expect("session");
+
+        let mut dlc = game("g2", "Foo");
+        dlc.kind = GameKind::Dlc;
+        dlc.parent_game_id = Some("g1".to_string());
+        assert!(supervisor.track(&dlc).await.is_none());
+        assert_eq!(supervisor.active_games.lock().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn background_and_idle_time_is_not_active() {
+        let probe = Arc::new(FakeProcessProbe::new());
+        let supervisor = supervisor(probe.clone(), Duration::from_secs(60)).await;
+
+        probe.spawn(10, None, &exe("Foo/Foo.exe"));
+        supervisor.track(&game("g1", "Foo")).await.expect("session");
+
+        probe.set_foreground(Some(10));
+        supervisor.tick(Duration::from_secs(5)).await;
+        probe.set_foreground(Some(99));
+        supervisor.tick(Duration::from_secs(5)).await;
+        probe.set_foreground(Some(10));
+        probe.set_idle_time(Some(Duration::from_secs(600)));
+        supervisor.tick(Duration::from_secs(5)).await;
+
+        let games = supervisor.active_games.lock();
+        let activity = &games.get("g1").expect("session").activity;
+        assert_eq!(activity.active, Duration::from_secs(5));
+        assert_eq!(activity.idle, Duration::from_secs(10));
+    }
+}
@@ -0,0 +1,275 @@
+// src/monitor/tree.rs
+// Suivi d'un jeu comme un groupe de processus : beaucoup de jeux passent par un
+// launcher qui lance le vrai exécutable puis se ferme (Battle.net, Epic, Ubisoft).
+use super::probe::{self, ProcessEntry};
+use crate::models::Game;
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, PathBuf};
+use std::time::{Duration, Instant};
+
+// Exécutables auxiliaires souvent installés dans le dossier du jeu
+const IGNORED_EXECUTABLES: &[&str] = &[
+    "unins",
+    "crash",
+    "redist",
+    "vcredist",
+    "dxsetup",
+    "easyanticheat",
+    "beservice",
+    "cefprocess",
+    "cefsubprocess",
+];
+
+#[derive(Clone, Debug)]
+pub struct GameMatcher {
+    executables: Vec<String>,
+    // Absent si le dossier est trop large pour identifier le jeu (racine d'un disque)
+    install_path: Option<PathBuf>,
+}
+
+impl GameMatcher {
+    pub fn for_game(game: &Game) -> Self {
+        // Chemins complets de l'exécutable connu (ignore les URLs et commandes shell)
+        let executables = game
+            .installation
+            .executable
+            .as_ref()
+            .map(|executable| PathBuf::from(sanitize_command(executable)))
+            .filter(|path| path.is_absolute())
+            .map(|path| probe::normalize_path(&path))
+            .into_iter()
+            .collect();
+
+        let install_path = &game.installation.install_path;
+        let depth = install_path
+            .components()
+            .filter(|component| matches!(component, Component::Normal(_)))
+            .count();
+
+        Self {
+            executables,
+            install_path: (install_path.is_absolute() && depth > 0).then(|| install_path.clone()),
+        }
+    }
+
+    // Exécutable exact : 2, processus sous le dossier d'installation : 1
+    pub fn score(&self, process: &ProcessEntry) -> Option<u8> {
+        let exe_path = process.exe_path.as_ref()?;
+        if self.executables.contains(&probe::normalize_path(exe_path)) {
+            Some(2)
+        } else if self
+            .install_path
+            .as_ref()
+            .is_some_and(|install_path| probe::is_under(exe_path, install_path))
+            && !is_ignored(process)
+        {
+            Some(1)
+        } else {
+            None
+        }
+    }
+
+    // Chemins normalisés des exécutables connus
+    pub fn executables(&self) -> &[String] {
+        &self.executables
+    }
+
+    // Dossier d'installation normalisé, s'il est assez précis pour identifier le jeu
+    pub fn install_root(&self) -> Option<String> {
+        self.install_path.as_deref().map(probe::normalize_path)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ProcessGroup {
+    matcher: GameMatcher,
+    // (pid, start_time) pour ne pas confondre un pid réutilisé par le système
+    members: HashSet<(u32, Option<u64>)>,
+    last_seen: Instant,
+}
+
+impl ProcessGroup {
+    pub fn new(matcher: GameMatcher, root: &ProcessEntry) -> Self {
+        let mut members = HashSet::new();
+        members.insert((root.pid, root.start_time));
+
+        Self {
+            matcher,
+            members,
+            last_seen: Instant::now(),
+        }
+    }
+
+    // Recalcule les membres : processus encore vivants, leurs descendants et tout
+    // processus dont l'exécutable se trouve sous le dossier d'installation
+    pub fn refresh(&mut self, processes: &[ProcessEntry]) {
+        let mut children: HashMap<u32, Vec<&ProcessEntry>> = HashMap::new();
+        for process in processes {
+            if let Some(parent_pid) = process.parent_pid {
+                children.entry(parent_pid).or_default().push(process);
+            }
+        }
+
+        let mut members: HashSet<(u32, Option<u64>)> = processes
+            .iter()
+            .filter(|process| {
+                self.members.contains(&(process.pid, process.start_time))
+                    || self.matcher.score(process).is_some()
+            })
+            .map(|process| (process.pid, process.start_time))
+            .collect();
+
+        let mut queue: Vec<u32> = members.iter().map(|(pid, _)| *pid).collect();
+        while let Some(pid) = queue.pop() {
+            for child in children.get(&pid).into_iter().flatten() {
+                if members.insert((child.pid, child.start_time)) {
+                    queue.push(child.pid);
+                }
+            }
+        }
+
+        if !members.is_empty() {
+            self.last_seen = Instant::now();
+        }
+        self.members = members;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    pub fn contains(&self, process: &ProcessEntry) -> bool {
+        self.members.contains(&(process.pid, process.start_time))
+    }
+
+    pub fn last_seen(&self) -> Instant {
+        self.last_seen
+    }
+
+    pub fn pids(&self) -> Vec<u32> {
+        let mut pids: Vec<u32> = self.members.iter().map(|(pid, _)| *pid).collect();
+        pids.sort();
+        pids
+    }
+
+    // Le groupe n'est considéré terminé qu'après une période de grâce sans membre
+    pub fn has_ended(&self, grace_period: Duration) -> bool {
+        self.is_empty() && self.last_seen.elapsed() >= grace_period
+    }
+
+    // Processus représentatif : meilleure correspondance, puis le plus ancien
+    pub fn primary<'a>(&self, processes: &'a [ProcessEntry]) -> Option<&'a ProcessEntry> {
+        processes
+            .iter()
+            .filter(|process| self.members.contains(&(process.pid, process.start_time)))
+            .max_by(|a, b| {
+                self.matcher
+                    .score(a)
+                    .cmp(&self.matcher.score(b))
+                    .then_with(|| b.start_time.cmp(&a.start_time))
+            })
+    }
+}
+
+fn sanitize_command(executable: &str) -> String {
+    executable
+        .split("--")
+        .next()
+        .unwrap_or(executable)
+        .split('\"')
+        .next()
+        .unwrap_or(executable)
+        .trim()
+        .to_string()
+}
+
+pub(super) fn is_ignored(process: &ProcessEntry) -> bool {
+    process
+        .executable_name()
+        .map(|name| IGNORED_EXECUTABLES.iter().any(|ignored| name.contains(ignored)))
+        .unwrap_or(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::probe::fake::{exe, game, FakeProcessProbe};
+    use crate::monitor::probe::ProcessProbe;
+
+    #[test]
+    fn matcher_prefers_the_exact_executable() {
+        let probe = FakeProcessProbe::new();
+        let matcher = GameMatcher::for_game(&game("g1", "Foo"));
+
+        let main = probe.spawn(10, None, &exe("Foo/Foo.exe"));
+        let helper = probe.spawn(11, None, &exe("Foo/bin/helper.exe"));
+        let uninstaller = probe.spawn(12, None, &exe("Foo/unins000.exe"));
+        let other = probe.spawn(13, None, &exe("Bar/Bar.exe"));
+
+        assert_eq!(matcher.score(&main), Some(2));
+        assert_eq!(matcher.score(&helper), Some(1));
+        assert_eq!(matcher.score(&uninstaller), None);
+        assert_eq!(matcher.score(&other), None);
+    }
+
+    #[test]
+    fn group_follows_children_of_a_launcher() {
+        let probe = FakeProcessProbe::new();
+        let launcher = probe.spawn(10, None, &exe("Launcher/launcher.exe"));
+        let mut group = ProcessGroup::new(GameMatcher::for_game(&game("g1", "Foo")), &launcher);
+
+        probe.spawn(11, Some(10), &exe("Elsewhere/game.exe"));
+        group.refresh(&probe.processes());
+        assert_eq!(group.pids(), vec![10, 11]);
+
+        // Le launcher se ferme : l'enfant reste membre du groupe
+        probe.kill(10);
+        group.refresh(&probe.processes());
+        assert_eq!(group.pids(), vec![11]);
+    }
+
+    #[test]
+    fn reused_pid_does_not_join_the_group() {
+        let probe = FakeProcessProbe::new();
+        let launcher = probe.spawn(10, None, &exe("Launcher/launcher.exe"));
+        let mut group = ProcessGroup::new(GameMatcher::for_game(&game("g1", "Foo")), &launcher);
+
+        probe.kill(10);
+        probe.spawn(10, None, &exe("Other/other.exe"));
+        group.refresh(&probe.processes());
+
+        assert!(group.is_empty());
+    }
+
+    #[test]
+    fn group_ends_only_after_the_grace_period() {
+        let probe = FakeProcessProbe::new();
+        let root = probe.spawn(10, None, &exe("Foo/Foo.exe"));
+        let mut group = ProcessGroup::new(GameMatcher::for_game(&game("g1", "Foo")), &root);
+
+        probe.kill(10);
+        group.refresh(&probe.processes());
+        assert!(group.is_empty());
+        assert!(!group.has_ended(Duration::from_secs(60)));
+        assert!(group.has_ended(Duration::ZERO));
+
+        // Un processus relancé sous le dossier du jeu rouvre le groupe
+        probe.spawn(20, None, &exe("Foo/Foo.exe"));
+        group.refresh(&probe.processes());
+        assert_eq!(group.pids(), vec![20]);
+        assert!(!group.has_ended(Duration::ZERO));
+    }
+
+    #[test]
+    fn primary_is_the_best_match() {
+        let probe = FakeProcessProbe::new();
+        let launcher = probe.spawn(10, None, &exe("Foo/launcher.exe"));
+        let mut group = ProcessGroup::new(GameMatcher::for_game(&game("g1", "Foo")), &launcher);
+
+        probe.spawn(11, Some(10), &exe("Foo/Foo.exe"));
+        let processes = probe.processes();
+        group.refresh(&processes);
+
+        assert_eq!(group.primary(&processes).map(|process| process.pid), Some(11));
+    }
+}
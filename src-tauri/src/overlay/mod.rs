@@ -1,13 +1,53 @@
 // src-tauri/src/overlay/mod.rs
 use tauri::AppHandle;
 use tauri::Manager;
-use windows::core::*;
+#[cfg(windows)]
+use windows::core::Result;
 mod process;
+#[cfg(windows)]
 mod window;
 use crate::monitor::GameMonitor;
 use process::GameProcess;
 use window::OverlayWindow;
 
+#[cfg(not(windows))]
+type Result<T> = std::io::Result<T>;
+
+// Hors Windows, pas de fenêtre native : l'overlay se limite à la fenêtre Tauri
+#[cfg(not(windows))]
+mod window {
+    use crate::monitor::probe::WindowHandle;
+    use std::io::Result;
+
+    pub struct OverlayWindow;
+
+    impl OverlayWindow {
+        pub fn new() -> Result<Self> {
+            Ok(Self)
+        }
+
+        pub fn attach_to_window(&self, _game_window: WindowHandle) -> Result<()> {
+            Ok(())
+        }
+
+        pub fn show(&self) -> Result<()> {
+            Ok(())
+        }
+
+        pub fn hide(&self) -> Result<()> {
+            Ok(())
+        }
+
+        pub fn make_transparent(&self) -> Result<()> {
+            Ok(())
+        }
+
+        pub fn set_topmost(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+}
+
 pub struct GameOverlay {
     window: OverlayWindow,
     pub is_visible: bool,
@@ -25,14 +65,16 @@ impl GameOverlay {
         })
     }
 
-    pub fn set_target_game(&mut self, game_monitor: &GameMonitor, game_id: &str) {
-        if let Some(session) = game_monitor.get_active_game() {
-            if session.game_id == game_id {
+    // Cible une session précise quand plusieurs jeux tournent en même temps
+    pub fn set_target_game(&mut self, game_monitor: &GameMonitor, game_id: &str) -> bool {
+        match game_monitor.get_session(game_id) {
+            Some(session) => {
                 self.game_process = Some(GameProcess {
-                    process_id: session.process_id,
-                    window_handle: session.window_handle,
+                    window_handle: session.window_handle.unwrap_or(0),
                 });
+                true
             }
+            None => false,
         }
     }
 
@@ -63,7 +105,7 @@ impl GameOverlay {
 
     pub fn hide(&mut self) -> Result<()> {
         if self.is_visible {
-            // Cacher la fenêtre native Windows
+            // Cacher la fenêtre native
             self.window.hide()?;
 
             // Cacher la fenêtre Tauri
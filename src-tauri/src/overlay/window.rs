@@ -1,4 +1,5 @@
 // src-tauri/src/overlay/window.rs
+use crate::monitor::probe::WindowHandle;
 use windows::core::{w, Error, Result, PCWSTR};
 use windows::Win32::System::LibraryLoader::GetModuleHandleW;
 use windows::Win32::{Foundation::*, UI::WindowsAndMessaging::*};
@@ -48,7 +49,8 @@ impl OverlayWindow {
         }
     }
 
-    pub fn attach_to_window(&self, game_hwnd: HWND) -> Result<()> {
+    pub fn attach_to_window(&self, game_window: WindowHandle) -> Result<()> {
+        let game_hwnd = HWND(game_window);
         unsafe {
             // Obtenir les dimensions de la fenêtre du jeu
             let mut rect = RECT::default();
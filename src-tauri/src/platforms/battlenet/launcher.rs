@@ -1,74 +1,58 @@
-use crate::models::GameResult;
-use crate::platforms::battlenet::scanner::BattleNetGameScanner;
+use crate::models::{GameAction, GameResult};
 use crate::platforms::traits::GameLauncher;
 use crate::utils::AppError;
 use std::path::PathBuf;
-use std::process::Command;
 use std::time::Duration;
 use tauri::AppHandle;
 use tauri_plugin_shell::ShellExt;
+#[cfg(windows)]
 use winreg::enums::*;
+#[cfg(windows)]
 use winreg::RegKey;
 
 #[derive(Debug)]
 struct GameLaunchInfo {
-    game_id: String,
     launch_code: &'static str,
     process_name: &'static str,
     process_name_alt: Option<&'static str>,
-    wait_time: Duration,
 }
 
 #[derive(Debug)]
 pub struct BattleNetGameLauncher {
-    scanner: BattleNetGameScanner,
     app_handle: AppHandle,
 }
 
 impl BattleNetGameLauncher {
     pub fn new(app_handle: AppHandle) -> Self {
-        Self {
-            scanner: BattleNetGameScanner::new(),
-            app_handle,
-        }
+        Self { app_handle }
     }
 
     fn get_launch_info(game_id: &str) -> Option<GameLaunchInfo> {
         match game_id.strip_prefix("battlenet_")? {
             "wow" => Some(GameLaunchInfo {
-                game_id: "wow".to_string(),
                 launch_code: "WoW",
                 process_name: "Wow.exe",
                 process_name_alt: Some("Wow.exe"),
-                wait_time: Duration::from_secs(5),
             }),
             "prometheus" => Some(GameLaunchInfo {
-                game_id: "overwatch".to_string(),
                 launch_code: "Pro",
                 process_name: "Overwatch Application.exe",
                 process_name_alt: Some("Overwatch.exe"),
-                wait_time: Duration::from_secs(3),
             }),
             "fenris" => Some(GameLaunchInfo {
-                game_id: "diablo4".to_string(),
                 launch_code: "OSI",
                 process_name: "Diablo IV.exe",
                 process_name_alt: Some("Diablo.exe"),
-                wait_time: Duration::from_secs(5),
             }),
             "d3" => Some(GameLaunchInfo {
-                game_id: "d3".to_string(),
                 launch_code: "D3",
                 process_name: "Diablo III.exe",
                 process_name_alt: Some("Diablo III.exe"),
-                wait_time: Duration::from_secs(3),
             }),
             "hs" => Some(GameLaunchInfo {
-                game_id: "hearthstone".to_string(),
                 launch_code: "WTCG",
                 process_name: "Hearthstone.exe",
                 process_name_alt: Some("Hearthstone.exe"),
-                wait_time: Duration::from_secs(3),
             }),
             _ => None,
         }
@@ -89,6 +73,11 @@ impl BattleNetGameLauncher {
         }
 
         // Si non trouvé, chercher dans le registre
+        Self::registry_battlenet_path()
+    }
+
+    #[cfg(windows)]
+    fn registry_battlenet_path() -> Result<PathBuf, AppError> {
         let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
         let key = hklm
             .open_subkey(r"SOFTWARE\WOW6432Node\Battle.net\Launch")
@@ -111,6 +100,13 @@ impl BattleNetGameLauncher {
         }
     }
 
+    #[cfg(not(windows))]
+    fn registry_battlenet_path() -> Result<PathBuf, AppError> {
+        Err(AppError {
+            message: "Battle.net launcher not found".to_string(),
+        })
+    }
+
     async fn ensure_battlenet_running(&self) -> Result<(), AppError> {
         // Vérifier si Battle.net est déjà en cours d'exécution
         let shell = self.app_handle.shell();
@@ -171,6 +167,27 @@ impl BattleNetGameLauncher {
 
         Ok(false)
     }
+
+    // Transmet un verbe --exec au launcher Battle.net pour le jeu donné
+    async fn exec_verb(&self, game_id: &str, verb: &str) -> GameResult<()> {
+        let launch_info = Self::get_launch_info(game_id).ok_or_else(|| AppError {
+            message: format!("Unsupported Battle.net game: {}", game_id),
+        })?;
+
+        self.ensure_battlenet_running().await?;
+        let launcher_path = Self::get_battlenet_path()?;
+
+        self.app_handle
+            .shell()
+            .command(&launcher_path)
+            .args(["--exec", &format!("{} {}", verb, launch_info.launch_code)])
+            .spawn()
+            .map_err(|e| AppError {
+                message: format!("Failed to run Battle.net {}: {}", verb, e),
+            })?;
+
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -191,7 +208,7 @@ impl GameLauncher for BattleNetGameLauncher {
         let shell = self.app_handle.shell();
         shell
             .command(&launcher_path)
-            .args(&["--exec", &format!("launch {}", launch_info.launch_code)])
+            .args(["--exec", &format!("launch {}", launch_info.launch_code)])
             .spawn()
             .map_err(|e| AppError {
                 message: format!("Failed to launch game: {}", e),
@@ -215,21 +232,19 @@ impl GameLauncher for BattleNetGameLauncher {
         Ok(())
     }
 
-    async fn is_game_running(&self, game_id: &str) -> GameResult<bool> {
-        if let Some(launch_info) = Self::get_launch_info(game_id) {
-            let shell = self.app_handle.shell();
-            let output = shell
-                .command("tasklist")
-                .output()
-                .await
-                .map_err(|e| AppError {
-                    message: format!("Failed to check running processes: {}", e),
-                })?;
+    fn supported_actions(&self) -> Vec<GameAction> {
+        vec![GameAction::Install, GameAction::Uninstall, GameAction::Verify]
+    }
 
-            let processes = String::from_utf8_lossy(&output.stdout);
-            Ok(processes.contains(launch_info.process_name))
-        } else {
-            Ok(false)
-        }
+    async fn install_game(&self, game_id: &str) -> GameResult<()> {
+        self.exec_verb(game_id, "install").await
     }
-}
\ No newline at end of file
+
+    async fn uninstall_game(&self, game_id: &str) -> GameResult<()> {
+        self.exec_verb(game_id, "uninstall").await
+    }
+
+    async fn verify_game(&self, game_id: &str) -> GameResult<()> {
+        self.exec_verb(game_id, "repair").await
+    }
+}
@@ -1,45 +1,31 @@
 // Dans platforms/battlenet/mod.rs
 mod launcher;
 mod scanner;
-use crate::models::{Game, GameResult};
+use crate::models::{Game, GameAction, GameResult};
 use crate::platforms::traits::*;
-use crate::Database;
 use async_trait::async_trait;
 pub use launcher::BattleNetGameLauncher;
 pub use scanner::BattleNetGameScanner;
-use std::path::PathBuf;
-use std::sync::Arc;
 use tauri::AppHandle;
 
 pub struct BattleNetPlatform {
     scanner: BattleNetGameScanner,
     launcher: BattleNetGameLauncher,
-    database: Arc<Database>,
 }
 
 impl BattleNetPlatform {
-    pub fn new(database: Arc<Database>, app_handle: AppHandle) -> Self {
+    pub fn new(app_handle: AppHandle) -> Self {
         Self {
             scanner: BattleNetGameScanner::new(),
             launcher: BattleNetGameLauncher::new(app_handle),
-            database,
         }
     }
 }
 
-#[async_trait]
 impl GamePlatform for BattleNetPlatform {
     fn platform_name(&self) -> &'static str {
         "Battle.net"
     }
-
-    fn supported_file_types(&self) -> Vec<&'static str> {
-        vec![] // Battle.net n'a pas de types de fichiers spécifiques à scanner
-    }
-
-    async fn initialize(&mut self) -> GameResult<()> {
-        Ok(()) // Rien de spécial à initialiser pour Battle.net
-    }
 }
 
 #[async_trait]
@@ -47,14 +33,6 @@ impl GameScanner for BattleNetPlatform {
     async fn scan_games(&self) -> GameResult<Vec<Game>> {
         self.scanner.scan_games().await
     }
-
-    fn get_library_paths(&self) -> Vec<PathBuf> {
-        vec![] // Les chemins sont gérés via le fichier de config
-    }
-
-    async fn add_library_path(&mut self, _path: PathBuf) -> GameResult<()> {
-        Ok(()) // Battle.net gère ses propres chemins
-    }
 }
 
 #[async_trait]
@@ -67,10 +45,23 @@ impl GameLauncher for BattleNetPlatform {
         self.launcher.stop_game(game_id).await
     }
 
-    async fn is_game_running(&self, game_id: &str) -> GameResult<bool> {
-        self.launcher.is_game_running(game_id).await
+    fn supported_actions(&self) -> Vec<GameAction> {
+        self.launcher.supported_actions()
     }
-}
 
-#[async_trait]
-impl MetadataProvider for BattleNetPlatform {}
+    async fn install_game(&self, game_id: &str) -> GameResult<()> {
+        self.launcher.install_game(game_id).await
+    }
+
+    async fn uninstall_game(&self, game_id: &str) -> GameResult<()> {
+        self.launcher.uninstall_game(game_id).await
+    }
+
+    async fn verify_game(&self, game_id: &str) -> GameResult<()> {
+        self.launcher.verify_game(game_id).await
+    }
+
+    async fn open_store_page(&self, game_id: &str) -> GameResult<()> {
+        self.launcher.open_store_page(game_id).await
+    }
+}
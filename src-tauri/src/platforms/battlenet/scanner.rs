@@ -1,5 +1,5 @@
 use crate::models::{
-    Game, GameInstallation, GameMedia, GameMetadata, GameResult, GameStats, Platform,
+    Game, GameInstallation, GameKind, GameMedia, GameMetadata, GameResult, GameStats, Platform,
 };
 use crate::utils::AppError;
 use serde::Deserialize;
@@ -9,76 +9,37 @@ use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 use crate::log_debug;
 
+// Battle.net.config : seules les clés utilisées sont lues
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
 struct BnetConfig {
-    Client: ClientConfig,
-    #[serde(rename = "Games")]
-    Games: HashMap<String, GameConfig>,
+    client: ClientConfig,
+    games: HashMap<String, GameConfig>,
     #[serde(flatten)]
     servers: HashMap<String, ServerInfo>,
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
 struct ClientConfig {
-    Install: Option<InstallConfig>,
-    Version: Option<VersionConfig>,
+    install: Option<InstallConfig>,
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
 struct InstallConfig {
-    DefaultInstallPath: String,
-}
-
-#[derive(Debug, Deserialize)]
-struct VersionConfig {
-    FirstRun: Option<String>,
-    Release: Option<ReleaseConfig>,
-}
-
-#[derive(Debug, Deserialize)]
-struct ReleaseConfig {
-    FirstRun: Option<String>,
-    LastBuildVersion: Option<String>,
+    default_install_path: String,
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
 struct GameConfig {
-    ServerUid: String,
-    LastPlayed: Option<String>,
-    Resumable: String,
-    AutoUpdateCriteriaPassed: Option<String>,
-    LastActioned: Option<String>,
-    AdditionalLaunchArguments: Option<String>,
+    last_actioned: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
 struct ServerInfo {
-    Path: Option<String>,
-    Client: Option<ServerClientConfig>,
-    Services: Option<ServicesConfig>,
-}
-
-#[derive(Debug, Deserialize)]
-struct ServerClientConfig {
-    Language: Option<String>,
-    LoginSettings: Option<LoginSettings>,
-}
-
-#[derive(Debug, Deserialize)]
-struct LoginSettings {
-    AllowedRegions: String,
-    AllowedLocales: String,
-}
-
-#[derive(Debug, Deserialize)]
-struct ServicesConfig {
-    LastLoginRegion: String,
-    LastLoginAddress: String,
-    LastLoginTassadar: String,
-}
-
-#[derive(Debug, Deserialize)]
-struct InstallInfo {
     path: Option<String>,
 }
 
@@ -86,7 +47,6 @@ struct InstallInfo {
 struct BnetGameInfo {
     name: &'static str,
     game_dir: &'static str,
-    launcher_exe: &'static str,
     launch_code: &'static str,
     game_exe: &'static str,
     game_subdir: &'static str,
@@ -95,7 +55,6 @@ struct BnetGameInfo {
 #[derive(Debug)]
 pub struct BattleNetGameScanner {
     known_games: HashMap<String, BnetGameInfo>,
-    bnet_launcher_path: Option<PathBuf>,
 }
 
 impl BattleNetGameScanner {
@@ -108,7 +67,6 @@ impl BattleNetGameScanner {
             BnetGameInfo {
                 name: "World of Warcraft",
                 game_dir: "World of Warcraft",
-                launcher_exe: "World of Warcraft Launcher.exe",
                 launch_code: "WoW",
                 game_exe: "Wow.exe",
                 game_subdir: "_retail_",
@@ -120,7 +78,6 @@ impl BattleNetGameScanner {
             BnetGameInfo {
                 name: "Overwatch 2",
                 game_dir: "Overwatch",
-                launcher_exe: "Overwatch Launcher.exe",
                 launch_code: "Pro",
                 game_exe: "Overwatch.exe",
                 game_subdir: "_retail_",
@@ -132,7 +89,6 @@ impl BattleNetGameScanner {
             BnetGameInfo {
                 name: "Diablo IV",
                 game_dir: "Diablo IV",
-                launcher_exe: "Diablo IV Launcher.exe",
                 launch_code: "D3",
                 game_exe: "Diablo IV.exe",
                 game_subdir: "_retail_",
@@ -144,7 +100,6 @@ impl BattleNetGameScanner {
             BnetGameInfo {
                 name: "Diablo III",
                 game_dir: "Diablo III",
-                launcher_exe: "Diablo III Launcher.exe",
                 launch_code: "Pro", // rename
                 game_exe: "Diablo III.exe",
                 game_subdir: "_retail_",
@@ -156,7 +111,6 @@ impl BattleNetGameScanner {
             BnetGameInfo {
                 name: "Hearthstone",
                 game_dir: "Hearthstone",
-                launcher_exe: "Hearthstone Launcher.exe",
                 launch_code: "Pro", // rename
                 game_exe: "Hearthstone.exe",
                 game_subdir: "_retail_",
@@ -168,7 +122,6 @@ impl BattleNetGameScanner {
             BnetGameInfo {
                 name: "Heroes of the Storm",
                 game_dir: "Heroes of the Storm",
-                launcher_exe: "Heroes of the Storm Launcher.exe",
                 launch_code: "Pro", // rename
                 game_exe: "Heroes of the Storm.exe",
                 game_subdir: "_retail_",
@@ -180,7 +133,6 @@ impl BattleNetGameScanner {
             BnetGameInfo {
                 name: "StarCraft: Remastered",
                 game_dir: "StarCraft",
-                launcher_exe: "StarCraft Launcher.exe",
                 launch_code: "Pro", // rename
                 game_exe: "StarCraft.exe",
                 game_subdir: "_retail_",
@@ -192,7 +144,6 @@ impl BattleNetGameScanner {
             BnetGameInfo {
                 name: "StarCraft II",
                 game_dir: "StarCraft II",
-                launcher_exe: "StarCraft II Launcher.exe",
                 launch_code: "Pro", // rename
                 game_exe: "StarCraft II.exe",
                 game_subdir: "_retail_",
@@ -204,7 +155,6 @@ impl BattleNetGameScanner {
             BnetGameInfo {
                 name: "Warcraft III: Reforged",
                 game_dir: "Warcraft III",
-                launcher_exe: "Warcraft III Launcher.exe",
                 launch_code: "Pro", // rename
                 game_exe: "Warcraft III.exe",
                 game_subdir: "_retail_",
@@ -216,7 +166,6 @@ impl BattleNetGameScanner {
             BnetGameInfo {
                 name: "Call of Duty: Modern Warfare",
                 game_dir: "Call of Duty",
-                launcher_exe: "Call of Duty Launcher.exe",
                 launch_code: "Pro", // rename
                 game_exe: "ModernWarfare.exe",
                 game_subdir: "_retail_",
@@ -225,7 +174,6 @@ impl BattleNetGameScanner {
 
         Self {
             known_games,
-            bnet_launcher_path: None,
         }
     }
 
@@ -300,14 +248,14 @@ impl BattleNetGameScanner {
         let mut paths_to_check = Vec::new();
 
         // 1. Chemin d'installation par défaut s'il existe
-        if let Some(default_path) = config.Client.Install.as_ref().map(|install| PathBuf::from(&install.DefaultInstallPath)) {
+        if let Some(default_path) = config.client.install.as_ref().map(|install| PathBuf::from(&install.default_install_path)) {
             paths_to_check.push(default_path);
         }
 
         // 2. Vérifier les chemins dans la config, y compris sous les tokens aléatoires
         let mut found_battlenet_path = false;
         for (token, server_info) in &config.servers {
-            if let Some(path) = &server_info.Path {
+            if let Some(path) = &server_info.path {
                 let path_buf = PathBuf::from(path);
                 if path_buf.ends_with("Battle.net") {
                     log_debug!("Found Battle.net path under token {}: {:?}", token, path_buf);
@@ -347,7 +295,7 @@ impl BattleNetGameScanner {
             log_debug!("   - {:?}", path);
         }
 
-        for game_id in config.Games.keys() {
+        for game_id in config.games.keys() {
             if let Some(game_info) = self.known_games.get(game_id) {
                 log_debug!("\n🎮 Checking game: {} ({})", game_info.name, game_id);
 
@@ -380,7 +328,7 @@ impl BattleNetGameScanner {
                             if let Ok(size) = self.calculate_folder_size(&install_path) {
                                 let launch_command = Self::create_launch_command(
                                     &bnet_launcher_path,
-                                    &game_info.launch_code,
+                                    game_info.launch_code,
                                 );
 
                                 let game = Game {
@@ -393,8 +341,8 @@ impl BattleNetGameScanner {
                                         executable: Some(launch_command),
                                         size,
                                         version: None,
-                                        last_updated: config.Games.get(game_id)
-                                            .and_then(|g| g.LastActioned.as_ref())
+                                        last_updated: config.games.get(game_id)
+                                            .and_then(|g| g.last_actioned.as_ref())
                                             .and_then(|ts| ts.parse().ok()),
                                     },
                                     metadata: GameMetadata {
@@ -416,13 +364,21 @@ impl BattleNetGameScanner {
                                         logo: None,
                                     },
                                     last_played: None,
+                                    installed: true,
                                     stats: GameStats {
                                         total_playtime: 0,
                                         last_session_duration: 0,
                                         sessions_count: 0,
                                         first_played: None,
                                         last_played: None,
+                                        crash_count: 0,
+                                        completed_at: None,
+                                        imported_playtime: 0,
+                                        imported_last_played: None,
                                     },
+                                    kind: GameKind::Base,
+                                    parent_game_id: None,
+                                    addons: Vec::new(),
                                 };
 
                                 games.push(game);
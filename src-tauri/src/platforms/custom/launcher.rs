@@ -26,9 +26,4 @@ impl crate::platforms::traits::GameLauncher for CustomGameLauncher {
         // Pour les jeux custom, on ne gère pas l'arrêt
         Ok(())
     }
-
-    async fn is_game_running(&self, _game_id: &str) -> GameResult<bool> {
-        // On pourrait implémenter une vérification basique du processus
-        Ok(false)
-    }
 }
\ No newline at end of file
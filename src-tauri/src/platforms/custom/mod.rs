@@ -22,19 +22,10 @@ impl CustomPlatform {
     }
 }
 
-#[async_trait::async_trait]
 impl GamePlatform for CustomPlatform {
     fn platform_name(&self) -> &'static str {
         "Custom"
     }
-
-    fn supported_file_types(&self) -> Vec<&'static str> {
-        vec!["exe"]
-    }
-
-    async fn initialize(&mut self) -> GameResult<()> {
-        Ok(())
-    }
 }
 
 #[async_trait::async_trait]
@@ -46,8 +37,4 @@ impl GameLauncher for CustomPlatform {
     async fn stop_game(&self, game_id: &str) -> GameResult<()> {
         self.launcher.stop_game(game_id).await
     }
-
-    async fn is_game_running(&self, game_id: &str) -> GameResult<bool> {
-        self.launcher.is_game_running(game_id).await
-    }
-}
\ No newline at end of file
+}
@@ -0,0 +1,236 @@
+use crate::log_warn;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EpicKeyImage {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EpicCategory {
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EpicReleaseInfo {
+    #[serde(default)]
+    pub app_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EpicMainGameItem {
+    pub id: String,
+}
+
+// Entrée du catalogue local du launcher Epic
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EpicCatalogItem {
+    pub id: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub developer: Option<String>,
+    #[serde(default)]
+    pub key_images: Vec<EpicKeyImage>,
+    #[serde(default)]
+    pub categories: Vec<EpicCategory>,
+    #[serde(default)]
+    pub release_info: Vec<EpicReleaseInfo>,
+    #[serde(default)]
+    pub main_game_item: Option<EpicMainGameItem>,
+}
+
+impl EpicCatalogItem {
+    // Un contenu additionnel référence son jeu principal ou porte la catégorie "addons"
+    pub fn is_dlc(&self) -> bool {
+        self.main_game_item.is_some()
+            || self
+                .categories
+                .iter()
+                .any(|category| category.path == "addons" || category.path.starts_with("addons/"))
+    }
+
+    // Première image disponible parmi les types demandés, par ordre de préférence
+    pub fn key_image(&self, kinds: &[&str]) -> Option<&str> {
+        kinds.iter().find_map(|kind| {
+            self.key_images
+                .iter()
+                .find(|image| image.kind == *kind)
+                .map(|image| image.url.as_str())
+        })
+    }
+}
+
+// catcache.bin : tableau JSON du catalogue encodé en base64
+pub struct EpicCatalog {
+    items: HashMap<String, EpicCatalogItem>,
+    // AppName -> catalogItemId, d'après releaseInfo
+    app_names: HashMap<String, String>,
+}
+
+impl EpicCatalog {
+    pub fn load() -> Option<Self> {
+        let path = Self::catalog_path()?;
+        Self::parse(&fs::read(&path).ok()?)
+    }
+
+    pub fn parse(encoded: &[u8]) -> Option<Self> {
+        // Le fichier peut contenir des retours à la ligne entre les blocs
+        let encoded: Vec<u8> = encoded
+            .iter()
+            .copied()
+            .filter(|byte| !byte.is_ascii_whitespace())
+            .collect();
+
+        let decoded = match STANDARD.decode(&encoded) {
+            Ok(decoded) => decoded,
+            Err(e) => {
+                log_warn!("Failed to decode Epic catalog cache: {}", e);
+                return None;
+            }
+        };
+        let items: Vec<EpicCatalogItem> = match serde_json::from_slice(&decoded) {
+            Ok(items) => items,
+            Err(e) => {
+                log_warn!("Failed to parse Epic catalog cache: {}", e);
+                return None;
+            }
+        };
+
+        let mut app_names = HashMap::new();
+        for item in &items {
+            for release in &item.release_info {
+                if let Some(app_id) = &release.app_id {
+                    app_names.insert(app_id.clone(), item.id.clone());
+                }
+            }
+        }
+
+        Some(Self {
+            items: items.into_iter().map(|item| (item.id.clone(), item)).collect(),
+            app_names,
+        })
+    }
+
+    fn catalog_path() -> Option<PathBuf> {
+        let program_data = std::env::var("PROGRAMDATA").ok()?;
+        let path = PathBuf::from(program_data)
+            .join("Epic")
+            .join("EpicGamesLauncher")
+            .join("Data")
+            .join("Catalog")
+            .join("catcache.bin");
+        path.exists().then_some(path)
+    }
+
+    // AppName installable d'une entrée du catalogue
+    pub fn app_name(&self, catalog_item_id: &str) -> Option<&str> {
+        self.items
+            .get(catalog_item_id)?
+            .release_info
+            .iter()
+            .find_map(|release| release.app_id.as_deref())
+    }
+
+    // catalogItemId du manifeste en priorité, sinon l'AppName via releaseInfo
+    pub fn find(&self, catalog_item_id: Option<&str>, app_name: &str) -> Option<&EpicCatalogItem> {
+        catalog_item_id
+            .and_then(|id| self.items.get(id))
+            .or_else(|| {
+                self.app_names
+                    .get(app_name)
+                    .and_then(|id| self.items.get(id))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    // Encodé comme catcache.bin, coupé en lignes de 76 caractères
+    fn catcache(items: serde_json::Value) -> Vec<u8> {
+        let encoded = STANDARD.encode(items.to_string());
+        let lines: Vec<&str> = encoded
+            .as_bytes()
+            .chunks(76)
+            .map(|line| std::str::from_utf8(line).unwrap())
+            .collect();
+        format!("\n{}\r\n", lines.join("\r\n")).into_bytes()
+    }
+
+    fn catalog() -> EpicCatalog {
+        let items = json!([
+            {
+                "id": "game-item",
+                "title": "Fortnite",
+                "releaseInfo": [{ "appId": "Fortnite" }],
+                "categories": [{ "path": "games" }],
+                "keyImages": [
+                    { "type": "DieselGameBox", "url": "https://example.com/box.jpg" },
+                    { "type": "Thumbnail", "url": "https://example.com/thumb.jpg" }
+                ]
+            },
+            {
+                "id": "dlc-item",
+                "title": "Fortnite Pack",
+                "releaseInfo": [{ "appId": "FortnitePack" }],
+                "mainGameItem": { "id": "game-item" }
+            },
+            {
+                "id": "addon-item",
+                "title": "Soundtrack",
+                "releaseInfo": [{ "appId": "FortniteOST" }],
+                "categories": [{ "path": "addons/durable" }]
+            }
+        ]);
+        EpicCatalog::parse(&catcache(items)).expect("catalog")
+    }
+
+    #[test]
+    fn parses_base64_wrapped_across_lines() {
+        let catalog = catalog();
+        assert_eq!(catalog.items.len(), 3);
+        assert_eq!(catalog.app_name("game-item"), Some("Fortnite"));
+
+        assert!(EpicCatalog::parse(b"not base64!").is_none());
+        assert!(EpicCatalog::parse(STANDARD.encode("{}").as_bytes()).is_none());
+    }
+
+    #[test]
+    fn finds_items_by_catalog_id_then_app_name() {
+        let catalog = catalog();
+        let by_id = catalog.find(Some("game-item"), "Unknown").expect("by catalog id");
+        assert_eq!(by_id.title.as_deref(), Some("Fortnite"));
+
+        // Manifeste sans catalogItemId, ou avec un id absent du cache
+        let by_app_name = catalog.find(None, "FortnitePack").expect("by app name");
+        assert_eq!(by_app_name.id, "dlc-item");
+        assert_eq!(catalog.find(Some("missing"), "FortniteOST").map(|item| item.id.as_str()), Some("addon-item"));
+        assert!(catalog.find(None, "Unknown").is_none());
+
+        assert_eq!(
+            by_id.key_image(&["Thumbnail", "DieselGameBox"]),
+            Some("https://example.com/thumb.jpg")
+        );
+    }
+
+    #[test]
+    fn dlcs_are_detected_by_main_game_or_addons_category() {
+        let catalog = catalog();
+        assert!(!catalog.find(None, "Fortnite").unwrap().is_dlc());
+        assert!(catalog.find(None, "FortnitePack").unwrap().is_dlc());
+        assert!(catalog.find(None, "FortniteOST").unwrap().is_dlc());
+    }
+}
@@ -1,4 +1,5 @@
-use crate::models::GameResult;
+use crate::models::{GameAction, GameResult};
+use crate::platforms::open_uri;
 use crate::utils::AppError;
 use std::process::Command;
 
@@ -8,6 +9,17 @@ impl EpicGameLauncher {
     pub fn new() -> Self {
         Self
     }
+
+    fn app_uri(game_id: &str, action: &str) -> GameResult<String> {
+        let platform_id = game_id.strip_prefix("epic_").ok_or_else(|| AppError {
+            message: "Invalid Epic game ID format".to_string(),
+        })?;
+
+        Ok(format!(
+            "com.epicgames.launcher://apps/{}?action={}",
+            platform_id, action
+        ))
+    }
 }
 
 #[async_trait::async_trait]
@@ -23,7 +35,7 @@ impl crate::platforms::traits::GameLauncher for EpicGameLauncher {
         );
 
         Command::new("cmd")
-            .args(&["/C", "start", "", &launch_url])
+            .args(["/C", "start", "", &launch_url])
             .spawn()
             .map_err(|e| AppError {
                 message: format!("Failed to launch Epic game: {}", e),
@@ -37,8 +49,16 @@ impl crate::platforms::traits::GameLauncher for EpicGameLauncher {
         Ok(())
     }
 
-    async fn is_game_running(&self, _game_id: &str) -> GameResult<bool> {
-        // TODO: Implémenter la vérification via le processus du jeu
-        Ok(false)
+    // Le launcher n'expose ni désinstallation ni page boutique par AppName
+    fn supported_actions(&self) -> Vec<GameAction> {
+        vec![GameAction::Install, GameAction::Verify]
+    }
+
+    async fn install_game(&self, game_id: &str) -> GameResult<()> {
+        open_uri(&Self::app_uri(game_id, "install")?)
+    }
+
+    async fn verify_game(&self, game_id: &str) -> GameResult<()> {
+        open_uri(&Self::app_uri(game_id, "verify")?)
     }
 }
@@ -1,43 +1,37 @@
+mod catalog;
 mod launcher;
 mod scanner;
-use crate::models::{Game, GameResult};
+use crate::models::{Game, GameAction, GameResult};
 use crate::platforms::traits::*;
-use crate::Database;
-use std::path::PathBuf;
 use std::sync::Arc;
 
+pub use catalog::{EpicCatalog, EpicCatalogItem};
 pub use launcher::EpicGameLauncher;
 pub use scanner::EpicGameScanner;
 
 pub struct EpicPlatform {
     scanner: EpicGameScanner,
     launcher: EpicGameLauncher,
-    database: Arc<Database>,
 }
 
 impl EpicPlatform {
-    pub fn new(database: Arc<Database>) -> Self {
+    pub fn new() -> Self {
         Self {
             scanner: EpicGameScanner::new(),
             launcher: EpicGameLauncher::new(),
-            database,
         }
     }
+
+    // Catalogue local lu lors du dernier scan
+    pub fn catalog(&self) -> Option<Arc<EpicCatalog>> {
+        self.scanner.catalog()
+    }
 }
 
-#[async_trait::async_trait]
 impl GamePlatform for EpicPlatform {
     fn platform_name(&self) -> &'static str {
         "Epic Games"
     }
-
-    fn supported_file_types(&self) -> Vec<&'static str> {
-        vec![] // Epic utilise un format de manifeste différent
-    }
-
-    async fn initialize(&mut self) -> GameResult<()> {
-        Ok(())
-    }
 }
 
 #[async_trait::async_trait]
@@ -45,14 +39,6 @@ impl GameScanner for EpicPlatform {
     async fn scan_games(&self) -> GameResult<Vec<Game>> {
         self.scanner.scan_games().await
     }
-
-    fn get_library_paths(&self) -> Vec<PathBuf> {
-        vec![self.scanner.get_install_path()]
-    }
-
-    async fn add_library_path(&mut self, path: PathBuf) -> GameResult<()> {
-        self.scanner.add_user_folder(path)
-    }
 }
 
 #[async_trait::async_trait]
@@ -65,10 +51,23 @@ impl GameLauncher for EpicPlatform {
         self.launcher.stop_game(game_id).await
     }
 
-    async fn is_game_running(&self, game_id: &str) -> GameResult<bool> {
-        self.launcher.is_game_running(game_id).await
+    fn supported_actions(&self) -> Vec<GameAction> {
+        self.launcher.supported_actions()
     }
-}
 
-#[async_trait::async_trait]
-impl MetadataProvider for EpicPlatform {}
+    async fn install_game(&self, game_id: &str) -> GameResult<()> {
+        self.launcher.install_game(game_id).await
+    }
+
+    async fn uninstall_game(&self, game_id: &str) -> GameResult<()> {
+        self.launcher.uninstall_game(game_id).await
+    }
+
+    async fn verify_game(&self, game_id: &str) -> GameResult<()> {
+        self.launcher.verify_game(game_id).await
+    }
+
+    async fn open_store_page(&self, game_id: &str) -> GameResult<()> {
+        self.launcher.open_store_page(game_id).await
+    }
+}
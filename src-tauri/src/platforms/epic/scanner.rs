@@ -1,78 +1,54 @@
 // Dans platforms/epic/scanner.rs
 use crate::models::{
-    Game, GameInstallation, GameMedia, GameMetadata, GameResult, GameStats, Platform,
+    Game, GameInstallation, GameKind, GameMedia, GameMetadata, GameResult, GameStats, Platform,
 };
+use super::catalog::EpicCatalog;
 use crate::utils::AppError;
 use serde::Deserialize;
 use std::fs;
 use std::path::PathBuf;
-use std::sync::Mutex;
-use winreg::enums::*;
-use winreg::RegKey;
+use std::sync::{Arc, Mutex};
 
 #[derive(Debug, Deserialize)]
 struct EpicManifest {
-    InstallLocation: String,
-    DisplayName: String,
-    AppName: String,
+    #[serde(rename = "InstallLocation")]
+    install_location: String,
+    #[serde(rename = "DisplayName")]
+    display_name: String,
+    #[serde(rename = "AppName")]
+    app_name: String,
+    #[serde(rename = "CatalogItemId")]
+    catalog_item_id: Option<String>,
     #[serde(rename = "MainGameAppName")]
-    main_executable: Option<String>,
-    InstallSize: Option<u64>,
+    main_game_app_name: Option<String>,
+    #[serde(rename = "InstallSize")]
+    install_size: Option<u64>,
     #[serde(rename = "AppVersion")]
     version: Option<String>,
     #[serde(rename = "AppCategories")]
     categories: Option<Vec<String>>,
 }
 
+#[derive(Default)]
 pub struct EpicGameScanner {
-    install_path: PathBuf,
-    user_added_folders: Mutex<Vec<PathBuf>>,
+    // Catalogue lu au dernier scan, réutilisé jusqu'au suivant
+    catalog: Mutex<Option<Arc<EpicCatalog>>>,
 }
 
 impl EpicGameScanner {
     pub fn new() -> Self {
-        let install_path = Self::get_epic_path()
-            .unwrap_or_else(|_| PathBuf::from("C:\\Program Files\\Epic Games"));
-
         Self {
-            install_path,
-            user_added_folders: Mutex::new(Vec::new()),
+            catalog: Mutex::new(None),
         }
     }
 
-    pub fn get_install_path(&self) -> PathBuf {
-        self.install_path.clone()
-    }
-
-    pub fn add_user_folder(&self, path: PathBuf) -> GameResult<()> {
-        if let Ok(mut folders) = self.user_added_folders.lock() {
-            if !folders.contains(&path) {
-                folders.push(path);
-            }
-            Ok(())
-        } else {
-            Err(AppError {
-                message: "Failed to lock user_added_folders".to_string(),
-            })
+    // Catalogue du dernier scan, chargé à la demande si aucun scan n'a eu lieu
+    pub fn catalog(&self) -> Option<Arc<EpicCatalog>> {
+        let mut catalog = self.catalog.lock().ok()?;
+        if catalog.is_none() {
+            *catalog = EpicCatalog::load().map(Arc::new);
         }
-    }
-
-    fn get_epic_path() -> GameResult<PathBuf> {
-        let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
-        let epic_key = hklm
-            .open_subkey("SOFTWARE\\WOW6432Node\\Epic Games\\EpicGamesLauncher")
-            .map_err(|e| AppError {
-                message: format!("Failed to find Epic Games registry key: {}", e),
-            })?;
-
-        let install_path: String =
-            epic_key
-                .get_value("AppInstallLocation")
-                .map_err(|e| AppError {
-                    message: format!("Failed to get Epic Games install path: {}", e),
-                })?;
-
-        Ok(PathBuf::from(install_path))
+        catalog.clone()
     }
 
     fn get_manifests_path() -> GameResult<PathBuf> {
@@ -91,6 +67,11 @@ impl EpicGameScanner {
         let manifests_path = Self::get_manifests_path()?;
 
         let mut games = Vec::new();
+        // Catalogue local : titres, descriptions et DLC sans appel réseau
+        let catalog = EpicCatalog::load().map(Arc::new);
+        if let Ok(mut cached) = self.catalog.lock() {
+            cached.clone_from(&catalog);
+        }
 
         if manifests_path.exists() {
             for entry in fs::read_dir(&manifests_path).map_err(|e| AppError {
@@ -105,7 +86,9 @@ impl EpicGameScanner {
                     if let Ok(content) = fs::read_to_string(&path) {
                         match serde_json::from_str::<EpicManifest>(&content) {
                             Ok(manifest) => {
-                                if let Ok(game) = self.create_game_from_manifest(manifest) {
+                                if let Ok(game) =
+                                    self.create_game_from_manifest(manifest, catalog.as_deref())
+                                {
                                     games.push(game);
                                 }
                             }
@@ -124,39 +107,68 @@ impl EpicGameScanner {
         if let Some(categories) = &manifest.categories {
             categories
                 .iter()
-                .any(|cat| cat == "games" || cat == "applications")
+                .any(|cat| cat == "games" || cat == "applications" || cat == "addons")
         } else {
             false
         }
     }
 
-    fn create_game_from_manifest(&self, manifest: EpicManifest) -> GameResult<Game> {
+    fn create_game_from_manifest(
+        &self,
+        manifest: EpicManifest,
+        catalog: Option<&EpicCatalog>,
+    ) -> GameResult<Game> {
         if !self.should_include_app(&manifest) {
             return Err(AppError {
-                message: format!("Skipping non-game application: {}", manifest.DisplayName),
+                message: format!("Skipping non-game application: {}", manifest.display_name),
             });
         }
-        let install_path = PathBuf::from(&manifest.InstallLocation);
+        let install_path = PathBuf::from(&manifest.install_location);
+
+        let catalog_item = catalog
+            .and_then(|catalog| catalog.find(manifest.catalog_item_id.as_deref(), &manifest.app_name));
+
+        // Un DLC pointe vers son jeu via MainGameAppName, sinon via le catalogue
+        let parent_app_name = manifest
+            .main_game_app_name
+            .clone()
+            .filter(|main_game| *main_game != manifest.app_name)
+            .or_else(|| {
+                let main_game_item = catalog_item?.main_game_item.as_ref()?;
+                catalog?.app_name(&main_game_item.id).map(String::from)
+            });
+        let has_category =
+            |name: &str| manifest.categories.iter().flatten().any(|category| category == name);
+        let kind = if parent_app_name.is_some() || catalog_item.is_some_and(|item| item.is_dlc()) {
+            GameKind::Dlc
+        } else if has_category("applications") && !has_category("games") {
+            GameKind::Tool
+        } else {
+            GameKind::Base
+        };
+        let parent_game_id = parent_app_name.map(|app_name| format!("epic_{}", app_name));
 
         let game = Game {
-            id: format!("epic_{}", manifest.AppName),
-            platform_id: manifest.AppName.clone(),
+            id: format!("epic_{}", manifest.app_name),
+            platform_id: manifest.app_name.clone(),
             platform: Platform::Epic,
-            title: manifest.DisplayName.clone(),
+            title: manifest.display_name.clone(),
             installation: GameInstallation {
                 install_path: install_path.clone(),
                 executable: Some(format!(
                     "com.epicgames.launcher://apps/{}?action=launch",
-                    manifest.AppName
+                    manifest.app_name
                 )),
-                size: manifest.InstallSize.unwrap_or(0),
+                size: manifest.install_size.unwrap_or(0),
                 version: manifest.version,
                 last_updated: None,
             },
             metadata: GameMetadata {
-                title: manifest.DisplayName,
-                description: None,
-                developer: None,
+                title: catalog_item
+                    .and_then(|item| item.title.clone())
+                    .unwrap_or(manifest.display_name),
+                description: catalog_item.and_then(|item| item.description.clone()),
+                developer: catalog_item.and_then(|item| item.developer.clone()),
                 publisher: None,
                 release_date: None,
                 genres: Vec::new(),
@@ -172,13 +184,21 @@ impl EpicGameScanner {
                 logo: None,
             },
             last_played: None,
+            installed: true,
             stats: GameStats {
                 total_playtime: 0,
                 last_session_duration: 0,
                 sessions_count: 0,
                 first_played: None,
                 last_played: None,
+                crash_count: 0,
+                completed_at: None,
+                imported_playtime: 0,
+                imported_last_played: None,
             },
+            kind,
+            parent_game_id,
+            addons: Vec::new(),
         };
 
         Ok(game)
@@ -0,0 +1,94 @@
+use crate::models::{GameAction, GameResult};
+use crate::platforms::open_uri;
+use crate::utils::AppError;
+use serde_json::Value;
+use sqlx::sqlite::SqlitePoolOptions;
+use sqlx::Row;
+use std::path::PathBuf;
+
+// Jeu GOG présent dans la bibliothèque du compte connecté à GOG Galaxy
+#[derive(Debug, Clone)]
+pub struct GogOwnedGame {
+    pub product_id: String,
+    pub title: String,
+}
+
+// Lit la base SQLite locale de GOG Galaxy 2.0 ; les releaseKey des autres
+// boutiques intégrées à Galaxy (steam_, epic_...) sont ignorés
+pub struct GogGalaxy {
+    database_path: PathBuf,
+}
+
+impl GogGalaxy {
+    pub fn new() -> Self {
+        let program_data =
+            std::env::var("PROGRAMDATA").unwrap_or_else(|_| "C:\\ProgramData".to_string());
+        Self {
+            database_path: PathBuf::from(program_data)
+                .join("GOG.com")
+                .join("Galaxy")
+                .join("storage")
+                .join("galaxy-2.0.db"),
+        }
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.database_path.exists()
+    }
+
+    pub async fn owned_games(&self) -> GameResult<Vec<GogOwnedGame>> {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect(&format!("sqlite:{}?mode=ro", self.database_path.display()))
+            .await
+            .map_err(|e| AppError {
+                message: format!("Failed to open GOG Galaxy database: {}", e),
+            })?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT DISTINCT lr.releaseKey as release_key, gp.value as title
+            FROM LibraryReleases lr
+            LEFT JOIN GamePieces gp ON gp.releaseKey = lr.releaseKey
+                AND gp.gamePieceTypeId = (SELECT id FROM GamePieceTypes WHERE type = 'title')
+            WHERE lr.releaseKey LIKE 'gog\_%' ESCAPE '\'
+            "#,
+        )
+        .fetch_all(&pool)
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to read GOG Galaxy library: {}", e),
+        })?;
+        pool.close().await;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| {
+                let release_key: String = row.get("release_key");
+                let product_id = release_key.strip_prefix("gog_")?.to_string();
+                // La valeur est un objet JSON : {"title": "..."}
+                let title = row
+                    .get::<Option<String>, _>("title")
+                    .and_then(|value| serde_json::from_str::<Value>(&value).ok())
+                    .and_then(|value| value["title"].as_str().map(str::to_string))
+                    .unwrap_or_else(|| release_key.clone());
+                Some(GogOwnedGame { product_id, title })
+            })
+            .collect())
+    }
+
+    // GOG n'est pas une plateforme scannée : seule l'installation est proposée,
+    // via la fiche du jeu dans Galaxy
+    pub fn supported_actions(&self) -> Vec<GameAction> {
+        vec![GameAction::Install]
+    }
+
+    pub fn run_action(&self, product_id: &str, action: GameAction) -> GameResult<()> {
+        match action {
+            GameAction::Install => open_uri(&format!("goggalaxy://openGameView/{}", product_id)),
+            _ => Err(AppError {
+                message: format!("{:?} is not supported for GOG games", action),
+            }),
+        }
+    }
+}
@@ -1,26 +1,43 @@
 pub mod battlenet;
 pub mod epic;
+pub mod gog;
 pub mod steam;
 pub mod traits;
-use crate::Database;
-use std::sync::Arc;
-use tauri::AppHandle;
+use crate::models::GameResult;
+use crate::utils::AppError;
+use std::process::Command;
 
-pub use battlenet::BattleNetPlatform;
-pub use epic::EpicPlatform;
 pub use steam::SteamPlatform;
 pub use traits::*;
 
-// Factory pour créer les instances de plateformes
-pub fn create_platform(
-    platform_type: &str,
-    database: Arc<Database>,
-    app_handle: AppHandle,
-) -> Box<dyn GamePlatform> {
-    match platform_type {
-        "steam" => Box::new(SteamPlatform::new(database.clone())),
-        "battlenet" => Box::new(BattleNetPlatform::new(database.clone(), app_handle)),
-        "epic" => Box::new(EpicPlatform::new(database.clone())),
-        _ => panic!("Unknown platform type: {}", platform_type),
-    }
+// Ouvre une URI de boutique (steam://, com.epicgames.launcher://, goggalaxy://)
+// dans le client associé
+pub fn open_uri(uri: &str) -> GameResult<()> {
+    #[cfg(windows)]
+    let mut command = {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", "", uri]);
+        command
+    };
+    #[cfg(target_os = "macos")]
+    let mut command = {
+        let mut command = Command::new("open");
+        command.arg(uri);
+        command
+    };
+    // Linux : gestionnaire d'URI déclaré par le client (Steam natif, Heroic, Lutris)
+    #[cfg(all(unix, not(target_os = "macos")))]
+    let mut command = {
+        let mut command = Command::new("xdg-open");
+        command.arg(uri);
+        command
+    };
+
+    command
+        .spawn()
+        .map_err(|e| AppError {
+            message: format!("Failed to open {}: {}", uri, e),
+        })?;
+
+    Ok(())
 }
@@ -0,0 +1,329 @@
+use crate::log_warn;
+use crate::models::GameKind;
+use crate::utils::vdf::VdfNode;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+// appcache/appinfo.vdf : cache binaire des informations d'applications
+const APPINFO_V27: u32 = 0x07564427;
+const APPINFO_V28: u32 = 0x07564428;
+// Les clés deviennent des index dans une table de chaînes en fin de fichier
+const APPINFO_V29: u32 = 0x07564429;
+
+// Type d'application et jeu parent selon la section "common"
+#[derive(Debug, Clone)]
+pub struct SteamAppInfo {
+    pub name: Option<String>,
+    pub kind: GameKind,
+    pub parent: Option<String>,
+}
+
+// Seules les applications demandées sont décodées, les autres sont sautées
+pub fn read_app_info(steam_path: &Path, app_ids: &HashSet<String>) -> HashMap<String, SteamAppInfo> {
+    let path = steam_path.join("appcache").join("appinfo.vdf");
+    let Ok(data) = fs::read(&path) else {
+        return HashMap::new();
+    };
+
+    parse_app_info(&data, app_ids).unwrap_or_else(|| {
+        log_warn!("Unsupported or corrupt Steam appinfo.vdf");
+        HashMap::new()
+    })
+}
+
+fn parse_app_info(data: &[u8], app_ids: &HashSet<String>) -> Option<HashMap<String, SteamAppInfo>> {
+    let mut reader = Reader::new(data);
+    let magic = reader.u32()?;
+    let _universe = reader.u32()?;
+
+    // Après la taille, chaque entrée commence par état, date, jeton, SHA-1 et
+    // numéro de changement, puis le SHA-1 des données binaires depuis la v28
+    let (entry_header, keys) = match magic {
+        APPINFO_V27 => (40, None),
+        APPINFO_V28 => (60, None),
+        APPINFO_V29 => {
+            let offset = usize::try_from(reader.u64()?).ok()?;
+            (60, Some(string_table(data.get(offset..)?)?))
+        }
+        _ => return None,
+    };
+
+    let mut apps = HashMap::new();
+    loop {
+        let app_id = reader.u32()?;
+        if app_id == 0 {
+            break;
+        }
+        let size = reader.u32()? as usize;
+        let entry = reader.bytes(size)?;
+
+        let app_id = app_id.to_string();
+        if !app_ids.contains(&app_id) {
+            continue;
+        }
+
+        // Une entrée illisible n'empêche pas de lire les suivantes
+        let mut kv = Reader::new(entry.get(entry_header..).unwrap_or_default());
+        let Some(root) = read_object(&mut kv, keys.as_deref()).map(VdfNode::Object) else {
+            continue;
+        };
+        let Some(common) = root.get_path(&["appinfo", "common"]) else {
+            continue;
+        };
+
+        apps.insert(
+            app_id,
+            SteamAppInfo {
+                name: common.value("name").map(String::from),
+                kind: app_kind(common.value("type").unwrap_or_default()),
+                parent: common
+                    .value("parent")
+                    .filter(|parent| *parent != "0")
+                    .map(String::from),
+            },
+        );
+    }
+
+    Some(apps)
+}
+
+fn app_kind(app_type: &str) -> GameKind {
+    match app_type.to_ascii_lowercase().as_str() {
+        "dlc" => GameKind::Dlc,
+        "music" => GameKind::Soundtrack,
+        "tool" | "application" => GameKind::Tool,
+        "demo" => GameKind::Demo,
+        _ => GameKind::Base,
+    }
+}
+
+fn string_table(data: &[u8]) -> Option<Vec<String>> {
+    let mut reader = Reader::new(data);
+    let count = reader.u32()?;
+    (0..count).map(|_| reader.cstring()).collect()
+}
+
+// KeyValues binaire : un octet de type, la clé, puis la valeur
+fn read_object(reader: &mut Reader, keys: Option<&[String]>) -> Option<Vec<(String, VdfNode)>> {
+    let mut entries = Vec::new();
+
+    loop {
+        let value_type = reader.u8()?;
+        // 0x08 ferme l'objet ; 0x0B est la variante "alternative end"
+        if value_type == 0x08 || value_type == 0x0B {
+            return Some(entries);
+        }
+
+        let key = match keys {
+            Some(keys) => keys.get(reader.u32()? as usize)?.clone(),
+            None => reader.cstring()?,
+        };
+        let value = match value_type {
+            0x00 => VdfNode::Object(read_object(reader, keys)?),
+            0x01 => VdfNode::Value(reader.cstring()?),
+            0x02 | 0x04 | 0x06 => VdfNode::Value((reader.u32()? as i32).to_string()),
+            0x03 => VdfNode::Value(f32::from_bits(reader.u32()?).to_string()),
+            0x07 => VdfNode::Value(reader.u64()?.to_string()),
+            0x0A => VdfNode::Value((reader.u64()? as i64).to_string()),
+            _ => return None,
+        };
+        entries.push((key, value));
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    fn bytes(&mut self, length: usize) -> Option<&'a [u8]> {
+        let bytes = self.data.get(self.position..self.position.checked_add(length)?)?;
+        self.position += length;
+        Some(bytes)
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        self.bytes(1).map(|bytes| bytes[0])
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.bytes(4)?.try_into().ok().map(u32::from_le_bytes)
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        self.bytes(8)?.try_into().ok().map(u64::from_le_bytes)
+    }
+
+    fn cstring(&mut self) -> Option<String> {
+        let rest = self.data.get(self.position..)?;
+        let length = rest.iter().position(|byte| *byte == 0)?;
+        let value = String::from_utf8_lossy(&rest[..length]).into_owned();
+        self.position += length + 1;
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    enum Kv {
+        Text(&'static str, &'static str),
+        Int(&'static str, u32),
+        Object(&'static str, Vec<Kv>),
+    }
+
+    // KeyValues binaire ; depuis la v29 les clés sont des index dans la table
+    struct Encoder {
+        table: Option<Vec<String>>,
+    }
+
+    impl Encoder {
+        fn key(&mut self, out: &mut Vec<u8>, key: &str) {
+            match &mut self.table {
+                Some(table) => {
+                    let index = table.iter().position(|known| known == key).unwrap_or_else(|| {
+                        table.push(key.to_string());
+                        table.len() - 1
+                    });
+                    out.extend((index as u32).to_le_bytes());
+                }
+                None => {
+                    out.extend(key.as_bytes());
+                    out.push(0);
+                }
+            }
+        }
+
+        fn object(&mut self, out: &mut Vec<u8>, entries: &[Kv]) {
+            for entry in entries {
+                match entry {
+                    Kv::Text(key, value) => {
+                        out.push(0x01);
+                        self.key(out, key);
+                        out.extend(value.as_bytes());
+                        out.push(0);
+                    }
+                    Kv::Int(key, value) => {
+                        out.push(0x02);
+                        self.key(out, key);
+                        out.extend(value.to_le_bytes());
+                    }
+                    Kv::Object(key, children) => {
+                        out.push(0x00);
+                        self.key(out, key);
+                        self.object(out, children);
+                    }
+                }
+            }
+            out.push(0x08);
+        }
+    }
+
+    fn appinfo(magic: u32, apps: &[(u32, Vec<Kv>)]) -> Vec<u8> {
+        let entry_header = if magic == APPINFO_V27 { 40 } else { 60 };
+        let mut encoder = Encoder {
+            table: (magic == APPINFO_V29).then(Vec::new),
+        };
+
+        let mut body = Vec::new();
+        for (app_id, kv) in apps {
+            let mut entry = vec![0u8; entry_header];
+            encoder.object(&mut entry, kv);
+            body.extend(app_id.to_le_bytes());
+            body.extend((entry.len() as u32).to_le_bytes());
+            body.extend(entry);
+        }
+        body.extend(0u32.to_le_bytes());
+
+        let mut file = magic.to_le_bytes().to_vec();
+        file.extend(1u32.to_le_bytes());
+        if let Some(table) = encoder.table {
+            // magic, univers et l'offset lui-même précèdent les entrées
+            file.extend(((16 + body.len()) as u64).to_le_bytes());
+            file.extend(body);
+            file.extend((table.len() as u32).to_le_bytes());
+            for key in table {
+                file.extend(key.as_bytes());
+                file.push(0);
+            }
+        } else {
+            file.extend(body);
+        }
+        file
+    }
+
+    fn app(app_id: u32, name: &'static str, app_type: &'static str, parent: &'static str) -> (u32, Vec<Kv>) {
+        let kv = vec![Kv::Object(
+            "appinfo",
+            vec![
+                Kv::Int("appid", app_id),
+                Kv::Object(
+                    "common",
+                    vec![
+                        Kv::Text("name", name),
+                        Kv::Text("type", app_type),
+                        Kv::Text("parent", parent),
+                    ],
+                ),
+            ],
+        )];
+        (app_id, kv)
+    }
+
+    fn ids(app_ids: &[&str]) -> HashSet<String> {
+        app_ids.iter().map(|app_id| app_id.to_string()).collect()
+    }
+
+    #[test]
+    fn reads_every_supported_version() {
+        for magic in [APPINFO_V27, APPINFO_V28, APPINFO_V29] {
+            let data = appinfo(
+                magic,
+                &[app(10, "Foo", "Game", "0"), app(20, "Foo Expansion", "DLC", "10")],
+            );
+            let apps = parse_app_info(&data, &ids(&["10", "20"])).expect("valid appinfo");
+
+            let game = &apps["10"];
+            assert_eq!(game.name.as_deref(), Some("Foo"));
+            assert_eq!(game.kind, GameKind::Base);
+            assert_eq!(game.parent, None);
+
+            let dlc = &apps["20"];
+            assert_eq!(dlc.kind, GameKind::Dlc);
+            assert_eq!(dlc.parent.as_deref(), Some("10"));
+        }
+    }
+
+    #[test]
+    fn apps_not_requested_are_skipped_without_decoding() {
+        // Type de valeur inconnu : l'entrée 10 ferait échouer son décodage
+        let broken = (10, vec![Kv::Object("appinfo", Vec::new())]);
+        let mut data = appinfo(APPINFO_V28, &[broken, app(20, "Bar Soundtrack", "Music", "30")]);
+        let entry_start = 8 + 8 + 60;
+        data[entry_start] = 0xFF;
+
+        let apps = parse_app_info(&data, &ids(&["20"])).expect("valid appinfo");
+        assert_eq!(apps.len(), 1);
+        assert_eq!(apps["20"].kind, GameKind::Soundtrack);
+
+        assert!(parse_app_info(&data, &ids(&["10", "20"])).is_some_and(|apps| !apps.contains_key("10")));
+    }
+
+    #[test]
+    fn truncated_files_are_rejected() {
+        for magic in [APPINFO_V27, APPINFO_V29] {
+            let data = appinfo(magic, &[app(10, "Foo", "Game", "0")]);
+            for length in 0..data.len() {
+                assert!(parse_app_info(&data[..length], &ids(&["10"])).is_none(), "{} octets", length);
+            }
+        }
+        assert!(parse_app_info(&0x07564426u32.to_le_bytes(), &ids(&["10"])).is_none());
+    }
+}
@@ -0,0 +1,70 @@
+use super::playtime::local_account_ids;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+// Images déjà présentes sur le disque pour une application Steam
+#[derive(Debug, Clone, Default)]
+pub struct SteamArtwork {
+    pub cover: Option<PathBuf>,
+    pub background: Option<PathBuf>,
+    pub logo: Option<PathBuf>,
+    pub thumbnail: Option<PathBuf>,
+}
+
+impl SteamArtwork {
+    pub fn is_empty(&self) -> bool {
+        self.cover.is_none()
+            && self.background.is_none()
+            && self.logo.is_none()
+            && self.thumbnail.is_none()
+    }
+}
+
+// Les images personnalisées (userdata/<compte>/config/grid) passent avant
+// celles que Steam met en cache (appcache/librarycache)
+pub fn find_local_artwork(steam_path: &Path, app_id: &str) -> SteamArtwork {
+    let grid_dirs: Vec<PathBuf> = local_account_ids(steam_path)
+        .into_iter()
+        .map(|account_id| {
+            steam_path
+                .join("userdata")
+                .join(account_id)
+                .join("config")
+                .join("grid")
+        })
+        .collect();
+    let grid = |suffix: &str| {
+        grid_dirs.iter().find_map(|dir| {
+            ["png", "jpg", "jpeg", "webp"]
+                .iter()
+                .map(|extension| dir.join(format!("{}{}.{}", app_id, suffix, extension)))
+                .find(|path| path.is_file())
+        })
+    };
+
+    let library_cache = steam_path.join("appcache").join("librarycache");
+    let cached = |name: &str| library_cache_file(&library_cache, app_id, name);
+
+    SteamArtwork {
+        cover: grid("p").or_else(|| cached("library_600x900.jpg")),
+        background: grid("_hero").or_else(|| cached("library_hero.jpg")),
+        logo: grid("_logo").or_else(|| cached("logo.png")),
+        thumbnail: grid("").or_else(|| cached("header.jpg")),
+    }
+}
+
+// Ancien format : librarycache/<appid>_<nom> ; depuis 2024 : librarycache/<appid>/
+// avec parfois un sous-dossier haché
+fn library_cache_file(library_cache: &Path, app_id: &str, name: &str) -> Option<PathBuf> {
+    let flat = library_cache.join(format!("{}_{}", app_id, name));
+    if flat.is_file() {
+        return Some(flat);
+    }
+
+    WalkDir::new(library_cache.join(app_id))
+        .max_depth(2)
+        .into_iter()
+        .flatten()
+        .find(|entry| entry.file_type().is_file() && entry.file_name() == name)
+        .map(|entry| entry.into_path())
+}
@@ -1,4 +1,5 @@
-use crate::models::GameResult;
+use crate::models::{GameAction, GameResult};
+use crate::platforms::open_uri;
 use crate::utils::AppError;
 use std::process::Command;
 
@@ -68,7 +69,7 @@ impl crate::platforms::traits::GameLauncher for SteamGameLauncher {
         let app_id = self.extract_app_id(game_id)?;
 
         let status = Command::new("cmd")
-            .args(&["/C", &format!("start steam://run/{}", app_id)])
+            .args(["/C", &format!("start steam://run/{}", app_id)])
             .status()
             .map_err(|e| AppError {
                 message: format!("Failed to launch Steam game: {}", e),
@@ -88,7 +89,7 @@ impl crate::platforms::traits::GameLauncher for SteamGameLauncher {
 
         // Utiliser l'URI Steam pour arrêter le jeu
         let status = Command::new("cmd")
-            .args(&["/C", &format!("start steam://stop/{}", app_id)])
+            .args(["/C", &format!("start steam://stop/{}", app_id)])
             .status()
             .map_err(|e| AppError {
                 message: format!("Failed to stop Steam game: {}", e),
@@ -103,16 +104,28 @@ impl crate::platforms::traits::GameLauncher for SteamGameLauncher {
         Ok(())
     }
 
-    async fn is_game_running(&self, game_id: &str) -> GameResult<bool> {
-        let app_id = self.extract_app_id(game_id)?;
+    fn supported_actions(&self) -> Vec<GameAction> {
+        vec![
+            GameAction::Install,
+            GameAction::Uninstall,
+            GameAction::Verify,
+            GameAction::OpenStorePage,
+        ]
+    }
 
-        let output = Command::new("tasklist").output().map_err(|e| AppError {
-            message: format!("Failed to check running processes: {}", e),
-        })?;
+    async fn install_game(&self, game_id: &str) -> GameResult<()> {
+        open_uri(&format!("steam://install/{}", self.extract_app_id(game_id)?))
+    }
 
-        let processes = String::from_utf8_lossy(&output.stdout);
+    async fn uninstall_game(&self, game_id: &str) -> GameResult<()> {
+        open_uri(&format!("steam://uninstall/{}", self.extract_app_id(game_id)?))
+    }
+
+    async fn verify_game(&self, game_id: &str) -> GameResult<()> {
+        open_uri(&format!("steam://validate/{}", self.extract_app_id(game_id)?))
+    }
 
-        // Cette vérification est basique et pourrait être améliorée
-        Ok(processes.contains(&format!("steam_app_{}.exe", app_id)))
+    async fn open_store_page(&self, game_id: &str) -> GameResult<()> {
+        open_uri(&format!("steam://store/{}", self.extract_app_id(game_id)?))
     }
 }
@@ -1,15 +1,23 @@
+mod appinfo;
+mod artwork;
 mod launcher;
+mod playtime;
 mod scanner;
+mod screenshots;
+mod workshop;
 
-use crate::models::{Game, GameResult};
+use crate::api::{SteamApi, SteamOwnedGame};
+use crate::models::{Game, GameAction, GameResult, WorkshopItem};
 use crate::platforms::traits::*;
 use crate::utils::AppError;
-use crate::Database;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+pub use artwork::SteamArtwork;
 pub use launcher::SteamGameLauncher;
+pub use playtime::SteamPlaytime;
 pub use scanner::SteamGameScanner;
+pub use screenshots::SteamScreenshot;
 
 pub struct SteamPlatform {
     scanner: Arc<SteamGameScanner>,
@@ -17,41 +25,103 @@ pub struct SteamPlatform {
 }
 
 impl SteamPlatform {
-    pub fn new(database: Arc<Database>) -> Self {
+    pub fn new() -> Self {
         Self {
             scanner: Arc::new(SteamGameScanner::new()),
             launcher: Arc::new(SteamGameLauncher::new()),
         }
     }
-    pub async fn get_game_by_id(&self, platform_specific_id: &str) -> GameResult<Option<Game>> {
-        // Cette méthode pourrait être optimisée pour chercher directement le jeu
-        // au lieu de scanner toute la bibliothèque
-        let game_path = format!("appmanifest_{}.acf", platform_specific_id);
-        for library in self.scanner.get_library_paths() {
-            let full_path = library.join(&game_path);
-            if full_path.exists() {
-                if let Ok(game) = self.scanner.parse_acf_file(&full_path).await {
-                    return Ok(Some(game));
+
+    pub fn steam_path(&self) -> &Path {
+        self.scanner.steam_path()
+    }
+
+    pub fn workshop_items(&self, app_id: &str) -> Vec<WorkshopItem> {
+        self.scanner.workshop_items(app_id)
+    }
+
+    // Supprime le dossier d'un élément ; Steam le retélécharge tant que
+    // l'abonnement n'est pas retiré
+    pub fn remove_workshop_item(&self, item: &WorkshopItem) -> GameResult<()> {
+        // Chemin résolu (liens symboliques, "..") avant toute vérification
+        let path = item.path.canonicalize().map_err(|e| AppError {
+            message: format!("Failed to resolve workshop item {}: {}", item.item_id, e),
+        })?;
+        if !workshop::is_workshop_content(&path, &item.app_id) {
+            return Err(AppError {
+                message: format!("Refusing to remove {}", path.display()),
+            });
+        }
+
+        std::fs::remove_dir_all(&path).map_err(|e| AppError {
+            message: format!("Failed to remove workshop item {}: {}", item.item_id, e),
+        })
+    }
+
+    pub fn local_artwork(&self, app_id: &str) -> SteamArtwork {
+        artwork::find_local_artwork(self.steam_path(), app_id)
+    }
+
+    pub fn local_screenshots(&self) -> Vec<SteamScreenshot> {
+        screenshots::read_local_screenshots(self.steam_path())
+    }
+
+    pub fn screenshot_dirs(&self) -> Vec<PathBuf> {
+        screenshots::screenshot_dirs(self.steam_path())
+    }
+
+    pub fn local_playtime(&self) -> Vec<SteamPlaytime> {
+        playtime::read_local_playtime(self.steam_path())
+    }
+
+    pub async fn web_playtime(
+        &self,
+        api: &SteamApi,
+        steam_id: Option<String>,
+    ) -> GameResult<Vec<SteamPlaytime>> {
+        playtime::fetch_owned_playtime(api, &self.steam_ids(steam_id)?).await
+    }
+
+    // Jeux possédés par les comptes, installés ou non, sans doublon
+    pub async fn owned_games(
+        &self,
+        api: &SteamApi,
+        steam_id: Option<String>,
+    ) -> GameResult<Vec<SteamOwnedGame>> {
+        let mut owned: Vec<SteamOwnedGame> = Vec::new();
+        for steam_id in self.steam_ids(steam_id)? {
+            for game in api.get_owned_games(&steam_id).await? {
+                if !owned.iter().any(|existing| existing.appid == game.appid) {
+                    owned.push(game);
                 }
             }
         }
-        Ok(None)
+        Ok(owned)
+    }
+
+    // Sans steam_id, tous les comptes déjà connectés sur la machine
+    fn steam_ids(&self, steam_id: Option<String>) -> GameResult<Vec<String>> {
+        let steam_ids: Vec<String> = match steam_id {
+            Some(steam_id) => vec![steam_id],
+            None => playtime::local_account_ids(self.steam_path())
+                .iter()
+                .filter_map(|account_id| playtime::steam_id64(account_id))
+                .collect(),
+        };
+        if steam_ids.is_empty() {
+            return Err(AppError {
+                message: "No Steam account found".to_string(),
+            });
+        }
+
+        Ok(steam_ids)
     }
 }
 
-#[async_trait::async_trait]
 impl GamePlatform for SteamPlatform {
     fn platform_name(&self) -> &'static str {
         "Steam"
     }
-
-    fn supported_file_types(&self) -> Vec<&'static str> {
-        vec!["acf"]
-    }
-
-    async fn initialize(&mut self) -> GameResult<()> {
-        Ok(())
-    }
 }
 
 #[async_trait::async_trait]
@@ -59,20 +129,6 @@ impl GameScanner for SteamPlatform {
     async fn scan_games(&self) -> GameResult<Vec<Game>> {
         self.scanner.scan_games().await
     }
-
-    fn get_library_paths(&self) -> Vec<PathBuf> {
-        self.scanner.get_library_paths()
-    }
-
-    async fn add_library_path(&mut self, path: PathBuf) -> GameResult<()> {
-        if let Some(scanner) = Arc::get_mut(&mut self.scanner) {
-            scanner.add_library_path(path).await
-        } else {
-            Err(AppError {
-                message: "Failed to get mutable reference to scanner".into(),
-            })
-        }
-    }
 }
 
 #[async_trait::async_trait]
@@ -85,15 +141,23 @@ impl GameLauncher for SteamPlatform {
         self.launcher.stop_game(game_id).await
     }
 
-    async fn is_game_running(&self, game_id: &str) -> GameResult<bool> {
-        self.launcher.is_game_running(game_id).await
+    fn supported_actions(&self) -> Vec<GameAction> {
+        self.launcher.supported_actions()
     }
-}
 
-#[async_trait::async_trait]
-impl MetadataProvider for SteamPlatform {
-    async fn update_metadata(&self, _game: &mut Game) -> GameResult<()> {
-        // La mise à jour des métadonnées est maintenant gérée par le service RAWG
-        Ok(())
+    async fn install_game(&self, game_id: &str) -> GameResult<()> {
+        self.launcher.install_game(game_id).await
+    }
+
+    async fn uninstall_game(&self, game_id: &str) -> GameResult<()> {
+        self.launcher.uninstall_game(game_id).await
+    }
+
+    async fn verify_game(&self, game_id: &str) -> GameResult<()> {
+        self.launcher.verify_game(game_id).await
+    }
+
+    async fn open_store_page(&self, game_id: &str) -> GameResult<()> {
+        self.launcher.open_store_page(game_id).await
     }
 }
@@ -0,0 +1,236 @@
+use crate::log_warn;
+use crate::api::SteamApi;
+use crate::models::GameResult;
+use crate::utils::vdf::VdfParser;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+// Les dossiers userdata portent l'identifiant de compte 32 bits
+const STEAM_ID64_BASE: u64 = 76561197960265728;
+
+// Temps de jeu connu de Steam pour une application
+#[derive(Debug, Clone)]
+pub struct SteamPlaytime {
+    pub app_id: String,
+    pub playtime_seconds: i64,
+    pub last_played: Option<i64>,
+}
+
+// Comptes Steam ayant déjà été connectés sur cette machine
+pub fn local_account_ids(steam_path: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(steam_path.join("userdata")) else {
+        return Vec::new();
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| name != "0" && name.parse::<u32>().is_ok())
+        .collect()
+}
+
+pub fn steam_id64(account_id: &str) -> Option<String> {
+    account_id
+        .parse::<u64>()
+        .ok()
+        .map(|account_id| (STEAM_ID64_BASE + account_id).to_string())
+}
+
+// userdata/<compte>/config/localconfig.vdf de tous les comptes locaux
+pub fn read_local_playtime(steam_path: &Path) -> Vec<SteamPlaytime> {
+    let mut playtimes = Vec::new();
+
+    for account_id in local_account_ids(steam_path) {
+        let path = steam_path
+            .join("userdata")
+            .join(&account_id)
+            .join("config")
+            .join("localconfig.vdf");
+        let root = match VdfParser::parse_file(&path) {
+            Ok(root) => root,
+            Err(e) => {
+                log_warn!("Skipping Steam account {}: {}", account_id, e);
+                continue;
+            }
+        };
+        let Some(apps) =
+            root.get_path(&["UserLocalConfigStore", "Software", "Valve", "Steam", "apps"])
+        else {
+            continue;
+        };
+
+        for (app_id, app) in apps.entries() {
+            // Playtime est en minutes
+            let playtime = app
+                .value("Playtime")
+                .and_then(|value| value.parse::<i64>().ok())
+                .unwrap_or(0);
+            let last_played = app
+                .value("LastPlayed")
+                .and_then(|value| value.parse::<i64>().ok())
+                .filter(|timestamp| *timestamp > 0);
+
+            if playtime > 0 || last_played.is_some() {
+                playtimes.push(SteamPlaytime {
+                    app_id: app_id.clone(),
+                    playtime_seconds: playtime * 60,
+                    last_played,
+                });
+            }
+        }
+    }
+
+    merge(playtimes)
+}
+
+// IPlayerService/GetOwnedGames pour chaque compte (le profil doit être public
+// ou la clé appartenir au compte)
+pub async fn fetch_owned_playtime(api: &SteamApi, steam_ids: &[String]) -> GameResult<Vec<SteamPlaytime>> {
+    let mut playtimes = Vec::new();
+
+    for steam_id in steam_ids {
+        for game in api.get_owned_games(steam_id).await? {
+            playtimes.push(SteamPlaytime {
+                app_id: game.appid.to_string(),
+                playtime_seconds: game.playtime_forever * 60,
+                last_played: Some(game.rtime_last_played).filter(|timestamp| *timestamp > 0),
+            });
+        }
+    }
+
+    Ok(merge(playtimes))
+}
+
+// Un même jeu sur plusieurs comptes : on garde les valeurs les plus élevées
+fn merge(playtimes: Vec<SteamPlaytime>) -> Vec<SteamPlaytime> {
+    let mut merged: HashMap<String, SteamPlaytime> = HashMap::new();
+
+    for playtime in playtimes {
+        match merged.get_mut(&playtime.app_id) {
+            Some(existing) => {
+                existing.playtime_seconds = existing.playtime_seconds.max(playtime.playtime_seconds);
+                existing.last_played = existing.last_played.max(playtime.last_played);
+            }
+            None => {
+                merged.insert(playtime.app_id.clone(), playtime);
+            }
+        }
+    }
+
+    merged.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_localconfig(steam_path: &Path, account_id: &str, apps: &str) {
+        let config = steam_path.join("userdata").join(account_id).join("config");
+        fs::create_dir_all(&config).unwrap();
+        let content = format!(
+            r#""UserLocalConfigStore"
+{{
+	"Software"
+	{{
+		"Valve"
+		{{
+			"Steam"
+			{{
+				"apps"
+				{{
+{}
+				}}
+			}}
+		}}
+	}}
+}}"#,
+            apps
+        );
+        fs::write(config.join("localconfig.vdf"), content).unwrap();
+    }
+
+    fn by_app(playtimes: Vec<SteamPlaytime>) -> Vec<(String, i64, Option<i64>)> {
+        let mut playtimes: Vec<_> = playtimes
+            .into_iter()
+            .map(|playtime| (playtime.app_id, playtime.playtime_seconds, playtime.last_played))
+            .collect();
+        playtimes.sort();
+        playtimes
+    }
+
+    #[test]
+    fn reads_localconfig_of_every_account() {
+        let steam = tempfile::tempdir().unwrap();
+        write_localconfig(
+            steam.path(),
+            "1001",
+            r#"
+					"440"
+					{
+						"Playtime"		"90"
+						"LastPlayed"		"1700000000"
+					}
+					"570"
+					{
+						"Playtime"		"15"
+						"LastPlayed"		"0"
+					}
+					"730"
+					{
+						"LastPlayed"		"0"
+					}
+			"#,
+        );
+        write_localconfig(
+            steam.path(),
+            "1002",
+            r#"
+					"440"
+					{
+						"Playtime"		"30"
+						"LastPlayed"		"1710000000"
+					}
+			"#,
+        );
+        // Ni compte "0", ni dossier non numérique
+        write_localconfig(steam.path(), "0", r#""10" { "Playtime" "999" }"#);
+        write_localconfig(steam.path(), "anonymous", r#""10" { "Playtime" "999" }"#);
+
+        assert_eq!(
+            by_app(read_local_playtime(steam.path())),
+            vec![
+                // Temps le plus élevé et dernière partie la plus récente des deux comptes
+                ("440".to_string(), 90 * 60, Some(1710000000)),
+                // LastPlayed à 0 : date inconnue
+                ("570".to_string(), 15 * 60, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_keeps_the_highest_values() {
+        let playtime = |app_id: &str, minutes: i64, last_played: Option<i64>| SteamPlaytime {
+            app_id: app_id.to_string(),
+            playtime_seconds: minutes * 60,
+            last_played,
+        };
+
+        let merged = merge(vec![
+            playtime("10", 5, Some(300)),
+            playtime("10", 50, None),
+            playtime("10", 20, Some(100)),
+            playtime("20", 1, None),
+        ]);
+        assert_eq!(
+            by_app(merged),
+            vec![("10".to_string(), 3000, Some(300)), ("20".to_string(), 60, None)]
+        );
+    }
+
+    #[test]
+    fn steam_id64_offsets_the_account_id() {
+        assert_eq!(steam_id64("1001").as_deref(), Some("76561197960266729"));
+        assert_eq!(steam_id64("anonymous"), None);
+    }
+}
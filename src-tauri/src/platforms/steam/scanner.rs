@@ -1,9 +1,12 @@
+use super::appinfo::read_app_info;
 use crate::models::{
-    Game, GameInstallation, GameMedia, GameMetadata, GameResult, GameStats, Platform,
+    Game, GameInstallation, GameKind, GameMedia, GameMetadata, GameResult, GameStats, Platform,
+    WorkshopItem,
 };
 use crate::utils::vdf::VdfParser;
 use crate::utils::{acf::AcfParser, AppError};
-use std::collections::HashSet;
+use crate::log_warn;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use tokio::sync::Mutex;
@@ -41,6 +44,7 @@ impl SteamGameScanner {
         }
     }
 
+    #[cfg(windows)]
     fn get_steam_path() -> GameResult<PathBuf> {
         use winreg::enums::*;
         use winreg::RegKey;
@@ -59,6 +63,22 @@ impl SteamGameScanner {
         Ok(PathBuf::from(install_path))
     }
 
+    // Client Steam natif : ~/.steam/steam est un lien vers le dossier d'installation
+    #[cfg(not(windows))]
+    fn get_steam_path() -> GameResult<PathBuf> {
+        let home = dirs::home_dir().ok_or_else(|| AppError {
+            message: "Failed to get home directory".to_string(),
+        })?;
+
+        [".steam/steam", ".local/share/Steam"]
+            .iter()
+            .map(|relative| home.join(relative))
+            .find(|path| path.is_dir())
+            .ok_or_else(|| AppError {
+                message: "Failed to find Steam installation".to_string(),
+            })
+    }
+
     fn find_game_path(&self, install_dir: &str) -> GameResult<PathBuf> {
         // Vérifier d'abord dans le dossier "common" de chaque bibliothèque
         for library in &self.library_folders {
@@ -100,8 +120,7 @@ impl SteamGameScanner {
         // On garde le jeu même si on ne trouve pas l'exécutable
         let executable = self
             .find_game_executable(&game_path, &acf_data.name)
-            .ok()
-            .map(String::from);
+            .ok();
 
         Ok(Game {
             id: format!("steam_{}", acf_data.app_id),
@@ -134,16 +153,33 @@ impl SteamGameScanner {
                 logo: None,
             },
             last_played: None,
+            installed: true,
             stats: GameStats {
                 total_playtime: 0,
                 last_session_duration: 0,
                 sessions_count: 0,
                 first_played: None,
                 last_played: None,
+                crash_count: 0,
+                completed_at: None,
+                imported_playtime: 0,
+                imported_last_played: None,
             },
+            kind: GameKind::Base,
+            parent_game_id: None,
+            addons: Vec::new(),
         })
     }
 
+    pub fn steam_path(&self) -> &Path {
+        &self.install_path
+    }
+
+    // Éléments du Workshop installés pour une application, toutes bibliothèques confondues
+    pub fn workshop_items(&self, app_id: &str) -> Vec<WorkshopItem> {
+        super::workshop::read_workshop_items(&self.get_library_paths(), app_id)
+    }
+
     pub fn get_library_paths(&self) -> Vec<PathBuf> {
         // Retourner toutes les bibliothèques, y compris les dossiers utilisateur
         let mut paths = self.library_folders.clone();
@@ -162,56 +198,112 @@ impl SteamGameScanner {
             .map(|path| path.to_string_lossy().to_string())
     }
 
-    pub async fn add_library_path(&self, path: PathBuf) -> GameResult<()> {
-        if !path.exists() {
-            return Err(AppError {
-                message: format!("Path does not exist: {:?}", path),
-            });
-        }
-
-        let mut folders = self.user_added_folders.lock().await;
-        if !folders.contains(&path) {
-            folders.push(path);
-        }
-
-        Ok(())
-    }
-
     pub async fn scan_games(&self) -> GameResult<Vec<Game>> {
-        let mut all_games = Vec::new();
-
-        // Scanner toutes les bibliothèques Steam
-        for library_path in &self.library_folders {
+        // Manifestes de toutes les bibliothèques Steam et des dossiers utilisateur
+        let mut manifests = Vec::new();
+        let user_folders = self.user_added_folders.lock().await;
+        for library_path in self.library_folders.iter().chain(user_folders.iter()) {
             if let Ok(entries) = fs::read_dir(library_path) {
                 for entry in entries.flatten() {
                     let path = entry.path();
                     if path.extension().and_then(|s| s.to_str()) == Some("acf") {
-                        match self.parse_acf_file(&path).await {
-                            Ok(game) => {
-                                all_games.push(game);
-                            }
-                            Err(e) => eprintln!("Error parsing ACF file {:?}: {}", path, e),
-                        }
+                        manifests.push(path);
                     }
                 }
             }
         }
+        drop(user_folders);
 
-        // Scanner les dossiers ajoutés par l'utilisateur
-        let user_folders = self.user_added_folders.lock().await;
-        for folder in user_folders.iter() {
-            if let Ok(entries) = fs::read_dir(folder) {
-                for entry in entries.flatten() {
-                    let path = entry.path();
-                    if path.extension().and_then(|s| s.to_str()) == Some("acf") {
-                        if let Ok(game) = self.parse_acf_file(&path).await {
-                            all_games.push(game);
-                        }
+        let mut all_games = Vec::new();
+        // (index du jeu de base, appid du DLC, taille installée)
+        let mut dlcs = Vec::new();
+        for path in manifests {
+            match self.parse_acf_file(&path).await {
+                Ok(game) => {
+                    for (app_id, size) in installed_dlcs(&path) {
+                        dlcs.push((all_games.len(), app_id, size));
                     }
+                    all_games.push(game);
+                }
+                Err(e) => log_warn!("Error parsing ACF file {:?}: {}", path, e),
+            }
+        }
+
+        // Bandes-son, outils et démos ont leur propre manifeste : seul appinfo.vdf
+        // donne leur type et leur jeu parent
+        let app_ids: HashSet<String> = all_games
+            .iter()
+            .map(|game| game.platform_id.clone())
+            .chain(dlcs.iter().map(|(_, app_id, _)| app_id.clone()))
+            .collect();
+        let app_info = read_app_info(&self.install_path, &app_ids);
+
+        for game in &mut all_games {
+            if let Some(info) = app_info.get(&game.platform_id) {
+                game.kind = info.kind;
+                if info.kind != GameKind::Base {
+                    game.parent_game_id = info.parent.as_ref().map(|parent| format!("steam_{}", parent));
                 }
             }
         }
 
+        let mut known_ids: HashSet<String> = all_games.iter().map(|game| game.id.clone()).collect();
+        for (parent, app_id, size) in dlcs {
+            if !known_ids.insert(format!("steam_{}", app_id)) {
+                continue;
+            }
+            let name = app_info.get(&app_id).and_then(|info| info.name.clone());
+            let dlc = dlc_game(&all_games[parent], app_id, size, name);
+            all_games.push(dlc);
+        }
+
         Ok(all_games)
     }
 }
+
+// Les DLC n'ont pas de manifeste : ils apparaissent dans les InstalledDepots du
+// jeu de base avec un dlcappid ; un DLC peut compter plusieurs dépôts
+fn installed_dlcs(manifest: &Path) -> Vec<(String, u64)> {
+    let Ok(root) = VdfParser::parse_file(manifest) else {
+        return Vec::new();
+    };
+    let Some(depots) = root.get_path(&["AppState", "InstalledDepots"]) else {
+        return Vec::new();
+    };
+
+    let mut sizes: HashMap<String, u64> = HashMap::new();
+    for (_, depot) in depots.entries() {
+        if let Some(app_id) = depot.value("dlcappid") {
+            let size = depot
+                .value("size")
+                .and_then(|size| size.parse::<u64>().ok())
+                .unwrap_or(0);
+            *sizes.entry(app_id.to_string()).or_default() += size;
+        }
+    }
+    sizes.into_iter().collect()
+}
+
+fn dlc_game(parent: &Game, app_id: String, size: u64, name: Option<String>) -> Game {
+    let title = name.unwrap_or_else(|| format!("{} DLC {}", parent.title, app_id));
+
+    Game {
+        id: format!("steam_{}", app_id),
+        platform_id: app_id,
+        title: title.clone(),
+        installation: GameInstallation {
+            install_path: parent.installation.install_path.clone(),
+            executable: None,
+            size,
+            version: None,
+            last_updated: parent.installation.last_updated,
+        },
+        metadata: GameMetadata {
+            title,
+            ..parent.metadata.clone()
+        },
+        kind: GameKind::Dlc,
+        parent_game_id: Some(parent.id.clone()),
+        ..parent.clone()
+    }
+}
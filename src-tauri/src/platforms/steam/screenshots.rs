@@ -0,0 +1,172 @@
+use super::playtime::local_account_ids;
+use crate::utils::image_header::is_image;
+use crate::utils::vdf::VdfParser;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Capture faite avec la touche F12 de Steam
+#[derive(Debug, Clone)]
+pub struct SteamScreenshot {
+    pub app_id: String,
+    pub path: PathBuf,
+    pub caption: Option<String>,
+    pub created: Option<i64>,
+}
+
+// Dossiers userdata/<compte>/760/remote à surveiller
+pub fn screenshot_dirs(steam_path: &Path) -> Vec<PathBuf> {
+    local_account_ids(steam_path)
+        .into_iter()
+        .map(|account_id| {
+            steam_path
+                .join("userdata")
+                .join(account_id)
+                .join("760")
+                .join("remote")
+        })
+        .filter(|path| path.exists())
+        .collect()
+}
+
+// remote/<appid>/screenshots de chaque compte ; légende et date viennent de
+// 760/screenshots.vdf quand Steam les y a inscrites
+pub fn read_local_screenshots(steam_path: &Path) -> Vec<SteamScreenshot> {
+    let mut screenshots = Vec::new();
+
+    for remote in screenshot_dirs(steam_path) {
+        let details = remote
+            .parent()
+            .map(|base| read_screenshot_details(&base.join("screenshots.vdf")))
+            .unwrap_or_default();
+
+        let Ok(apps) = fs::read_dir(&remote) else {
+            continue;
+        };
+        for app in apps.flatten() {
+            let app_id = app.file_name().to_string_lossy().to_string();
+            let Ok(files) = fs::read_dir(app.path().join("screenshots")) else {
+                continue;
+            };
+
+            for file in files.flatten() {
+                let path = file.path();
+                if !path.is_file() || !is_image(&path) {
+                    continue;
+                }
+                let key = format!("{}/{}", app_id, file.file_name().to_string_lossy()).to_lowercase();
+                let (caption, created) = details.get(&key).cloned().unwrap_or_default();
+
+                screenshots.push(SteamScreenshot {
+                    app_id: app_id.clone(),
+                    path,
+                    caption,
+                    created,
+                });
+            }
+        }
+    }
+
+    screenshots
+}
+
+// Clé : "<appid>/<fichier>" en minuscules
+fn read_screenshot_details(path: &Path) -> HashMap<String, (Option<String>, Option<i64>)> {
+    let mut details = HashMap::new();
+    let Ok(root) = VdfParser::parse_file(path) else {
+        return details;
+    };
+    let Some(apps) = root.get("Screenshots") else {
+        return details;
+    };
+
+    for (app_id, shots) in apps.entries() {
+        for (_, shot) in shots.entries() {
+            // filename vaut "<appid>/screenshots/<fichier>"
+            let Some(file_name) = shot
+                .value("filename")
+                .and_then(|filename| filename.rsplit(['/', '\\']).next())
+            else {
+                continue;
+            };
+
+            let caption = shot
+                .value("caption")
+                .filter(|caption| !caption.is_empty())
+                .map(str::to_string);
+            let created = shot
+                .value("creation")
+                .and_then(|value| value.parse::<i64>().ok())
+                .filter(|timestamp| *timestamp > 0);
+            details.insert(
+                format!("{}/{}", app_id, file_name).to_lowercase(),
+                (caption, created),
+            );
+        }
+    }
+
+    details
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCREENSHOTS_VDF: &str = r#"
+"Screenshots"
+{
+	"440"
+	{
+		"0"
+		{
+			"type"		"1"
+			"filename"		"440/screenshots/20240101120000_1.jpg"
+			"thumbnail"		"440/screenshots/thumbnails/20240101120000_1.jpg"
+			"caption"		"Boss fight"
+			"creation"		"1704110400"
+		}
+		"1"
+		{
+			"filename"		"440/screenshots/20240101120500_1.jpg"
+			"caption"		""
+			"creation"		"0"
+		}
+	}
+}
+"#;
+
+    #[test]
+    fn captions_come_from_screenshots_vdf() {
+        let steam = tempfile::tempdir().unwrap();
+        let base = steam.path().join("userdata").join("12345").join("760");
+        let shots = base.join("remote").join("440").join("screenshots");
+        fs::create_dir_all(&shots).unwrap();
+        fs::write(base.join("screenshots.vdf"), SCREENSHOTS_VDF).unwrap();
+        // La casse du fichier peut différer de celle inscrite par Steam
+        for file in ["20240101120000_1.JPG", "20240101120500_1.jpg", "20240101130000_1.jpg", "notes.txt"] {
+            fs::write(shots.join(file), b"").unwrap();
+        }
+
+        let mut screenshots = read_local_screenshots(steam.path());
+        screenshots.sort_by_key(|shot| shot.path.clone());
+        let found: Vec<(String, Option<&str>, Option<i64>)> = screenshots
+            .iter()
+            .map(|shot| {
+                let file = shot.path.file_name().unwrap().to_string_lossy().to_string();
+                (file, shot.caption.as_deref(), shot.created)
+            })
+            .collect();
+
+        assert!(screenshots.iter().all(|shot| shot.app_id == "440"));
+        assert_eq!(
+            found,
+            vec![
+                ("20240101120000_1.JPG".to_string(), Some("Boss fight"), Some(1704110400)),
+                // Légende vide et date nulle : inconnues
+                ("20240101120500_1.jpg".to_string(), None, None),
+                // Absente de screenshots.vdf
+                ("20240101130000_1.jpg".to_string(), None, None),
+            ]
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use crate::log_warn;
+use crate::models::WorkshopItem;
+use crate::utils::vdf::VdfParser;
+use std::path::{Path, PathBuf};
+
+// steamapps/workshop/appworkshop_<appid>.acf de chaque bibliothèque ; le contenu
+// des éléments est dans steamapps/workshop/content/<appid>/<itemid>
+pub fn read_workshop_items(library_folders: &[PathBuf], app_id: &str) -> Vec<WorkshopItem> {
+    let mut items: Vec<WorkshopItem> = Vec::new();
+
+    for library in library_folders {
+        let workshop_dir = library.join("workshop");
+        let manifest = workshop_dir.join(format!("appworkshop_{}.acf", app_id));
+        if !manifest.exists() {
+            continue;
+        }
+
+        let root = match VdfParser::parse_file(&manifest) {
+            Ok(root) => root,
+            Err(e) => {
+                log_warn!("Skipping {}: {}", manifest.display(), e);
+                continue;
+            }
+        };
+        let Some(installed) = root.get_path(&["AppWorkshop", "WorkshopItemsInstalled"]) else {
+            continue;
+        };
+
+        let content_dir = workshop_dir.join("content").join(app_id);
+        for (item_id, item) in installed.entries() {
+            // L'identifiant devient un nom de dossier : rien d'autre que des chiffres
+            if !is_item_id(item_id) || items.iter().any(|existing| &existing.item_id == item_id) {
+                continue;
+            }
+
+            items.push(WorkshopItem {
+                item_id: item_id.clone(),
+                app_id: app_id.to_string(),
+                path: content_dir.join(item_id),
+                size: item
+                    .value("size")
+                    .and_then(|value| value.parse().ok())
+                    .unwrap_or(0),
+                time_updated: item
+                    .value("timeupdated")
+                    .and_then(|value| value.parse().ok())
+                    .filter(|timestamp| *timestamp > 0),
+                title: None,
+                preview_url: None,
+            });
+        }
+    }
+
+    items.sort_by_key(|item| std::cmp::Reverse(item.time_updated));
+    items
+}
+
+fn is_item_id(item_id: &str) -> bool {
+    !item_id.is_empty() && item_id.bytes().all(|byte| byte.is_ascii_digit())
+}
+
+// Un élément ne peut être supprimé que dans le dossier content du jeu
+pub fn is_workshop_content(path: &Path, app_id: &str) -> bool {
+    let is_item = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(is_item_id);
+
+    is_item
+        && path.parent().is_some_and(|parent| {
+            parent.file_name().is_some_and(|name| name == app_id)
+                && parent
+                    .parent()
+                    .and_then(Path::file_name)
+                    .is_some_and(|name| name == "content")
+        })
+}
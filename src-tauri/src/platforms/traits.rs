@@ -1,31 +1,45 @@
-use crate::models::{Game, GameResult};
+use crate::models::{Game, GameAction, GameResult};
+use crate::utils::AppError;
 use async_trait::async_trait;
-use std::path::PathBuf;
 
 #[async_trait]
 pub trait GameScanner: Send + Sync {
     async fn scan_games(&self) -> GameResult<Vec<Game>>;
-    fn get_library_paths(&self) -> Vec<PathBuf>;
-    async fn add_library_path(&mut self, path: PathBuf) -> GameResult<()>;
 }
 
 #[async_trait]
 pub trait GameLauncher: Send + Sync {
     async fn launch_game(&self, game_id: &str) -> GameResult<()>;
     async fn stop_game(&self, game_id: &str) -> GameResult<()>;
-    async fn is_game_running(&self, game_id: &str) -> GameResult<bool>;
-}
 
-#[async_trait]
-pub trait MetadataProvider: Send + Sync {
-    async fn update_metadata(&self, game: &mut Game) -> GameResult<()> {
-        Ok(())
+    // Capacités optionnelles : chaque plateforme déclare celles qu'elle gère
+    fn supported_actions(&self) -> Vec<GameAction> {
+        Vec::new()
+    }
+
+    async fn install_game(&self, game_id: &str) -> GameResult<()> {
+        unsupported(game_id, GameAction::Install)
+    }
+
+    async fn uninstall_game(&self, game_id: &str) -> GameResult<()> {
+        unsupported(game_id, GameAction::Uninstall)
+    }
+
+    async fn verify_game(&self, game_id: &str) -> GameResult<()> {
+        unsupported(game_id, GameAction::Verify)
+    }
+
+    async fn open_store_page(&self, game_id: &str) -> GameResult<()> {
+        unsupported(game_id, GameAction::OpenStorePage)
     }
 }
 
-#[async_trait]
-pub trait GamePlatform: GameScanner + GameLauncher + MetadataProvider {
+fn unsupported(game_id: &str, action: GameAction) -> GameResult<()> {
+    Err(AppError {
+        message: format!("{:?} is not supported for {}", action, game_id),
+    })
+}
+
+pub trait GamePlatform: GameScanner + GameLauncher {
     fn platform_name(&self) -> &'static str;
-    fn supported_file_types(&self) -> Vec<&'static str>;
-    async fn initialize(&mut self) -> GameResult<()>;
 }
@@ -0,0 +1,110 @@
+// src/runners/mod.rs
+mod wine;
+
+pub use wine::{create_prefix, discover_wine_installs, normalize_dll_overrides};
+
+use crate::models::{GameResult, RunnerConfig, RunnerKind};
+use crate::utils::AppError;
+use std::path::Path;
+use tokio::process::Command;
+
+// GAMEID attendu par umu-run quand le jeu n'a pas d'identifiant connu
+const UMU_DEFAULT_GAME_ID: &str = "0";
+
+// Sur Linux, un .exe sans runner explicite passe par le Wine système
+pub fn effective_config(config: Option<RunnerConfig>, executable: &str) -> RunnerConfig {
+    match config {
+        Some(config) => config,
+        None if !cfg!(windows) && executable.to_lowercase().ends_with(".exe") => RunnerConfig {
+            kind: RunnerKind::Wine,
+            ..RunnerConfig::default()
+        },
+        None => RunnerConfig::default(),
+    }
+}
+
+pub fn build_command(
+    config: &RunnerConfig,
+    executable: &str,
+    working_dir: &Path,
+) -> GameResult<Command> {
+    let mut command = match config.kind {
+        RunnerKind::Native => Command::new(executable),
+        RunnerKind::Wine => {
+            let mut command = Command::new(config.wine_path.as_deref().unwrap_or("wine"));
+            command.arg(executable);
+            command
+        }
+        RunnerKind::Proton => {
+            let mut command = Command::new("umu-run");
+            command.arg(executable);
+            command.env("GAMEID", UMU_DEFAULT_GAME_ID);
+            if let Some(proton_path) = &config.wine_path {
+                command.env("PROTONPATH", proton_path);
+            }
+            command
+        }
+    };
+
+    if config.kind != RunnerKind::Native {
+        if let Some(prefix) = &config.prefix_path {
+            if !Path::new(prefix).exists() {
+                return Err(AppError {
+                    message: format!("Wine prefix not found: {}", prefix),
+                });
+            }
+            command.env("WINEPREFIX", prefix);
+        }
+
+        if !config.dll_overrides.is_empty() {
+            command.env("WINEDLLOVERRIDES", dll_overrides_env(config));
+        }
+    }
+
+    if working_dir.is_dir() {
+        command.current_dir(working_dir);
+    }
+
+    Ok(command)
+}
+
+// Format WINEDLLOVERRIDES : "d3d11=n,b;dxgi=n"
+fn dll_overrides_env(config: &RunnerConfig) -> String {
+    let mut overrides: Vec<String> = config
+        .dll_overrides
+        .iter()
+        .map(|(dll, mode)| format!("{}={}", dll, mode))
+        .collect();
+    overrides.sort();
+    overrides.join(";")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exe_without_runner_uses_wine_outside_windows() {
+        let config = effective_config(None, "/games/Foo/Foo.EXE");
+        let expected = if cfg!(windows) {
+            RunnerKind::Native
+        } else {
+            RunnerKind::Wine
+        };
+        assert_eq!(config.kind, expected);
+    }
+
+    #[test]
+    fn explicit_runner_and_native_binaries_are_kept() {
+        assert_eq!(effective_config(None, "/games/Foo/foo.sh").kind, RunnerKind::Native);
+
+        let proton = RunnerConfig {
+            kind: RunnerKind::Proton,
+            ..RunnerConfig::default()
+        };
+        assert_eq!(effective_config(Some(proton), "/games/Foo/Foo.exe").kind, RunnerKind::Proton);
+
+        let native = RunnerConfig::default();
+        assert_eq!(effective_config(Some(native), "/games/Foo/Foo.exe").kind, RunnerKind::Native);
+    }
+}
@@ -0,0 +1,260 @@
+// src/runners/wine.rs
+use crate::log_debug;
+use crate::models::{GameResult, RunnerKind, WineInstall};
+use crate::utils::AppError;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+const SYSTEM_WINE_DIRS: &[&str] = &[
+    "/usr/bin",
+    "/usr/local/bin",
+    "/opt/wine-stable/bin",
+    "/opt/wine-staging/bin",
+    "/opt/wine-devel/bin",
+];
+
+pub fn discover_wine_installs() -> Vec<WineInstall> {
+    let mut installs = Vec::new();
+
+    // Wine système
+    for dir in SYSTEM_WINE_DIRS {
+        for binary in ["wine", "wine64"] {
+            let path = Path::new(dir).join(binary);
+            if path.is_file() {
+                installs.push(WineInstall {
+                    name: format!("System {}", binary),
+                    kind: RunnerKind::Wine,
+                    version: wine_version(&path),
+                    path: path.to_string_lossy().to_string(),
+                    source: "system".to_string(),
+                });
+            }
+        }
+    }
+
+    if let Some(home) = dirs::home_dir() {
+        // Runners Wine installés par Lutris
+        for dir in sub_directories(&home.join(".local/share/lutris/runners/wine")) {
+            let binary = dir.join("bin").join("wine");
+            if binary.is_file() {
+                installs.push(WineInstall {
+                    name: directory_name(&dir),
+                    kind: RunnerKind::Wine,
+                    version: wine_version(&binary),
+                    path: binary.to_string_lossy().to_string(),
+                    source: "lutris".to_string(),
+                });
+            }
+        }
+
+        // Proton fourni par Steam et builds custom (GE-Proton, ...)
+        let steam_roots = [
+            home.join(".steam/root"),
+            home.join(".steam/steam"),
+            home.join(".local/share/Steam"),
+            home.join(".var/app/com.valvesoftware.Steam/data/Steam"),
+        ];
+        for root in &steam_roots {
+            for (dir, source) in sub_directories(&root.join("steamapps/common"))
+                .into_iter()
+                .map(|dir| (dir, "steam"))
+                .chain(
+                    sub_directories(&root.join("compatibilitytools.d"))
+                        .into_iter()
+                        .map(|dir| (dir, "custom")),
+                )
+            {
+                if dir.join("proton").is_file() {
+                    installs.push(WineInstall {
+                        name: directory_name(&dir),
+                        kind: RunnerKind::Proton,
+                        version: proton_version(&dir),
+                        path: dir.to_string_lossy().to_string(),
+                        source: source.to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    // Plusieurs racines Steam pointent souvent vers le même dossier
+    let mut seen = HashSet::new();
+    installs.retain(|install| {
+        let key = fs::canonicalize(&install.path).unwrap_or_else(|_| PathBuf::from(&install.path));
+        seen.insert(key)
+    });
+
+    log_debug!("Found {} Wine/Proton installs", installs.len());
+    installs
+}
+
+pub async fn create_prefix(
+    kind: &RunnerKind,
+    runner_path: Option<&str>,
+    prefix: &Path,
+) -> GameResult<()> {
+    let mut command = match kind {
+        RunnerKind::Wine => {
+            let mut command = Command::new(runner_path.unwrap_or("wine"));
+            command.arg("wineboot").arg("--init");
+            command
+        }
+        RunnerKind::Proton => {
+            let mut command = Command::new("umu-run");
+            command.arg("wineboot").arg("--init");
+            command.env("GAMEID", super::UMU_DEFAULT_GAME_ID);
+            if let Some(proton_path) = runner_path {
+                command.env("PROTONPATH", proton_path);
+            }
+            command
+        }
+        RunnerKind::Native => {
+            return Err(AppError {
+                message: "Native runner does not use a Wine prefix".to_string(),
+            })
+        }
+    };
+
+    fs::create_dir_all(prefix).map_err(|e| AppError {
+        message: format!("Failed to create prefix directory: {}", e),
+    })?;
+
+    let status = command
+        .env("WINEPREFIX", prefix)
+        .env("WINEDEBUG", "-all")
+        .status()
+        .await
+        .map_err(|e| AppError {
+            message: format!("Failed to run wineboot: {}", e),
+        })?;
+
+    if !status.success() {
+        return Err(AppError {
+            message: format!("wineboot failed for prefix {}: {}", prefix.display(), status),
+        });
+    }
+
+    Ok(())
+}
+
+// Accepte les formes longues ("native,builtin") et courtes ("n,b") de Wine
+pub fn normalize_dll_override(dll: &str, mode: &str) -> GameResult<(String, String)> {
+    let dll = dll.trim().to_lowercase();
+    let dll = dll.strip_suffix(".dll").unwrap_or(&dll).to_string();
+    if dll.is_empty() || dll.contains(['=', ';', ',']) {
+        return Err(AppError {
+            message: format!("Invalid DLL name: {}", dll),
+        });
+    }
+
+    let mode = mode
+        .split(',')
+        .map(|part| match part.trim().to_lowercase().as_str() {
+            "n" | "native" => Ok("n"),
+            "b" | "builtin" => Ok("b"),
+            "" | "disabled" => Ok(""),
+            other => Err(AppError {
+                message: format!("Invalid DLL override mode: {}", other),
+            }),
+        })
+        .collect::<GameResult<Vec<_>>>()?
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(",");
+
+    Ok((dll, mode))
+}
+
+pub fn normalize_dll_overrides(
+    overrides: &HashMap<String, String>,
+) -> GameResult<HashMap<String, String>> {
+    overrides
+        .iter()
+        .map(|(dll, mode)| normalize_dll_override(dll, mode))
+        .collect()
+}
+
+fn sub_directories(path: &Path) -> Vec<PathBuf> {
+    fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn directory_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string_lossy().to_string())
+}
+
+fn wine_version(binary: &Path) -> Option<String> {
+    let output = std::process::Command::new(binary)
+        .arg("--version")
+        .output()
+        .ok()?;
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!version.is_empty()).then_some(version)
+}
+
+// Le fichier "version" de Proton contient "<timestamp> <version>"
+fn proton_version(dir: &Path) -> Option<String> {
+    fs::read_to_string(dir.join("version"))
+        .ok()?
+        .split_whitespace()
+        .last()
+        .map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dll_override_accepts_long_and_short_modes() {
+        assert_eq!(
+            normalize_dll_override(" D3D11.dll ", "native, Builtin").unwrap(),
+            ("d3d11".to_string(), "n,b".to_string())
+        );
+        assert_eq!(
+            normalize_dll_override("dxgi", "b,n").unwrap(),
+            ("dxgi".to_string(), "b,n".to_string())
+        );
+        // Mode vide ou "disabled" : la DLL est désactivée
+        assert_eq!(normalize_dll_override("xinput1_3", "disabled").unwrap().1, "");
+        assert_eq!(normalize_dll_override("xinput1_3", "").unwrap().1, "");
+    }
+
+    #[test]
+    fn dll_override_rejects_invalid_names_and_modes() {
+        assert!(normalize_dll_override("", "n").is_err());
+        assert!(normalize_dll_override(".dll", "n").is_err());
+        assert!(normalize_dll_override("d3d11=n;dxgi", "n").is_err());
+        assert!(normalize_dll_override("d3d11", "native,fast").is_err());
+    }
+
+    #[test]
+    fn dll_overrides_fail_on_the_first_invalid_entry() {
+        let overrides = HashMap::from([
+            ("D3D9.DLL".to_string(), "native".to_string()),
+            ("dinput8".to_string(), "n,b".to_string()),
+        ]);
+        assert_eq!(
+            normalize_dll_overrides(&overrides).unwrap(),
+            HashMap::from([
+                ("d3d9".to_string(), "n".to_string()),
+                ("dinput8".to_string(), "n,b".to_string()),
+            ])
+        );
+
+        let invalid = HashMap::from([("d3d9".to_string(), "always".to_string())]);
+        assert!(normalize_dll_overrides(&invalid).is_err());
+    }
+}
@@ -0,0 +1,583 @@
+use crate::db::Database;
+use crate::models::{
+    PlayStreaks, PlaytimeBucket, PlaytimeDimension, PlaytimeGranularity, PlaytimeHeatmap,
+    PlaytimeShare, SessionRecord, TopGame,
+};
+use crate::utils::AppError;
+use chrono::{DateTime, Datelike, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Arc;
+
+// Fuseau dans lequel les sessions sont découpées en jours et en heures
+#[derive(Debug, Clone, Copy)]
+pub enum AnalyticsZone {
+    Local,
+    Fixed(FixedOffset),
+}
+
+impl AnalyticsZone {
+    // Décalage envoyé par le frontend, en minutes à l'est d'UTC ; sinon le
+    // fuseau du système, qui tient compte des changements d'heure
+    pub fn from_offset_minutes(minutes: Option<i32>) -> Self {
+        minutes
+            .and_then(|minutes| FixedOffset::east_opt(minutes * 60))
+            .map(Self::Fixed)
+            .unwrap_or(Self::Local)
+    }
+
+    // Timestamp Unix d'une heure locale
+    pub fn timestamp(&self, local: NaiveDateTime) -> i64 {
+        match self {
+            Self::Local => local_timestamp(&Local, local),
+            Self::Fixed(offset) => local_timestamp(offset, local),
+        }
+    }
+
+    // Heure locale avec son décalage, au format RFC 3339
+    pub fn rfc3339(&self, timestamp: i64) -> String {
+        let utc = DateTime::<Utc>::from_timestamp(timestamp, 0).unwrap_or_default();
+        match self {
+            Self::Local => utc.with_timezone(&Local).to_rfc3339(),
+            Self::Fixed(offset) => utc.with_timezone(offset).to_rfc3339(),
+        }
+    }
+
+    fn local(&self, timestamp: i64) -> NaiveDateTime {
+        let utc = DateTime::<Utc>::from_timestamp(timestamp, 0).unwrap_or_default();
+        match self {
+            Self::Local => utc.with_timezone(&Local).naive_local(),
+            Self::Fixed(offset) => utc.with_timezone(offset).naive_local(),
+        }
+    }
+}
+
+// La plus tôt si l'heure est ambiguë ; une heure sautée lors du passage à
+// l'heure d'été est décalée d'une heure
+fn local_timestamp<Tz: TimeZone>(zone: &Tz, local: NaiveDateTime) -> i64 {
+    zone.from_local_datetime(&local)
+        .earliest()
+        .map(|time| time.timestamp())
+        .unwrap_or_else(|| local_timestamp(zone, local + Duration::hours(1)))
+}
+
+// Portion d'une session comprise dans une même heure locale
+struct Slice {
+    at: NaiveDateTime,
+    seconds: f64,
+}
+
+pub struct AnalyticsService {
+    database: Arc<Database>,
+}
+
+impl AnalyticsService {
+    pub fn new(database: Arc<Database>) -> Self {
+        Self { database }
+    }
+
+    // Temps de jeu par jour, semaine ISO ou mois, périodes vides comprises
+    pub async fn playtime_totals(
+        &self,
+        granularity: PlaytimeGranularity,
+        from: Option<i64>,
+        to: Option<i64>,
+        zone: AnalyticsZone,
+    ) -> Result<Vec<PlaytimeBucket>, AppError> {
+        let sessions = self.database.analytics().sessions_between(from, to).await?;
+
+        let mut totals: BTreeMap<NaiveDate, (f64, i64)> = BTreeMap::new();
+        for session in &sessions {
+            let slices = slices(session, from, to, zone);
+            if let Some(first) = slices.first() {
+                totals
+                    .entry(period_start(first.at.date(), granularity))
+                    .or_default()
+                    .1 += 1;
+            }
+            for slice in slices {
+                totals
+                    .entry(period_start(slice.at.date(), granularity))
+                    .or_default()
+                    .0 += slice.seconds;
+            }
+        }
+
+        let first = from
+            .map(|from| period_start(zone.local(from).date(), granularity))
+            .or_else(|| totals.keys().next().copied());
+        let last = to
+            .map(|to| period_start(zone.local(to - 1).date(), granularity))
+            .or_else(|| totals.keys().next_back().copied());
+        let (Some(mut current), Some(last)) = (first, last) else {
+            return Ok(Vec::new());
+        };
+
+        let mut buckets = Vec::new();
+        while current <= last {
+            let (seconds, count) = totals.get(&current).copied().unwrap_or_default();
+            buckets.push(PlaytimeBucket {
+                period: period_label(current, granularity),
+                start_date: current.format("%Y-%m-%d").to_string(),
+                seconds: seconds.round() as i64,
+                sessions: count,
+            });
+            current = next_period(current, granularity);
+        }
+
+        Ok(buckets)
+    }
+
+    // Séries de jours consécutifs avec au moins une session, sur tout l'historique.
+    // La série en cours n'est rompue qu'une fois la journée terminée sans jouer
+    pub async fn play_streaks(&self, zone: AnalyticsZone) -> Result<PlayStreaks, AppError> {
+        let sessions = self.database.analytics().sessions_between(None, None).await?;
+
+        let days: BTreeSet<NaiveDate> = sessions
+            .iter()
+            .flat_map(|session| slices(session, None, None, zone))
+            .map(|slice| slice.at.date())
+            .collect();
+
+        let mut longest: Option<(NaiveDate, NaiveDate)> = None;
+        let mut run: Option<(NaiveDate, NaiveDate)> = None;
+        for day in &days {
+            let (start, end) = match run {
+                Some((start, end)) if end.succ_opt() == Some(*day) => (start, *day),
+                _ => (*day, *day),
+            };
+            run = Some((start, end));
+            if longest.is_none_or(|(first, last)| run_length(first, last) < run_length(start, end)) {
+                longest = run;
+            }
+        }
+
+        let today = zone.local(Utc::now().timestamp()).date();
+        let mut current_days = 0;
+        let mut day = if days.contains(&today) {
+            Some(today)
+        } else {
+            today.pred_opt()
+        };
+        while let Some(current) = day.filter(|day| days.contains(day)) {
+            current_days += 1;
+            day = current.pred_opt();
+        }
+
+        Ok(PlayStreaks {
+            current_days,
+            longest_days: longest.map_or(0, |(start, end)| run_length(start, end)),
+            longest_start: longest.map(|(start, _)| start.format("%Y-%m-%d").to_string()),
+            longest_end: longest.map(|(_, end)| end.format("%Y-%m-%d").to_string()),
+            last_played_day: days.last().map(|day| day.format("%Y-%m-%d").to_string()),
+        })
+    }
+
+    // Temps de jeu par genre, plateforme ou développeur ; un jeu à plusieurs
+    // genres compte entièrement dans chacun d'eux
+    pub async fn playtime_breakdown(
+        &self,
+        dimension: PlaytimeDimension,
+        from: Option<i64>,
+        to: Option<i64>,
+    ) -> Result<Vec<PlaytimeShare>, AppError> {
+        let per_game = self.seconds_per_game(from, to).await?;
+        let facets = self.database.analytics().game_facets().await?;
+
+        let mut shares: HashMap<String, (f64, i64)> = HashMap::new();
+        for (game_id, (seconds, _, _)) in &per_game {
+            let keys = match facets.get(game_id) {
+                Some(game) => match dimension {
+                    PlaytimeDimension::Genre => game.genres.clone(),
+                    PlaytimeDimension::Platform => vec![game.platform.clone()],
+                    PlaytimeDimension::Developer => game.developer.clone().into_iter().collect(),
+                },
+                None => Vec::new(),
+            };
+            let keys = if keys.is_empty() {
+                vec!["Unknown".to_string()]
+            } else {
+                keys
+            };
+
+            for key in keys {
+                let share = shares.entry(key).or_default();
+                share.0 += seconds;
+                share.1 += 1;
+            }
+        }
+
+        let mut shares: Vec<PlaytimeShare> = shares
+            .into_iter()
+            .map(|(key, (seconds, games))| PlaytimeShare {
+                key,
+                seconds: seconds.round() as i64,
+                games,
+            })
+            .collect();
+        shares.sort_by(|a, b| b.seconds.cmp(&a.seconds).then_with(|| a.key.cmp(&b.key)));
+
+        Ok(shares)
+    }
+
+    // Répartition du temps de jeu par jour de la semaine et heure locale
+    pub async fn playtime_heatmap(
+        &self,
+        from: Option<i64>,
+        to: Option<i64>,
+        zone: AnalyticsZone,
+    ) -> Result<PlaytimeHeatmap, AppError> {
+        let sessions = self.database.analytics().sessions_between(from, to).await?;
+
+        let mut cells = vec![vec![0f64; 24]; 7];
+        for slice in sessions
+            .iter()
+            .flat_map(|session| slices(session, from, to, zone))
+        {
+            let weekday = slice.at.weekday().num_days_from_monday() as usize;
+            cells[weekday][slice.at.hour() as usize] += slice.seconds;
+        }
+
+        let seconds: Vec<Vec<i64>> = cells
+            .into_iter()
+            .map(|row| row.into_iter().map(|value| value.round() as i64).collect())
+            .collect();
+        let max_seconds = seconds.iter().flatten().copied().max().unwrap_or_default();
+
+        Ok(PlaytimeHeatmap {
+            seconds,
+            max_seconds,
+        })
+    }
+
+    pub async fn top_games(
+        &self,
+        from: Option<i64>,
+        to: Option<i64>,
+        limit: usize,
+    ) -> Result<Vec<TopGame>, AppError> {
+        let per_game = self.seconds_per_game(from, to).await?;
+        let facets = self.database.analytics().game_facets().await?;
+
+        let mut games: Vec<TopGame> = per_game
+            .into_iter()
+            .map(|(game_id, (seconds, sessions, last_played))| TopGame {
+                title: facets
+                    .get(&game_id)
+                    .map(|game| game.title.clone())
+                    .unwrap_or_else(|| game_id.clone()),
+                game_id,
+                seconds: seconds.round() as i64,
+                sessions,
+                last_played,
+            })
+            .collect();
+        games.sort_by(|a, b| b.seconds.cmp(&a.seconds).then_with(|| a.title.cmp(&b.title)));
+        games.truncate(limit);
+
+        Ok(games)
+    }
+
+    // Temps retenu, nombre de sessions et dernière fin de session par jeu
+    async fn seconds_per_game(
+        &self,
+        from: Option<i64>,
+        to: Option<i64>,
+    ) -> Result<HashMap<String, (f64, i64, Option<i64>)>, AppError> {
+        let sessions = self.database.analytics().sessions_between(from, to).await?;
+
+        let mut per_game: HashMap<String, (f64, i64, Option<i64>)> = HashMap::new();
+        for session in &sessions {
+            let entry = per_game.entry(session.game_id.clone()).or_default();
+            entry.0 += clipped_seconds(session, from, to);
+            entry.1 += 1;
+            entry.2 = entry.2.max(session.end_time);
+        }
+
+        Ok(per_game)
+    }
+}
+
+// Temps retenu de la session, réparti uniformément entre son début et sa fin
+fn clipped_seconds(session: &SessionRecord, from: Option<i64>, to: Option<i64>) -> f64 {
+    let duration = session.duration.unwrap_or_default() as f64;
+    let start = session.start_time;
+    let end = session.end_time.unwrap_or(start);
+    if end <= start {
+        return duration;
+    }
+
+    let clipped = end.min(to.unwrap_or(i64::MAX)) - start.max(from.unwrap_or(i64::MIN));
+    duration * clipped.max(0) as f64 / (end - start) as f64
+}
+
+// Découpe la session aux limites d'heure locale, donc aussi à minuit
+fn slices(session: &SessionRecord, from: Option<i64>, to: Option<i64>, zone: AnalyticsZone) -> Vec<Slice> {
+    let duration = session.duration.unwrap_or_default() as f64;
+    let start = session.start_time;
+    let end = session.end_time.unwrap_or(start);
+    if end <= start {
+        return vec![Slice {
+            at: zone.local(start),
+            seconds: duration,
+        }];
+    }
+
+    let scale = duration / (end - start) as f64;
+    let stop = end.min(to.unwrap_or(i64::MAX));
+    let mut current = start.max(from.unwrap_or(i64::MIN));
+    let mut slices = Vec::new();
+    while current < stop {
+        let at = zone.local(current);
+        let next = (current + 3600 - (at.minute() * 60 + at.second()) as i64).min(stop);
+        slices.push(Slice {
+            at,
+            seconds: (next - current) as f64 * scale,
+        });
+        current = next;
+    }
+    slices
+}
+
+fn run_length(start: NaiveDate, end: NaiveDate) -> i64 {
+    (end - start).num_days() + 1
+}
+
+fn period_start(date: NaiveDate, granularity: PlaytimeGranularity) -> NaiveDate {
+    match granularity {
+        PlaytimeGranularity::Day => date,
+        PlaytimeGranularity::Week => date - Duration::days(date.weekday().num_days_from_monday() as i64),
+        PlaytimeGranularity::Month => date.with_day(1).unwrap_or(date),
+    }
+}
+
+fn next_period(date: NaiveDate, granularity: PlaytimeGranularity) -> NaiveDate {
+    match granularity {
+        PlaytimeGranularity::Day => date + Duration::days(1),
+        PlaytimeGranularity::Week => date + Duration::days(7),
+        PlaytimeGranularity::Month => {
+            let (year, month) = if date.month() == 12 {
+                (date.year() + 1, 1)
+            } else {
+                (date.year(), date.month() + 1)
+            };
+            NaiveDate::from_ymd_opt(year, month, 1).unwrap_or(date + Duration::days(31))
+        }
+    }
+}
+
+fn period_label(date: NaiveDate, granularity: PlaytimeGranularity) -> String {
+    match granularity {
+        PlaytimeGranularity::Day => date.format("%Y-%m-%d").to_string(),
+        PlaytimeGranularity::Week => {
+            let week = date.iso_week();
+            format!("{}-W{:02}", week.year(), week.week())
+        }
+        PlaytimeGranularity::Month => date.format("%Y-%m").to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::fixtures;
+    use chrono::LocalResult;
+
+    fn at(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .and_then(|date| date.and_hms_opt(hour, minute, 0))
+            .unwrap()
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn session(start_time: i64, end_time: i64, duration: i64) -> SessionRecord {
+        SessionRecord {
+            id: 1,
+            game_id: "g1".to_string(),
+            start_time,
+            end_time: Some(end_time),
+            duration: Some(duration),
+            active_seconds: None,
+            idle_seconds: None,
+            end_reason: None,
+            exit_code: None,
+            exit_signal: None,
+            killed: false,
+            crashed: false,
+        }
+    }
+
+    fn seconds_per_day(slices: &[Slice]) -> Vec<(NaiveDate, f64)> {
+        let mut days: BTreeMap<NaiveDate, f64> = BTreeMap::new();
+        for slice in slices {
+            *days.entry(slice.at.date()).or_default() += slice.seconds;
+        }
+        days.into_iter().collect()
+    }
+
+    // Europe/Paris en 2024 : 02:00 devient 03:00 le 31 mars, 03:00 redevient
+    // 02:00 le 27 octobre
+    #[derive(Clone)]
+    struct Paris2024;
+
+    impl Paris2024 {
+        fn winter() -> FixedOffset {
+            FixedOffset::east_opt(3600).unwrap()
+        }
+
+        fn summer() -> FixedOffset {
+            FixedOffset::east_opt(2 * 3600).unwrap()
+        }
+    }
+
+    impl TimeZone for Paris2024 {
+        type Offset = FixedOffset;
+
+        fn from_offset(_offset: &FixedOffset) -> Self {
+            Paris2024
+        }
+
+        fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<FixedOffset> {
+            self.offset_from_local_datetime(&local.and_hms_opt(0, 0, 0).unwrap())
+        }
+
+        // Décalages dont la conversion en UTC retombe dans leur propre période
+        fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<FixedOffset> {
+            let valid: Vec<FixedOffset> = [Self::summer(), Self::winter()]
+                .into_iter()
+                .filter(|offset| {
+                    let utc = *local - Duration::seconds(offset.local_minus_utc() as i64);
+                    self.offset_from_utc_datetime(&utc) == *offset
+                })
+                .collect();
+            match valid[..] {
+                [offset] => LocalResult::Single(offset),
+                [earliest, latest] => LocalResult::Ambiguous(earliest, latest),
+                _ => LocalResult::None,
+            }
+        }
+
+        fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+            self.offset_from_utc_datetime(&utc.and_hms_opt(0, 0, 0).unwrap())
+        }
+
+        fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+            if *utc >= at(2024, 3, 31, 1, 0) && *utc < at(2024, 10, 27, 1, 0) {
+                Self::summer()
+            } else {
+                Self::winter()
+            }
+        }
+    }
+
+    #[test]
+    fn session_across_midnight_is_split_between_both_days() {
+        let zone = AnalyticsZone::from_offset_minutes(Some(0));
+        let start = zone.timestamp(at(2024, 5, 1, 23, 30));
+
+        let whole = slices(&session(start, start + 3600, 3600), None, None, zone);
+        assert_eq!(
+            seconds_per_day(&whole),
+            vec![(date(2024, 5, 1), 1800.0), (date(2024, 5, 2), 1800.0)]
+        );
+
+        // Le temps retenu est réparti au prorata de la durée réelle
+        let active = slices(&session(start, start + 3600, 1800), None, None, zone);
+        assert_eq!(
+            seconds_per_day(&active),
+            vec![(date(2024, 5, 1), 900.0), (date(2024, 5, 2), 900.0)]
+        );
+    }
+
+    #[test]
+    fn half_hour_offset_cuts_at_local_hours() {
+        let zone = AnalyticsZone::from_offset_minutes(Some(330));
+        // 18:00 UTC = 23:30 à +05:30
+        let start = at(2024, 5, 1, 18, 0).and_utc().timestamp();
+        assert_eq!(zone.timestamp(at(2024, 5, 1, 23, 30)), start);
+
+        let cuts: Vec<(NaiveDateTime, f64)> = slices(&session(start, start + 5400, 5400), None, None, zone)
+            .iter().map(|slice| (slice.at, slice.seconds)).collect();
+        assert_eq!(
+            cuts,
+            vec![(at(2024, 5, 1, 23, 30), 1800.0), (at(2024, 5, 2, 0, 0), 3600.0)]
+        );
+        assert!(zone.rfc3339(start).ends_with("+05:30"));
+    }
+
+    #[test]
+    fn skipped_and_repeated_hours_resolve_to_a_real_instant() {
+        // 02:30 n'existe pas le 31 mars : 03:30 CEST, soit 01:30 UTC
+        assert_eq!(
+            local_timestamp(&Paris2024, at(2024, 3, 31, 2, 30)),
+            at(2024, 3, 31, 1, 30).and_utc().timestamp()
+        );
+        // 02:30 existe deux fois le 27 octobre : la première, en CEST
+        assert_eq!(
+            local_timestamp(&Paris2024, at(2024, 10, 27, 2, 30)),
+            at(2024, 10, 27, 0, 30).and_utc().timestamp()
+        );
+        assert_eq!(
+            local_timestamp(&Paris2024, at(2024, 1, 15, 12, 0)),
+            at(2024, 1, 15, 11, 0).and_utc().timestamp()
+        );
+    }
+
+    #[test]
+    fn weeks_follow_iso_numbering_across_years() {
+        let week = PlaytimeGranularity::Week;
+        // Le mardi 31 décembre 2024 appartient à la semaine 1 de 2025
+        let start = period_start(date(2024, 12, 31), week);
+        assert_eq!(start, date(2024, 12, 30));
+        assert_eq!(period_label(start, week), "2025-W01");
+        assert_eq!(period_label(next_period(start, week), week), "2025-W02");
+
+        // Le dimanche 3 janvier 2021 termine la semaine 53 de 2020
+        let start = period_start(date(2021, 1, 3), week);
+        assert_eq!(start, date(2020, 12, 28));
+        assert_eq!(period_label(start, week), "2020-W53");
+    }
+
+    #[test]
+    fn months_roll_over_from_december_to_january() {
+        let month = PlaytimeGranularity::Month;
+        let start = period_start(date(2024, 12, 15), month);
+        assert_eq!(start, date(2024, 12, 1));
+        assert_eq!(next_period(start, month), date(2025, 1, 1));
+        assert_eq!(period_label(next_period(start, month), month), "2025-01");
+        assert_eq!(
+            next_period(date(2024, 12, 31), PlaytimeGranularity::Day),
+            date(2025, 1, 1)
+        );
+    }
+
+    #[tokio::test]
+    async fn current_streak_survives_until_today_ends() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        database.games().upsert_game(&fixtures::game("g1", "Foo")).await.unwrap();
+        let service = AnalyticsService::new(database.clone());
+        let zone = AnalyticsZone::from_offset_minutes(Some(0));
+
+        // Joué les deux jours précédents, pas encore aujourd'hui ; plus tôt,
+        // une série de trois jours
+        let today = Utc::now().date_naive();
+        for days_ago in [1, 2, 10, 11, 12] {
+            let noon = zone.timestamp((today - Duration::days(days_ago)).and_hms_opt(12, 0, 0).unwrap());
+            database
+                .sessions()
+                .add_manual_session("g1", noon, noon + 3600)
+                .await
+                .unwrap();
+        }
+
+        let streaks = service.play_streaks(zone).await.unwrap();
+        assert_eq!(streaks.current_days, 2);
+        assert_eq!(streaks.longest_days, 3);
+        assert_eq!(
+            streaks.last_played_day,
+            Some((today - Duration::days(1)).format("%Y-%m-%d").to_string())
+        );
+    }
+}
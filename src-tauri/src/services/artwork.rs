@@ -0,0 +1,131 @@
+use crate::log_warn;
+use crate::db::Database;
+use crate::models::{Game, GameMedia, GameResult};
+use crate::platforms::epic::EpicCatalogItem;
+use crate::platforms::steam::SteamArtwork;
+use crate::utils::cache::MediaCache;
+use crate::utils::AppError;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::AppHandle;
+
+// Les images du catalogue Epic changent rarement
+const EPIC_IMAGE_MAX_AGE: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+// Images fournies par les clients installés, copiées dans le cache média : elles
+// s'affichent hors ligne et passent avant celles d'IGDB
+pub struct LocalArtworkService {
+    database: Arc<Database>,
+    media_cache: MediaCache,
+}
+
+impl LocalArtworkService {
+    pub fn new(database: Arc<Database>, app: &AppHandle) -> Result<Self, AppError> {
+        Ok(Self {
+            database,
+            media_cache: MediaCache::new(app)?,
+        })
+    }
+
+    // Renvoie les seules images locales trouvées, pour les réappliquer après IGDB
+    pub async fn apply_steam(
+        &self,
+        game: &mut Game,
+        artwork: SteamArtwork,
+    ) -> GameResult<Option<GameMedia>> {
+        if artwork.is_empty() {
+            return Ok(None);
+        }
+
+        let local = GameMedia {
+            thumbnail: self.import(artwork.thumbnail).await,
+            cover: self.import(artwork.cover).await,
+            screenshots: Vec::new(),
+            background: self.import(artwork.background).await,
+            icon: None,
+            logo: self.import(artwork.logo).await,
+        };
+
+        game.media = overlay_media(&local, game.media.clone());
+        self.database
+            .metadata()
+            .update_media(&game.id, &game.media)
+            .await?;
+
+        Ok(Some(local))
+    }
+
+    // Images et textes du catalogue local Epic ; seuls les champs vides du jeu
+    // sont complétés pour ne pas écraser les modifications de l'utilisateur
+    pub async fn apply_epic(
+        &self,
+        game: &mut Game,
+        item: &EpicCatalogItem,
+    ) -> GameResult<Option<GameMedia>> {
+        let local = GameMedia {
+            thumbnail: self
+                .download(item.key_image(&["Thumbnail", "DieselGameBox"]))
+                .await,
+            cover: self.download(item.key_image(&["DieselGameBoxTall"])).await,
+            screenshots: Vec::new(),
+            background: self.download(item.key_image(&["DieselGameBox"])).await,
+            icon: None,
+            logo: self.download(item.key_image(&["DieselGameBoxLogo"])).await,
+        };
+
+        let metadata = &mut game.metadata;
+        if metadata.description.is_none() {
+            metadata.description = item.description.clone();
+        }
+        if metadata.developer.is_none() {
+            metadata.developer = item.developer.clone();
+        }
+
+        let found = local.cover.is_some()
+            || local.background.is_some()
+            || local.logo.is_some()
+            || local.thumbnail.is_some();
+        game.media = overlay_media(&local, game.media.clone());
+        self.database
+            .metadata()
+            .update_metadata(&game.id, &game.metadata, Some(&game.media))
+            .await?;
+
+        Ok(found.then_some(local))
+    }
+
+    async fn download(&self, url: Option<&str>) -> Option<String> {
+        let url = url?;
+        match self.media_cache.get_or_download(url, EPIC_IMAGE_MAX_AGE).await {
+            Ok(media) => Some(media),
+            Err(e) => {
+                log_warn!("{}", e);
+                None
+            }
+        }
+    }
+
+    async fn import(&self, path: Option<PathBuf>) -> Option<String> {
+        let path = path?;
+        match self.media_cache.import_file(&path).await {
+            Ok(media) => Some(media),
+            Err(e) => {
+                log_warn!("{}", e);
+                None
+            }
+        }
+    }
+}
+
+// Les images locales remplacent celles de base, champ par champ
+pub fn overlay_media(local: &GameMedia, base: GameMedia) -> GameMedia {
+    GameMedia {
+        thumbnail: local.thumbnail.clone().or(base.thumbnail),
+        cover: local.cover.clone().or(base.cover),
+        screenshots: base.screenshots,
+        background: local.background.clone().or(base.background),
+        icon: local.icon.clone().or(base.icon),
+        logo: local.logo.clone().or(base.logo),
+    }
+}
@@ -0,0 +1,263 @@
+use super::analytics::AnalyticsZone;
+use crate::db::Database;
+use crate::models::{SessionEndReason, SessionExportFormat, SessionRecord};
+use crate::utils::AppError;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::path::Path;
+use std::sync::Arc;
+
+// Ligne exportée : heures en UTC et dans le fuseau demandé
+#[derive(Debug, Serialize)]
+struct ExportedSession {
+    session_id: i64,
+    game_id: String,
+    title: String,
+    start_utc: String,
+    end_utc: String,
+    start_local: String,
+    end_local: String,
+    duration: i64,
+    active_seconds: Option<i64>,
+    idle_seconds: Option<i64>,
+    end_reason: Option<SessionEndReason>,
+    exit_code: Option<i32>,
+    crashed: bool,
+}
+
+pub struct ExportService {
+    database: Arc<Database>,
+}
+
+impl ExportService {
+    pub fn new(database: Arc<Database>) -> Self {
+        Self { database }
+    }
+
+    // Écrit les sessions terminées qui chevauchent [from, to[ ; renvoie leur nombre
+    pub async fn export_sessions(
+        &self,
+        path: &Path,
+        format: SessionExportFormat,
+        from: Option<i64>,
+        to: Option<i64>,
+        game_ids: Option<&[String]>,
+        zone: AnalyticsZone,
+    ) -> Result<usize, AppError> {
+        let facets = self.database.analytics().game_facets().await?;
+        let sessions: Vec<ExportedSession> = self
+            .database
+            .analytics()
+            .sessions_between(from, to)
+            .await?
+            .into_iter()
+            .filter(|session| game_ids.is_none_or(|ids| ids.contains(&session.game_id)))
+            .map(|session| {
+                let title = facets
+                    .get(&session.game_id)
+                    .map(|game| game.title.clone())
+                    .unwrap_or_else(|| session.game_id.clone());
+                exported(session, title, zone)
+            })
+            .collect();
+
+        let content = match format {
+            SessionExportFormat::Csv => to_csv(&sessions),
+            SessionExportFormat::JsonLines => to_json_lines(&sessions)?,
+            SessionExportFormat::ICalendar => to_ical(&sessions),
+        };
+
+        tokio::fs::write(path, content).await.map_err(|e| AppError {
+            message: format!("Failed to write export file: {}", e),
+        })?;
+
+        Ok(sessions.len())
+    }
+}
+
+fn exported(session: SessionRecord, title: String, zone: AnalyticsZone) -> ExportedSession {
+    let end_time = session.end_time.unwrap_or(session.start_time);
+
+    ExportedSession {
+        session_id: session.id,
+        game_id: session.game_id,
+        title,
+        start_utc: utc_rfc3339(session.start_time),
+        end_utc: utc_rfc3339(end_time),
+        start_local: zone.rfc3339(session.start_time),
+        end_local: zone.rfc3339(end_time),
+        duration: session.duration.unwrap_or_default(),
+        active_seconds: session.active_seconds,
+        idle_seconds: session.idle_seconds,
+        end_reason: session.end_reason,
+        exit_code: session.exit_code,
+        crashed: session.crashed,
+    }
+}
+
+fn utc_rfc3339(timestamp: i64) -> String {
+    DateTime::<Utc>::from_timestamp(timestamp, 0)
+        .unwrap_or_default()
+        .format("%Y-%m-%dT%H:%M:%SZ")
+        .to_string()
+}
+
+fn to_csv(sessions: &[ExportedSession]) -> String {
+    let mut csv = String::from(
+        "session_id,game_id,title,start_utc,end_utc,start_local,end_local,duration,active_seconds,idle_seconds,end_reason,exit_code,crashed\r\n",
+    );
+
+    let optional = |value: Option<i64>| value.map(|value| value.to_string()).unwrap_or_default();
+    for session in sessions {
+        let fields = [
+            session.session_id.to_string(),
+            csv_field(&session.game_id),
+            csv_field(&session.title),
+            csv_field(&session.start_utc),
+            csv_field(&session.end_utc),
+            csv_field(&session.start_local),
+            csv_field(&session.end_local),
+            session.duration.to_string(),
+            optional(session.active_seconds),
+            optional(session.idle_seconds),
+            csv_field(
+                &session
+                    .end_reason
+                    .map(|reason| format!("{:?}", reason))
+                    .unwrap_or_default(),
+            ),
+            optional(session.exit_code.map(i64::from)),
+            session.crashed.to_string(),
+        ];
+        csv.push_str(&fields.join(","));
+        csv.push_str("\r\n");
+    }
+
+    csv
+}
+
+// Champ texte toujours entre guillemets, pour que les tableurs ne
+// l'interprètent pas comme un nombre ou une date (RFC 4180)
+fn csv_field(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "\"\""))
+}
+
+fn to_json_lines(sessions: &[ExportedSession]) -> Result<String, AppError> {
+    let mut lines = String::new();
+    for session in sessions {
+        let line = serde_json::to_string(session).map_err(|e| AppError {
+            message: format!("Failed to serialize session: {}", e),
+        })?;
+        lines.push_str(&line);
+        lines.push('\n');
+    }
+    Ok(lines)
+}
+
+// Un VEVENT par session, en UTC : le calendrier les affiche dans son propre fuseau
+fn to_ical(sessions: &[ExportedSession]) -> String {
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+    let ical_time = |rfc3339: &str| rfc3339.replace(['-', ':'], "");
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//glaunch//Game sessions//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+    for session in sessions {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:session-{}@glaunch", session.session_id));
+        lines.push(format!("DTSTAMP:{}", stamp));
+        lines.push(format!("DTSTART:{}", ical_time(&session.start_utc)));
+        lines.push(format!("DTEND:{}", ical_time(&session.end_utc)));
+        lines.push(format!("SUMMARY:{}", ical_text(&session.title)));
+        lines.push(format!(
+            "DESCRIPTION:{}",
+            ical_text(&format!(
+                "Played {} min ({} min active)",
+                session.duration / 60,
+                session.active_seconds.unwrap_or(session.duration) / 60
+            ))
+        ));
+        lines.push("CATEGORIES:Gaming".to_string());
+        lines.push("TRANSP:TRANSPARENT".to_string());
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines
+        .iter()
+        .map(|line| fold_ical_line(line))
+        .collect::<Vec<_>>()
+        .join("")
+}
+
+fn ical_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace(['\r', '\n'], "\\n")
+}
+
+// Lignes de 75 octets au plus, sans couper un caractère UTF-8 (RFC 5545)
+fn fold_ical_line(line: &str) -> String {
+    let mut folded = String::new();
+    let mut width = 0;
+    for character in line.chars() {
+        if width + character.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(character);
+        width += character.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Retire les replis "\r\n " et le "\r\n" final
+    fn unfold(folded: &str) -> String {
+        folded.trim_end_matches("\r\n").replace("\r\n ", "")
+    }
+
+    #[test]
+    fn csv_field_quotes_quotes_and_newlines() {
+        assert_eq!(csv_field("Half-Life"), "\"Half-Life\"");
+        assert_eq!(csv_field("The \"Best\" Game"), "\"The \"\"Best\"\" Game\"");
+        assert_eq!(csv_field("line 1\nline 2, again"), "\"line 1\nline 2, again\"");
+    }
+
+    #[test]
+    fn ical_text_escapes_separators_and_newlines() {
+        assert_eq!(
+            ical_text("Baldur's Gate; Enhanced, Edition"),
+            r"Baldur's Gate\; Enhanced\, Edition"
+        );
+        assert_eq!(ical_text(r"C:\Games\Foo"), r"C:\\Games\\Foo");
+        assert_eq!(ical_text("a\r\nb\nc"), r"a\nb\nc");
+    }
+
+    #[test]
+    fn ical_lines_fold_at_75_octets_without_splitting_characters() {
+        assert_eq!(fold_ical_line("SUMMARY:Foo"), "SUMMARY:Foo\r\n");
+
+        // 74 octets puis "é" (2 octets) : le repli passe avant le caractère
+        let line = format!("{}é", "a".repeat(74));
+        let folded = fold_ical_line(&line);
+        assert_eq!(folded, format!("{}\r\n é\r\n", "a".repeat(74)));
+
+        let line = format!("SUMMARY:{}", "日本語のゲーム".repeat(10));
+        let folded = fold_ical_line(&line);
+        for physical in folded.trim_end_matches("\r\n").split("\r\n") {
+            assert!(physical.len() <= 75, "{} octets", physical.len());
+        }
+        assert_eq!(unfold(&folded), line);
+    }
+}
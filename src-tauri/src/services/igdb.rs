@@ -1,4 +1,4 @@
-use crate::models::{GameMedia, GameMetadata};
+use crate::models::GameMedia;
 use crate::utils::{cache::MediaCache, AppError};
 use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
 use reqwest::Client;
@@ -23,6 +23,14 @@ pub struct IgdbGame {
     pub first_release_date: Option<i64>,
     pub involved_companies: Option<Vec<IgdbCompany>>,
     pub genres: Option<Vec<IgdbGenre>>,
+    // Identifiants du jeu sur les boutiques (Steam, GOG...)
+    pub external_games: Option<Vec<IgdbExternalGame>>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct IgdbExternalGame {
+    pub category: Option<i64>,
+    pub uid: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -111,7 +119,7 @@ impl IgdbService {
 
         let response = self
             .client
-            .post(&format!("{}/games", IGDB_API_URL))
+            .post(format!("{}/games", IGDB_API_URL))
             .headers(self.get_headers())
             .body(body)
             .send()
@@ -150,12 +158,13 @@ impl IgdbService {
             involved_companies.company.logo.*,
             involved_companies.developer,
             involved_companies.publisher,
-            genres.*;"#
+            genres.*,
+            external_games.category, external_games.uid;"#
         );
 
         let response = self
             .client
-            .post(&format!("{}/games", IGDB_API_URL))
+            .post(format!("{}/games", IGDB_API_URL))
             .headers(self.get_headers())
             .body(body)
             .send()
@@ -202,13 +211,14 @@ impl IgdbService {
             involved_companies.company.logo.*,
             involved_companies.developer,
             involved_companies.publisher,
-            genres.*;
+            genres.*,
+            external_games.category, external_games.uid;
             limit 1;"#
         );
 
         let response = self
             .client
-            .post(&format!("{}/games", IGDB_API_URL))
+            .post(format!("{}/games", IGDB_API_URL))
             .headers(self.get_headers())
             .body(body)
             .send()
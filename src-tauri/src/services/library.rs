@@ -0,0 +1,267 @@
+use crate::log_warn;
+use crate::api::{EpicApi, SteamApi};
+use crate::db::Database;
+use crate::models::{
+    Game, GameInstallation, GameKind, GameMedia, GameMetadata, GameResult, GameStats, OwnedLibrarySync,
+    Platform,
+};
+use crate::platforms::gog::GogGalaxy;
+use crate::platforms::SteamPlatform;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+// Namespace des contenus Unreal Engine (Marketplace), qui ne sont pas des jeux
+const EPIC_UNREAL_NAMESPACE: &str = "ue";
+// Nombre d'éléments demandés par appel au catalogue Epic
+const EPIC_CATALOG_BATCH: usize = 50;
+
+// Jeu possédé tel que renvoyé par une boutique
+struct OwnedEntry {
+    platform_id: String,
+    title: String,
+}
+
+// Synchronise les jeux possédés mais non installés : ils partagent le modèle
+// Game avec installed = false et sont retirés quand ils ne sont plus possédés
+pub struct OwnedLibraryService {
+    database: Arc<Database>,
+}
+
+impl OwnedLibraryService {
+    pub fn new(database: Arc<Database>) -> Self {
+        Self { database }
+    }
+
+    pub async fn sync_steam(
+        &self,
+        steam: &SteamPlatform,
+        api: &SteamApi,
+        steam_id: Option<String>,
+    ) -> GameResult<OwnedLibrarySync> {
+        let owned = steam
+            .owned_games(api, steam_id)
+            .await?
+            .into_iter()
+            .map(|game| OwnedEntry {
+                platform_id: game.appid.to_string(),
+                title: game
+                    .name
+                    .unwrap_or_else(|| format!("Steam app {}", game.appid)),
+            })
+            .collect();
+
+        self.apply(Platform::Steam, owned).await
+    }
+
+    pub async fn sync_epic(&self, api: &EpicApi) -> GameResult<OwnedLibrarySync> {
+        let items: Vec<_> = api
+            .get_library_items()
+            .await?
+            .into_iter()
+            .filter(|item| item.namespace != EPIC_UNREAL_NAMESPACE && item.app_name.is_some())
+            .collect();
+
+        // Les titres viennent du catalogue ; à défaut on garde l'AppName
+        let mut by_namespace: HashMap<&str, Vec<&str>> = HashMap::new();
+        for item in &items {
+            by_namespace
+                .entry(item.namespace.as_str())
+                .or_default()
+                .push(item.catalog_item_id.as_str());
+        }
+        let mut titles: HashMap<String, String> = HashMap::new();
+        for (namespace, ids) in by_namespace {
+            for batch in ids.chunks(EPIC_CATALOG_BATCH) {
+                match api.get_catalog_items(namespace, batch).await {
+                    Ok(catalog) => {
+                        for (id, item) in catalog {
+                            if let Some(title) = item["title"].as_str() {
+                                titles.insert(id, title.to_string());
+                            }
+                        }
+                    }
+                    Err(e) => log_warn!("Epic catalog lookup failed for {}: {}", namespace, e),
+                }
+            }
+        }
+
+        let owned = items
+            .into_iter()
+            .filter_map(|item| {
+                let app_name = item.app_name?;
+                Some(OwnedEntry {
+                    title: titles
+                        .remove(&item.catalog_item_id)
+                        .unwrap_or_else(|| app_name.clone()),
+                    platform_id: app_name,
+                })
+            })
+            .collect();
+
+        self.apply(Platform::Epic, owned).await
+    }
+
+    pub async fn sync_gog(&self, galaxy: &GogGalaxy) -> GameResult<OwnedLibrarySync> {
+        let owned = galaxy
+            .owned_games()
+            .await?
+            .into_iter()
+            .map(|game| OwnedEntry {
+                platform_id: game.product_id,
+                title: game.title,
+            })
+            .collect();
+
+        self.apply(Platform::Gog, owned).await
+    }
+
+    // Ajoute les jeux possédés inconnus et retire les entrées non installées qui
+    // ne sont plus possédées ; les jeux installés ne sont jamais modifiés
+    async fn apply(&self, platform: Platform, owned: Vec<OwnedEntry>) -> GameResult<OwnedLibrarySync> {
+        let existing: HashMap<String, Game> = self
+            .database
+            .games()
+            .get_all_games()
+            .await?
+            .into_iter()
+            .filter(|game| game.platform == platform)
+            .map(|game| (game.id.clone(), game))
+            .collect();
+
+        let mut summary = OwnedLibrarySync {
+            platform: platform.clone(),
+            owned: owned.len(),
+            added: 0,
+            removed: 0,
+            error: None,
+        };
+
+        let mut owned_ids = HashSet::new();
+        for entry in owned {
+            let game = owned_game(platform.clone(), entry);
+            owned_ids.insert(game.id.clone());
+            if !existing.contains_key(&game.id) {
+                self.database.games().upsert_game(&game).await?;
+                summary.added += 1;
+            }
+        }
+
+        for game in existing.values() {
+            if !game.installed && !owned_ids.contains(&game.id) {
+                self.database.games().delete_game(&game.id).await?;
+                summary.removed += 1;
+            }
+        }
+
+        Ok(summary)
+    }
+}
+
+fn owned_game(platform: Platform, entry: OwnedEntry) -> Game {
+    let prefix = match platform {
+        Platform::Steam => "steam",
+        Platform::BattleNet => "battlenet",
+        Platform::Epic => "epic",
+        Platform::Gog => "gog",
+        Platform::Custom => "custom",
+    };
+
+    Game {
+        id: format!("{}_{}", prefix, entry.platform_id),
+        platform_id: entry.platform_id,
+        platform,
+        title: entry.title.clone(),
+        installation: GameInstallation {
+            install_path: PathBuf::new(),
+            executable: None,
+            size: 0,
+            version: None,
+            last_updated: None,
+        },
+        metadata: GameMetadata {
+            title: entry.title,
+            description: None,
+            developer: None,
+            publisher: None,
+            release_date: None,
+            genres: Vec::new(),
+            tags: Vec::new(),
+            media: None,
+        },
+        media: GameMedia {
+            thumbnail: None,
+            cover: None,
+            screenshots: Vec::new(),
+            background: None,
+            icon: None,
+            logo: None,
+        },
+        last_played: None,
+        installed: false,
+        stats: GameStats::default(),
+        kind: GameKind::Base,
+        parent_game_id: None,
+        addons: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::stub_server::{StubResponse, StubServer};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn epic_sync_adds_owned_games_and_removes_lost_ones() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        let entry = |platform_id: &str| OwnedEntry {
+            platform_id: platform_id.to_string(),
+            title: platform_id.to_string(),
+        };
+        let mut installed = owned_game(Platform::Epic, entry("Installed"));
+        installed.installed = true;
+        let lost = owned_game(Platform::Epic, entry("Lost"));
+        let kept = owned_game(Platform::Epic, entry("Kept"));
+        for game in [kept, lost, installed] {
+            database.games().upsert_game(&game).await.unwrap();
+        }
+
+        let server = StubServer::start(|request| {
+            if request.path.starts_with("/library/api/public/items") {
+                StubResponse::json(json!({
+                    "records": [
+                        { "namespace": "ns", "catalogItemId": "c1", "appName": "Kept" },
+                        { "namespace": "ns", "catalogItemId": "c2", "appName": "New" },
+                        { "namespace": "ue", "catalogItemId": "c3", "appName": "Asset" }
+                    ],
+                    "responseMetadata": {}
+                }))
+            } else if request.path.starts_with("/catalog/api/shared/namespace/ns/bulk/items") {
+                StubResponse::json(json!({ "c2": { "title": "New Game" } }))
+            } else {
+                StubResponse::status(404)
+            }
+        })
+        .await;
+        let api = EpicApi::new("token".to_string()).with_base_urls(&server.url, &server.url);
+
+        let summary = OwnedLibraryService::new(database.clone())
+            .sync_epic(&api)
+            .await
+            .unwrap();
+
+        assert_eq!((summary.owned, summary.added, summary.removed), (2, 1, 1));
+        let games = database.games();
+        let added = games.get_game("epic_New").await.unwrap().expect("owned game added");
+        assert_eq!(added.title, "New Game");
+        assert!(!added.installed);
+        assert!(games.get_game("epic_Kept").await.unwrap().is_some());
+        assert!(games.get_game("epic_Lost").await.unwrap().is_none());
+        // Un jeu installé n'est jamais retiré par la synchronisation
+        assert!(games.get_game("epic_Installed").await.unwrap().is_some());
+        assert!(server.requests().iter().all(|request| {
+            request.headers.get("authorization").map(String::as_str) == Some("Bearer token")
+        }));
+    }
+}
@@ -1,17 +1,20 @@
 // Dans services/metadata.rs
 use super::igdb::IgdbService;
 use crate::db::Database;
-use crate::models::{Game, GameMedia, GameMetadata};
+use crate::models::{Game, GameMedia, GameMetadata, Platform};
 use crate::services::igdb::IgdbGame;
-use crate::services::IgdbSearchResult;
+use crate::services::{overlay_media, IgdbSearchResult};
 use crate::utils::AppError;
 use std::sync::Arc;
 use tauri::AppHandle;
-use crate::log_info;
 use crate::log_warn;
 use crate::log_error;
 use crate::log_debug;
 
+// Catégories external_games d'IGDB dont l'uid est l'identifiant de nos jeux
+const IGDB_EXTERNAL_STEAM: i64 = 1;
+const IGDB_EXTERNAL_GOG: i64 = 5;
+
 pub struct MetadataService {
     database: Arc<Database>,
     igdb: Arc<IgdbService>,
@@ -47,15 +50,17 @@ impl MetadataService {
 
         // Récupérer les données IGDB
         let igdb_game = self.igdb.get_game_by_id(igdb_id).await?;
+        let external = external_ids(&igdb_game);
 
         // Mettre à jour les métadonnées et médias
-        let (metadata, media) = self.convert_to_metadata(&game.id, igdb_game).await?;
+        let (metadata, media) = self.convert_to_metadata(igdb_game).await?;
 
         // Sauvegarder en base
         self.database
             .metadata()
             .update_metadata(&game.id, &metadata, Some(&media))
             .await?;
+        self.remember_igdb_game(&game.id, igdb_id, &external).await?;
 
         Ok(())
     }
@@ -66,7 +71,6 @@ impl MetadataService {
 
     pub async fn convert_to_metadata(
         &self,
-        game_id: &str,
         igdb_game: IgdbGame,
     ) -> Result<(GameMetadata, GameMedia), AppError> {
         let metadata = GameMetadata {
@@ -105,6 +109,20 @@ impl MetadataService {
         Ok((metadata, media))
     }
 
+    // Sert au regroupement des installations d'un même jeu en œuvres
+    async fn remember_igdb_game(
+        &self,
+        game_id: &str,
+        igdb_id: i64,
+        external: &[(Platform, String)],
+    ) -> Result<(), AppError> {
+        self.database.works().set_igdb_id(game_id, igdb_id).await?;
+        self.database
+            .works()
+            .add_external_games(igdb_id, external)
+            .await
+    }
+
     async fn get_twitch_access_token(client_id: &str, client_secret: &str) -> Result<String, AppError> {
         log_debug!("Attempting to get Twitch access token...");
 
@@ -150,7 +168,12 @@ impl MetadataService {
         }
     }
 
-    pub async fn update_metadata(&self, game: &mut Game) -> Result<(), AppError> {
+    // local : images trouvées sur le disque, conservées à la place de celles d'IGDB
+    pub async fn update_metadata(
+        &self,
+        game: &mut Game,
+        local: Option<&GameMedia>,
+    ) -> Result<(), AppError> {
         match self.igdb.search_game(&game.title).await {
             Ok(igdb_game) => {
                 // Créer les métadonnées à partir des données IGDB
@@ -184,19 +207,40 @@ impl MetadataService {
                 };
 
                 // Récupérer les médias
-                let media = self.igdb.get_media(&igdb_game).await?;
+                let mut media = self.igdb.get_media(&igdb_game).await?;
+                if let Some(local) = local {
+                    media = overlay_media(local, media);
+                }
 
                 self.database
                     .metadata()
                     .update_metadata(&game.id, &metadata, Some(&media))
                     .await?;
+                self.remember_igdb_game(&game.id, igdb_game.id, &external_ids(&igdb_game))
+                    .await?;
 
                 // Mettre à jour le jeu en mémoire
                 game.metadata = metadata;
                 game.media = media;
                 Ok(())
             }
-            Err(e) => Ok(()),
+            Err(_) => Ok(()),
         }
     }
 }
+
+fn external_ids(igdb_game: &IgdbGame) -> Vec<(Platform, String)> {
+    igdb_game
+        .external_games
+        .iter()
+        .flatten()
+        .filter_map(|external| {
+            let platform = match external.category? {
+                IGDB_EXTERNAL_STEAM => Platform::Steam,
+                IGDB_EXTERNAL_GOG => Platform::Gog,
+                _ => return None,
+            };
+            Some((platform, external.uid.clone()?))
+        })
+        .collect()
+}
@@ -1,5 +1,20 @@
+mod analytics;
+mod artwork;
+mod export;
 mod igdb;
+mod library;
 mod metadata;
+mod news;
+mod screenshots;
+mod works;
+mod year_review;
+pub use analytics::{AnalyticsService, AnalyticsZone};
+pub use artwork::{overlay_media, LocalArtworkService};
+pub use export::ExportService;
 pub use igdb::IgdbSearchResult;
-pub use igdb::IgdbService;
+pub use library::OwnedLibraryService;
 pub use metadata::MetadataService;
+pub use news::NewsService;
+pub use screenshots::ScreenshotService;
+pub use works::WorkService;
+pub use year_review::YearReviewService;
@@ -0,0 +1,134 @@
+use crate::api::SteamApi;
+use crate::db::Database;
+use crate::models::{GameResult, NewsRefreshSummary, Platform};
+use chrono::Utc;
+use std::sync::Arc;
+
+// Nombre d'actualités demandées par jeu à chaque rafraîchissement
+const NEWS_PER_GAME: u32 = 20;
+
+// Flux ISteamNews des jeux Steam installés, mis en cache dans game_news
+pub struct NewsService {
+    database: Arc<Database>,
+}
+
+impl NewsService {
+    pub fn new(database: Arc<Database>) -> Self {
+        Self { database }
+    }
+
+    // Ne redemande que les flux plus vieux que ttl_secs, sauf si force ;
+    // l'ETag connu est toujours envoyé pour profiter des réponses 304
+    pub async fn refresh(
+        &self,
+        api: &SteamApi,
+        ttl_secs: i64,
+        force: bool,
+    ) -> GameResult<NewsRefreshSummary> {
+        let now = Utc::now().timestamp();
+        let games = self.database.games().get_all_games().await?;
+        let mut summary = NewsRefreshSummary::default();
+
+        for game in games
+            .iter()
+            // Les actualités d'un DLC sont publiées sur celles du jeu de base
+            .filter(|game| {
+                game.platform == Platform::Steam && game.installed && game.parent_game_id.is_none()
+            })
+        {
+            let state = self.database.news().fetch_state(&game.id).await?;
+            if !force && state.as_ref().is_some_and(|(_, fetched_at)| now - fetched_at < ttl_secs) {
+                continue;
+            }
+            summary.games_checked += 1;
+
+            let etag = state.and_then(|(etag, _)| etag);
+            match api
+                .get_news_for_app(&game.platform_id, NEWS_PER_GAME, etag.as_deref())
+                .await
+            {
+                Ok(None) => {
+                    summary.games_not_modified += 1;
+                    self.database
+                        .news()
+                        .set_fetch_state(&game.id, etag.as_deref())
+                        .await?;
+                }
+                Ok(Some(page)) => {
+                    summary.games_fetched += 1;
+                    summary.new_items += self.database.news().upsert_news(&game.id, &page.items).await?;
+                    self.database
+                        .news()
+                        .set_fetch_state(&game.id, page.etag.as_deref())
+                        .await?;
+                }
+                Err(e) => summary.errors.push(format!("{}: {}", game.title, e)),
+            }
+        }
+
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::stub_server::{StubResponse, StubServer};
+    use crate::models::{fixtures, Game};
+    use serde_json::json;
+
+    const ETAG: &str = "\"news-v1\"";
+
+    fn steam_game(app_id: &str) -> Game {
+        Game {
+            platform: Platform::Steam,
+            platform_id: app_id.to_string(),
+            ..fixtures::game(&format!("steam_{}", app_id), app_id)
+        }
+    }
+
+    #[tokio::test]
+    async fn refresh_uses_the_etag_and_the_ttl() {
+        let database = Arc::new(Database::in_memory().await.unwrap());
+        database.games().upsert_game(&steam_game("10")).await.unwrap();
+
+        let server = StubServer::start(|request| {
+            if request.headers.get("if-none-match").map(String::as_str) == Some(ETAG) {
+                return StubResponse::status(304);
+            }
+            StubResponse::json(json!({
+                "appnews": {
+                    "appid": 10,
+                    "newsitems": [{
+                        "gid": "1",
+                        "title": "Patch notes",
+                        "url": "https://example.com/news/1",
+                        "date": 1700000000
+                    }]
+                }
+            }))
+            .with_header("ETag", ETAG)
+        })
+        .await;
+        let api = SteamApi::new(String::new()).with_base_urls(&server.url, &server.url);
+        let service = NewsService::new(database.clone());
+
+        // 200 : les actualités et l'ETag sont enregistrés
+        let fetched = service.refresh(&api, 3600, false).await.unwrap();
+        assert_eq!((fetched.games_fetched, fetched.new_items), (1, 1));
+        let (etag, _) = database.news().fetch_state("steam_10").await.unwrap().unwrap();
+        assert_eq!(etag.as_deref(), Some(ETAG));
+
+        // Flux encore frais : aucun appel
+        let skipped = service.refresh(&api, 3600, false).await.unwrap();
+        assert_eq!(skipped.games_checked, 0);
+        assert_eq!(server.requests().len(), 1);
+
+        // Rafraîchissement forcé : l'ETag est renvoyé et le serveur répond 304
+        let not_modified = service.refresh(&api, 3600, true).await.unwrap();
+        assert_eq!((not_modified.games_checked, not_modified.games_not_modified), (1, 1));
+        assert_eq!(not_modified.new_items, 0);
+        assert!(not_modified.errors.is_empty());
+        assert_eq!(server.requests().len(), 2);
+    }
+}
@@ -0,0 +1,207 @@
+use crate::log_warn;
+use crate::db::Database;
+use crate::models::{GameResult, ScreenshotIndexSummary, ScreenshotSource, UserScreenshot};
+use crate::platforms::steam::SteamScreenshot;
+use crate::utils::image_header::{image_dimensions, is_image};
+use crate::utils::settings::ScreenshotFolder;
+use crate::utils::text::normalize;
+use crate::utils::AppError;
+use notify::{watcher, DebouncedEvent, RecursiveMode, Watcher};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, UNIX_EPOCH};
+use tokio::sync::mpsc::UnboundedSender;
+use walkdir::WalkDir;
+
+// Un titre plus court serait reconnu dans trop de noms de fichiers
+const MIN_TITLE_MATCH_LEN: usize = 3;
+
+// Index des captures d'écran locales, rattachées aux jeux de la bibliothèque
+pub struct ScreenshotService {
+    database: Arc<Database>,
+}
+
+impl ScreenshotService {
+    pub fn new(database: Arc<Database>) -> Self {
+        Self { database }
+    }
+
+    // Ajoute les nouvelles captures et oublie celles dont le fichier a disparu
+    pub async fn index(
+        &self,
+        steam_screenshots: Vec<SteamScreenshot>,
+        folders: &[ScreenshotFolder],
+    ) -> GameResult<ScreenshotIndexSummary> {
+        let games: Vec<(String, String)> = self
+            .database
+            .games()
+            .get_all_games()
+            .await?
+            .into_iter()
+            .map(|game| (game.id, normalize(&game.title)))
+            .collect();
+        let known: HashSet<&str> = games.iter().map(|(id, _)| id.as_str()).collect();
+        // Seule la reconnaissance par titre exclut les titres trop courts
+        let titles: Vec<(String, String)> = games
+            .iter()
+            .filter(|(_, title)| title.len() >= MIN_TITLE_MATCH_LEN)
+            .cloned()
+            .collect();
+
+        let mut summary = ScreenshotIndexSummary::default();
+        let mut candidates = Vec::new();
+        for shot in steam_screenshots {
+            candidates.push(screenshot(
+                format!("steam_{}", shot.app_id),
+                shot.path,
+                shot.caption,
+                shot.created,
+                ScreenshotSource::Steam,
+            ));
+        }
+        for folder in folders {
+            for path in image_files(&folder.path) {
+                let game_id = folder
+                    .game_id
+                    .clone()
+                    .or_else(|| match_game(&titles, &folder.path, &path));
+                match game_id {
+                    Some(game_id) => candidates.push(screenshot(
+                        game_id,
+                        path,
+                        None,
+                        None,
+                        ScreenshotSource::Folder,
+                    )),
+                    None => summary.unmatched += 1,
+                }
+            }
+        }
+
+        let indexed = self.database.screenshots().indexed_paths().await?;
+        let mut seen = HashSet::new();
+        for mut candidate in candidates {
+            let path = candidate.path.to_string_lossy().to_string();
+            if !seen.insert(path.clone()) || indexed.contains(&path) {
+                continue;
+            }
+            if !known.contains(candidate.game_id.as_str()) {
+                summary.unmatched += 1;
+                continue;
+            }
+
+            if let Some((width, height)) = image_dimensions(&candidate.path) {
+                candidate.width = Some(width);
+                candidate.height = Some(height);
+            }
+            if self.database.screenshots().insert_screenshot(&candidate).await? {
+                summary.indexed += 1;
+            }
+        }
+
+        let missing: Vec<String> = indexed
+            .into_iter()
+            .filter(|path| !seen.contains(path) && !Path::new(path).exists())
+            .collect();
+        summary.removed = self.database.screenshots().delete_paths(&missing).await? as usize;
+
+        Ok(summary)
+    }
+
+    pub async fn game_gallery(&self, game_id: &str) -> GameResult<Vec<UserScreenshot>> {
+        self.database.screenshots().game_gallery(game_id).await
+    }
+
+    // Surveille les dossiers dans un thread dédié ; chaque image créée, renommée
+    // ou supprimée envoie un signal, jusqu'à fermeture du récepteur
+    pub fn watch(dirs: Vec<PathBuf>, changes: UnboundedSender<()>) -> GameResult<()> {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut watcher = watcher(tx, Duration::from_secs(2)).map_err(|e| AppError {
+            message: format!("Failed to create screenshot watcher: {}", e),
+        })?;
+        for dir in &dirs {
+            if let Err(e) = watcher.watch(dir, RecursiveMode::Recursive) {
+                log_warn!("Cannot watch {}: {}", dir.display(), e);
+            }
+        }
+
+        std::thread::spawn(move || {
+            // Le watcher vit aussi longtemps que ce thread
+            let _watcher = watcher;
+            while let Ok(event) = rx.recv() {
+                let path = match event {
+                    DebouncedEvent::Create(path)
+                    | DebouncedEvent::Write(path)
+                    | DebouncedEvent::Remove(path)
+                    | DebouncedEvent::Rename(_, path) => path,
+                    _ => continue,
+                };
+                if is_image(&path) && changes.send(()).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(())
+    }
+}
+
+fn screenshot(
+    game_id: String,
+    path: PathBuf,
+    caption: Option<String>,
+    created: Option<i64>,
+    source: ScreenshotSource,
+) -> UserScreenshot {
+    let taken_at = created.unwrap_or_else(|| {
+        std::fs::metadata(&path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| duration.as_secs() as i64)
+            .unwrap_or_default()
+    });
+
+    UserScreenshot {
+        id: 0,
+        game_id,
+        path,
+        taken_at,
+        width: None,
+        height: None,
+        caption,
+        source,
+    }
+}
+
+fn image_files(folder: &Path) -> Vec<PathBuf> {
+    WalkDir::new(folder)
+        .into_iter()
+        .flatten()
+        .filter(|entry| entry.file_type().is_file() && is_image(entry.path()))
+        .map(|entry| entry.into_path())
+        .collect()
+}
+
+// Le sous-dossier (GeForce Experience) ou le début du nom de fichier
+// (Xbox Game Bar) doit commencer par le titre ; le titre le plus long l'emporte
+fn match_game(games: &[(String, String)], folder: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(folder).ok()?;
+    let mut names: Vec<String> = Vec::new();
+    if let Some(first) = relative.components().next() {
+        if relative.components().count() > 1 {
+            names.push(normalize(&first.as_os_str().to_string_lossy()));
+        }
+    }
+    if let Some(stem) = path.file_stem() {
+        names.push(normalize(&stem.to_string_lossy()));
+    }
+
+    games
+        .iter()
+        .filter(|(_, title)| names.iter().any(|name| name.starts_with(title.as_str())))
+        .max_by_key(|(_, title)| title.len())
+        .map(|(id, _)| id.clone())
+}
+
@@ -0,0 +1,527 @@
+use super::artwork::overlay_media;
+use crate::db::{nest_addons, Database};
+use crate::models::{Game, GameKind, GameResult, GameStats, Work, WorkRecord};
+use crate::utils::text::normalize;
+use crate::utils::AppError;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+// Clés de rapprochement d'une installation
+struct MatchKey {
+    igdb_id: Option<i64>,
+    title: String,
+    year: Option<i32>,
+}
+
+impl MatchKey {
+    fn of(game: &Game, igdb_ids: &HashMap<String, i64>) -> Self {
+        Self {
+            igdb_id: igdb_ids.get(&game.id).copied(),
+            title: normalize(&game.metadata.title),
+            year: release_year(game),
+        }
+    }
+
+    // Même jeu IGDB ; sinon même titre normalisé et années compatibles
+    fn matches(&self, work: &WorkRecord) -> bool {
+        match (self.igdb_id, work.igdb_id) {
+            (Some(igdb_id), Some(work_igdb_id)) => igdb_id == work_igdb_id,
+            _ => {
+                !self.title.is_empty()
+                    && self.title == work.normalized_title
+                    && (self.year.is_none()
+                        || work.release_year.is_none()
+                        || self.year == work.release_year)
+            }
+        }
+    }
+}
+
+// Regroupe les installations d'un même jeu sur plusieurs boutiques en œuvres
+pub struct WorkService {
+    database: Arc<Database>,
+}
+
+impl WorkService {
+    pub fn new(database: Arc<Database>) -> Self {
+        Self { database }
+    }
+
+    // Rattache chaque jeu de base à une œuvre. Une installation déjà groupée avec
+    // d'autres ne bouge plus, ni un rattachement choisi par l'utilisateur
+    pub async fn regroup(&self) -> GameResult<()> {
+        let games: Vec<Game> = self
+            .database
+            .games()
+            .get_all_games()
+            .await?
+            .into_iter()
+            .filter(|game| game.kind == GameKind::Base && game.parent_game_id.is_none())
+            .collect();
+        let igdb_ids = self.database.works().igdb_ids().await?;
+        let assignments = self.database.works().assignments().await?;
+        let mut works: HashMap<String, WorkRecord> = self
+            .database
+            .works()
+            .list_works()
+            .await?
+            .into_iter()
+            .map(|work| (work.id.clone(), work))
+            .collect();
+        let saved = works.clone();
+
+        let mut members: HashMap<String, Vec<&Game>> = HashMap::new();
+        for game in &games {
+            if let Some((Some(work_id), _)) = assignments.get(&game.id) {
+                members.entry(work_id.clone()).or_default().push(game);
+            }
+        }
+
+        for game in &games {
+            let (work_id, locked) = assignments.get(&game.id).cloned().unwrap_or_default();
+            let work_id = work_id.filter(|work_id| works.contains_key(work_id));
+            if locked && work_id.is_some() {
+                continue;
+            }
+            if work_id
+                .as_ref()
+                .is_some_and(|work_id| members.get(work_id).is_some_and(|games| games.len() > 1))
+            {
+                continue;
+            }
+
+            // Jamais deux installations de la même boutique dans une œuvre
+            let key = MatchKey::of(game, &igdb_ids);
+            let target = works
+                .values()
+                .filter(|work| Some(&work.id) != work_id.as_ref())
+                .filter(|work| {
+                    members.get(&work.id).is_some_and(|games| {
+                        !games.is_empty() && games.iter().all(|other| other.platform != game.platform)
+                    })
+                })
+                .find(|work| key.matches(work))
+                .map(|work| work.id.clone());
+
+            let new_work_id = match (target, work_id) {
+                (Some(target), previous) => {
+                    if let Some(games) = previous.and_then(|previous| members.get_mut(&previous)) {
+                        games.retain(|other| other.id != game.id);
+                    }
+                    target
+                }
+                (None, Some(_)) => continue,
+                (None, None) => {
+                    let work = new_work(game, key);
+                    self.database.works().save_work(&work).await?;
+                    let work_id = work.id.clone();
+                    works.insert(work_id.clone(), work);
+                    work_id
+                }
+            };
+
+            self.database
+                .works()
+                .assign(&game.id, Some(&new_work_id), false)
+                .await?;
+            members.entry(new_work_id).or_default().push(game);
+        }
+
+        // Titre, année, IGDB et installation préférée suivent les membres
+        for (work_id, games) in &members {
+            let Some(work) = works.get_mut(work_id) else {
+                continue;
+            };
+            if games.is_empty() {
+                continue;
+            }
+
+            let source = metadata_source(games, work.preferred_game_id.as_deref(), &igdb_ids);
+            work.title = source.metadata.title.clone();
+            work.normalized_title = normalize(&work.title);
+            work.release_year = release_year(source);
+            work.igdb_id = igdb_ids.get(&source.id).copied();
+            let preferred_is_member = work
+                .preferred_game_id
+                .as_ref()
+                .is_some_and(|preferred| games.iter().any(|game| &game.id == preferred));
+            if !preferred_is_member {
+                work.preferred_game_id = default_installation(games).map(|game| game.id.clone());
+            }
+
+            if saved.get(work_id) != Some(&*work) {
+                self.database.works().save_work(work).await?;
+            }
+        }
+
+        self.database.works().delete_empty_works().await?;
+        Ok(())
+    }
+
+    pub async fn works(&self) -> GameResult<Vec<Work>> {
+        let assignments = self.database.works().assignments().await?;
+        let igdb_ids = self.database.works().igdb_ids().await?;
+
+        let mut installations: HashMap<String, Vec<Game>> = HashMap::new();
+        for game in nest_addons(self.database.games().get_all_games().await?) {
+            if let Some((Some(work_id), _)) = assignments.get(&game.id) {
+                installations.entry(work_id.clone()).or_default().push(game);
+            }
+        }
+
+        Ok(self
+            .database
+            .works()
+            .list_works()
+            .await?
+            .into_iter()
+            .filter_map(|work| {
+                let games = installations.remove(&work.id)?;
+                Some(work_view(work, games, &igdb_ids))
+            })
+            .collect())
+    }
+
+    // Rattachement manuel ; None sort le jeu dans une œuvre à lui seul. Les
+    // installations restées dans l'ancienne œuvre sont verrouillées aussi pour
+    // ne pas rejoindre aussitôt le jeu déplacé
+    pub async fn assign(&self, game_id: &str, work_id: Option<&str>) -> GameResult<String> {
+        let game = self.get_base_game(game_id).await?;
+        let assignments = self.database.works().assignments().await?;
+
+        let work_id = match work_id {
+            Some(work_id) => {
+                self.get_work(work_id).await?;
+                work_id.to_string()
+            }
+            None => {
+                let igdb_ids = self.database.works().igdb_ids().await?;
+                let work = new_work(&game, MatchKey::of(&game, &igdb_ids));
+                self.database.works().save_work(&work).await?;
+                work.id
+            }
+        };
+
+        if let Some((Some(previous), _)) = assignments.get(game_id) {
+            for (other_id, (other_work, _)) in &assignments {
+                if other_id != game_id && other_work.as_ref() == Some(previous) {
+                    self.database
+                        .works()
+                        .assign(other_id, Some(previous), true)
+                        .await?;
+                }
+            }
+        }
+        self.database
+            .works()
+            .assign(game_id, Some(&work_id), true)
+            .await?;
+
+        self.regroup().await?;
+        Ok(work_id)
+    }
+
+    // Rend le jeu au rapprochement automatique
+    pub async fn reset(&self, game_id: &str) -> GameResult<()> {
+        self.get_base_game(game_id).await?;
+        self.database.works().assign(game_id, None, false).await?;
+        self.regroup().await
+    }
+
+    pub async fn set_preferred(&self, work_id: &str, game_id: &str) -> GameResult<()> {
+        let mut work = self.get_work(work_id).await?;
+        self.ensure_member(work_id, game_id).await?;
+
+        work.preferred_game_id = Some(game_id.to_string());
+        self.database.works().save_work(&work).await
+    }
+
+    // Installation à lancer : celle demandée, sinon celle préférée
+    pub async fn installation(&self, work_id: &str, game_id: Option<&str>) -> GameResult<String> {
+        let work = self.get_work(work_id).await?;
+        let game_id = game_id
+            .map(String::from)
+            .or(work.preferred_game_id)
+            .ok_or_else(|| AppError {
+                message: format!("No installation to launch for {}", work.title),
+            })?;
+
+        self.ensure_member(work_id, &game_id).await?;
+        Ok(game_id)
+    }
+
+    async fn get_work(&self, work_id: &str) -> GameResult<WorkRecord> {
+        self.database
+            .works()
+            .list_works()
+            .await?
+            .into_iter()
+            .find(|work| work.id == work_id)
+            .ok_or_else(|| AppError {
+                message: format!("Work not found: {}", work_id),
+            })
+    }
+
+    async fn get_base_game(&self, game_id: &str) -> GameResult<Game> {
+        let game = self
+            .database
+            .games()
+            .get_game(game_id)
+            .await?
+            .ok_or_else(|| AppError {
+                message: format!("Game not found: {}", game_id),
+            })?;
+
+        if game.kind != GameKind::Base || game.parent_game_id.is_some() {
+            return Err(AppError {
+                message: format!("{} is an add-on and cannot belong to a work", game.title),
+            });
+        }
+        Ok(game)
+    }
+
+    async fn ensure_member(&self, work_id: &str, game_id: &str) -> GameResult<()> {
+        let assignments = self.database.works().assignments().await?;
+        match assignments.get(game_id) {
+            Some((Some(current), _)) if current == work_id => Ok(()),
+            _ => Err(AppError {
+                message: format!("{} is not an installation of this work", game_id),
+            }),
+        }
+    }
+}
+
+fn new_work(game: &Game, key: MatchKey) -> WorkRecord {
+    WorkRecord {
+        id: format!("work_{}", uuid::Uuid::new_v4()),
+        title: game.metadata.title.clone(),
+        normalized_title: key.title,
+        release_year: key.year,
+        igdb_id: key.igdb_id,
+        preferred_game_id: Some(game.id.clone()),
+    }
+}
+
+fn release_year(game: &Game) -> Option<i32> {
+    game.metadata.release_date.as_deref()?.get(..4)?.parse().ok()
+}
+
+fn playtime(game: &Game) -> i64 {
+    game.stats.total_playtime.max(game.stats.imported_playtime)
+}
+
+// Installation qui porte les métadonnées : la préférée si IGDB la connaît,
+// sinon la première connue d'IGDB, sinon la préférée
+fn metadata_source<'a, G: std::borrow::Borrow<Game>>(
+    games: &'a [G],
+    preferred: Option<&str>,
+    igdb_ids: &HashMap<String, i64>,
+) -> &'a Game {
+    let games: Vec<&Game> = games.iter().map(|game| game.borrow()).collect();
+    let preferred = games
+        .iter()
+        .find(|game| Some(game.id.as_str()) == preferred)
+        .copied();
+
+    preferred
+        .filter(|game| igdb_ids.contains_key(&game.id))
+        .or_else(|| games.iter().find(|game| igdb_ids.contains_key(&game.id)).copied())
+        .or(preferred)
+        .unwrap_or(games[0])
+}
+
+// Par défaut : une installation présente, la plus jouée
+fn default_installation<'a>(games: &[&'a Game]) -> Option<&'a Game> {
+    games
+        .iter()
+        .max_by_key(|game| (game.installed, playtime(game)))
+        .copied()
+}
+
+fn work_view(work: WorkRecord, installations: Vec<Game>, igdb_ids: &HashMap<String, i64>) -> Work {
+    let source = metadata_source(&installations, work.preferred_game_id.as_deref(), igdb_ids);
+    let media = installations
+        .iter()
+        .filter(|game| game.id != source.id)
+        .fold(source.media.clone(), |media, game| {
+            overlay_media(&media, game.media.clone())
+        });
+
+    Work {
+        id: work.id,
+        title: work.title,
+        release_year: work.release_year,
+        igdb_id: work.igdb_id,
+        metadata: source.metadata.clone(),
+        media,
+        stats: aggregate_stats(&installations),
+        preferred_game_id: work.preferred_game_id,
+        installations,
+    }
+}
+
+// imported_playtime reçoit, par installation, le plus grand des deux totaux :
+// max(total_playtime, imported_playtime) reste juste pour l'œuvre
+fn aggregate_stats(games: &[Game]) -> GameStats {
+    let latest = games.iter().max_by_key(|game| game.stats.last_played);
+
+    GameStats {
+        total_playtime: games.iter().map(|game| game.stats.total_playtime).sum(),
+        last_session_duration: latest.map_or(0, |game| game.stats.last_session_duration),
+        sessions_count: games.iter().map(|game| game.stats.sessions_count).sum(),
+        first_played: games.iter().filter_map(|game| game.stats.first_played).min(),
+        last_played: games.iter().filter_map(|game| game.stats.last_played).max(),
+        crash_count: games.iter().map(|game| game.stats.crash_count).sum(),
+        completed_at: games.iter().filter_map(|game| game.stats.completed_at).min(),
+        imported_playtime: games.iter().map(playtime).sum(),
+        imported_last_played: games
+            .iter()
+            .filter_map(|game| game.stats.imported_last_played)
+            .max(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{fixtures, Platform};
+
+    struct Library {
+        database: Arc<Database>,
+        works: WorkService,
+    }
+
+    impl Library {
+        async fn new() -> Self {
+            let database = Arc::new(Database::in_memory().await.unwrap());
+            let works = WorkService::new(database.clone());
+            Self { database, works }
+        }
+
+        async fn add(&self, id: &str, platform: Platform, title: &str, release_date: Option<&str>) {
+            let mut game = fixtures::game(id, title);
+            game.platform = platform;
+            game.metadata.release_date = release_date.map(String::from);
+            self.database.games().upsert_game(&game).await.unwrap();
+            self.database
+                .metadata()
+                .update_metadata(id, &game.metadata, None)
+                .await
+                .unwrap();
+        }
+
+        async fn work_of(&self, game_id: &str) -> Option<String> {
+            self.database
+                .works()
+                .assignments()
+                .await
+                .unwrap()
+                .remove(game_id)
+                .and_then(|(work_id, _)| work_id)
+        }
+
+        async fn same_work(&self, first: &str, second: &str) -> bool {
+            let work = self.work_of(first).await;
+            work.is_some() && work == self.work_of(second).await
+        }
+    }
+
+    fn work(title: &str, year: Option<i32>, igdb_id: Option<i64>) -> WorkRecord {
+        WorkRecord {
+            id: "work".to_string(),
+            title: title.to_string(),
+            normalized_title: normalize(title),
+            release_year: year,
+            igdb_id,
+            preferred_game_id: None,
+        }
+    }
+
+    #[test]
+    fn igdb_id_wins_over_the_title() {
+        let key = |igdb_id: Option<i64>, year: Option<i32>| MatchKey {
+            igdb_id,
+            title: normalize("DOOM"),
+            year,
+        };
+
+        assert!(!key(Some(1), None).matches(&work("Doom", None, Some(2))));
+        assert!(key(Some(1), Some(1993)).matches(&work("Doom 2016", Some(2016), Some(1))));
+        // Sans IGDB d'un côté, le titre et l'année décident
+        assert!(key(Some(1), None).matches(&work("Doom", Some(1993), None)));
+        assert!(!key(None, Some(1993)).matches(&work("Doom", Some(2016), None)));
+        assert!(!MatchKey { igdb_id: None, title: String::new(), year: None }.matches(&work("", None, None)));
+    }
+
+    #[tokio::test]
+    async fn stores_are_grouped_by_igdb_then_by_title() {
+        let library = Library::new().await;
+        library.add("steam_doom", Platform::Steam, "DOOM", Some("2016-05-13")).await;
+        library.add("gog_doom", Platform::Gog, "Doom (2016)", None).await;
+        library.add("epic_doom", Platform::Epic, "Doom", Some("1993-12-10")).await;
+        library.database.works().set_igdb_id("steam_doom", 7351).await.unwrap();
+        library.database.works().set_igdb_id("gog_doom", 7351).await.unwrap();
+        library.database.works().set_igdb_id("epic_doom", 673).await.unwrap();
+        // Année manquante : seul le titre compte
+        library.add("steam_hades", Platform::Steam, "Hades", Some("2020-09-17")).await;
+        library.add("epic_hades", Platform::Epic, "HADES", None).await;
+
+        library.works.regroup().await.unwrap();
+
+        assert!(library.same_work("steam_doom", "gog_doom").await);
+        assert!(!library.same_work("steam_doom", "epic_doom").await);
+        assert!(library.same_work("steam_hades", "epic_hades").await);
+        assert_eq!(library.works.works().await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn a_work_never_holds_two_installations_of_one_store() {
+        let library = Library::new().await;
+        library.add("steam_portal", Platform::Steam, "Portal", None).await;
+        library.add("steam_portal_beta", Platform::Steam, "Portal", None).await;
+        library.add("epic_portal", Platform::Epic, "Portal", None).await;
+
+        library.works.regroup().await.unwrap();
+
+        assert!(!library.same_work("steam_portal", "steam_portal_beta").await);
+        for work in library.works.works().await.unwrap() {
+            let stores: Vec<&Platform> = work.installations.iter().map(|game| &game.platform).collect();
+            let steam = stores.iter().filter(|platform| ***platform == Platform::Steam).count();
+            assert!(steam <= 1, "{:?}", stores);
+        }
+    }
+
+    #[tokio::test]
+    async fn manual_assignments_are_locked_until_reset() {
+        let library = Library::new().await;
+        library.add("steam_celeste", Platform::Steam, "Celeste", None).await;
+        library.add("epic_celeste", Platform::Epic, "Celeste", None).await;
+        library.works.regroup().await.unwrap();
+        assert!(library.same_work("steam_celeste", "epic_celeste").await);
+
+        // Sorti à la main dans sa propre œuvre, il n'est plus rapproché
+        library.works.assign("epic_celeste", None).await.unwrap();
+        library.works.regroup().await.unwrap();
+        assert!(!library.same_work("steam_celeste", "epic_celeste").await);
+        let (_, locked) = library.database.works().assignments().await.unwrap()["steam_celeste"].clone();
+        assert!(locked);
+
+        library.works.reset("epic_celeste").await.unwrap();
+        assert!(library.same_work("steam_celeste", "epic_celeste").await);
+    }
+
+    #[tokio::test]
+    async fn single_installation_works_can_match_again() {
+        let library = Library::new().await;
+        library.add("steam_hk", Platform::Steam, "Hollow Knight", None).await;
+        library.add("epic_hk", Platform::Epic, "Hollow Knight: Voidheart Edition", None).await;
+        library.works.regroup().await.unwrap();
+        assert!(!library.same_work("steam_hk", "epic_hk").await);
+
+        // Métadonnées corrigées : l'installation seule rejoint l'autre œuvre
+        library.add("epic_hk", Platform::Epic, "Hollow Knight", None).await;
+        library.works.regroup().await.unwrap();
+        assert!(library.same_work("steam_hk", "epic_hk").await);
+        assert_eq!(library.database.works().list_works().await.unwrap().len(), 1);
+    }
+}
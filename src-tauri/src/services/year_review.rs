@@ -0,0 +1,264 @@
+use super::analytics::{AnalyticsService, AnalyticsZone};
+use crate::db::Database;
+use crate::models::{
+    GameFacets, LongestSession, PlaytimeDimension, PlaytimeGranularity, ReviewGame, YearReview,
+};
+use crate::utils::cache::MediaCache;
+use crate::utils::AppError;
+use chrono::NaiveDate;
+use std::fmt::Write as _;
+use std::sync::Arc;
+use tauri::AppHandle;
+
+const TOP_GAMES: usize = 10;
+
+pub struct YearReviewService {
+    database: Arc<Database>,
+    analytics: Arc<AnalyticsService>,
+    media_cache: MediaCache,
+}
+
+impl YearReviewService {
+    pub fn new(
+        database: Arc<Database>,
+        analytics: Arc<AnalyticsService>,
+        app: &AppHandle,
+    ) -> Result<Self, AppError> {
+        Ok(Self {
+            database,
+            analytics,
+            media_cache: MediaCache::new(app)?,
+        })
+    }
+
+    pub async fn year_review(&self, year: i32, zone: AnalyticsZone) -> Result<YearReview, AppError> {
+        let start = NaiveDate::from_ymd_opt(year, 1, 1)
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .ok_or_else(|| AppError {
+                message: format!("Invalid year: {}", year),
+            })?;
+        let end = NaiveDate::from_ymd_opt(year + 1, 1, 1)
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .ok_or_else(|| AppError {
+                message: format!("Invalid year: {}", year),
+            })?;
+        let (from, to) = (Some(zone.timestamp(start)), Some(zone.timestamp(end)));
+
+        let months = self
+            .analytics
+            .playtime_totals(PlaytimeGranularity::Month, from, to, zone)
+            .await?;
+        let days = self
+            .analytics
+            .playtime_totals(PlaytimeGranularity::Day, from, to, zone)
+            .await?;
+        let mut top_games = self.analytics.top_games(from, to, usize::MAX).await?;
+        let genres = self
+            .analytics
+            .playtime_breakdown(PlaytimeDimension::Genre, from, to)
+            .await?;
+        let facets = self.database.analytics().game_facets().await?;
+
+        let in_year = |timestamp: &Option<i64>| {
+            timestamp.is_some_and(|timestamp| Some(timestamp) >= from && Some(timestamp) < to)
+        };
+        let review_games = |timestamp: fn(&GameFacets) -> Option<i64>| {
+            let mut games: Vec<ReviewGame> = facets
+                .values()
+                .filter(|game| in_year(&timestamp(game)))
+                .map(|game| ReviewGame {
+                    game_id: game.game_id.clone(),
+                    title: game.title.clone(),
+                    timestamp: timestamp(game).unwrap_or_default(),
+                })
+                .collect();
+            games.sort_by_key(|game| game.timestamp);
+            games
+        };
+        let first_played = review_games(|game| game.first_played);
+        let games_finished = review_games(|game| game.completed_at);
+
+        let longest_session = self
+            .database
+            .analytics()
+            .sessions_between(from, to)
+            .await?
+            .into_iter()
+            .max_by_key(|session| session.duration)
+            .map(|session| LongestSession {
+                title: facets
+                    .get(&session.game_id)
+                    .map(|game| game.title.clone())
+                    .unwrap_or_else(|| session.game_id.clone()),
+                session,
+            });
+
+        let games_played = top_games.len() as i64;
+        top_games.truncate(TOP_GAMES);
+
+        Ok(YearReview {
+            year,
+            total_seconds: months.iter().map(|month| month.seconds).sum(),
+            sessions_count: months.iter().map(|month| month.sessions).sum(),
+            games_played,
+            days_played: days.iter().filter(|day| day.seconds > 0).count() as i64,
+            top_games,
+            first_played,
+            longest_session,
+            most_active_month: months
+                .iter()
+                .filter(|month| month.seconds > 0)
+                .max_by_key(|month| month.seconds)
+                .cloned(),
+            months,
+            genres,
+            games_finished,
+        })
+    }
+
+    // Page HTML autonome à partager : styles et jaquettes sont intégrés
+    pub async fn render_html(&self, review: &YearReview) -> Result<String, AppError> {
+        let facets = self.database.analytics().game_facets().await?;
+
+        let mut covers = Vec::new();
+        for game in &review.top_games {
+            let cover = match facets.get(&game.game_id).and_then(|game| game.cover.as_deref()) {
+                Some(cover) => self.media_cache.data_uri(cover).await,
+                None => None,
+            };
+            covers.push(cover);
+        }
+
+        let mut html = String::new();
+        let _ = write!(
+            html,
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{year} in review</title>
+<style>
+body {{ margin: 0; padding: 48px; background: #0f1115; color: #e6e6e6; font-family: system-ui, sans-serif; }}
+h1 {{ font-size: 48px; margin: 0 0 32px; }}
+h2 {{ margin-top: 48px; color: #9aa4b2; font-size: 18px; text-transform: uppercase; letter-spacing: 2px; }}
+.stats {{ display: flex; gap: 32px; flex-wrap: wrap; }}
+.stat b {{ display: block; font-size: 36px; }}
+.stat span, .muted {{ color: #9aa4b2; }}
+.games {{ display: grid; grid-template-columns: repeat(auto-fill, minmax(160px, 1fr)); gap: 24px; }}
+.game img, .game .cover {{ width: 100%; aspect-ratio: 3 / 4; object-fit: cover; border-radius: 8px; background: #1c2027; }}
+.game b {{ display: block; margin-top: 8px; }}
+ul {{ padding-left: 20px; line-height: 1.8; }}
+</style>
+</head>
+<body>
+<h1>{year} in review</h1>
+<div class="stats">
+<div class="stat"><b>{total}</b><span>played</span></div>
+<div class="stat"><b>{games}</b><span>games</span></div>
+<div class="stat"><b>{sessions}</b><span>sessions</span></div>
+<div class="stat"><b>{days}</b><span>days played</span></div>
+</div>
+"#,
+            year = review.year,
+            total = format_duration(review.total_seconds),
+            games = review.games_played,
+            sessions = review.sessions_count,
+            days = review.days_played,
+        );
+
+        if !review.top_games.is_empty() {
+            html.push_str("<h2>Most played</h2>\n<div class=\"games\">\n");
+            for (game, cover) in review.top_games.iter().zip(&covers) {
+                let image = match cover {
+                    Some(cover) => format!("<img src=\"{}\" alt=\"\">", cover),
+                    None => "<div class=\"cover\"></div>".to_string(),
+                };
+                let _ = writeln!(
+                    html,
+                    "<div class=\"game\">{}<b>{}</b><span class=\"muted\">{}</span></div>",
+                    image,
+                    escape_html(&game.title),
+                    format_duration(game.seconds),
+                );
+            }
+            html.push_str("</div>\n");
+        }
+
+        if let Some(month) = &review.most_active_month {
+            let _ = writeln!(
+                html,
+                "<h2>Most active month</h2>\n<p>{} &middot; <span class=\"muted\">{}</span></p>",
+                month.period,
+                format_duration(month.seconds),
+            );
+        }
+
+        if let Some(longest) = &review.longest_session {
+            let _ = writeln!(
+                html,
+                "<h2>Longest session</h2>\n<p>{} &middot; <span class=\"muted\">{}</span></p>",
+                escape_html(&longest.title),
+                format_duration(longest.session.duration.unwrap_or_default()),
+            );
+        }
+
+        push_list(
+            &mut html,
+            "Top genres",
+            review
+                .genres
+                .iter()
+                .take(5)
+                .map(|genre| {
+                    format!(
+                        "{} &middot; <span class=\"muted\">{}</span>",
+                        escape_html(&genre.key),
+                        format_duration(genre.seconds)
+                    )
+                }),
+        );
+        push_list(
+            &mut html,
+            "Played for the first time",
+            review.first_played.iter().map(|game| escape_html(&game.title)),
+        );
+        push_list(
+            &mut html,
+            "Finished",
+            review.games_finished.iter().map(|game| escape_html(&game.title)),
+        );
+
+        html.push_str("</body>\n</html>\n");
+        Ok(html)
+    }
+}
+
+fn push_list(html: &mut String, title: &str, items: impl Iterator<Item = String>) {
+    let items: Vec<String> = items.collect();
+    if items.is_empty() {
+        return;
+    }
+
+    let _ = writeln!(html, "<h2>{}</h2>\n<ul>", title);
+    for item in items {
+        let _ = writeln!(html, "<li>{}</li>", item);
+    }
+    html.push_str("</ul>\n");
+}
+
+fn format_duration(seconds: i64) -> String {
+    let hours = seconds / 3600;
+    let minutes = (seconds % 3600) / 60;
+    if hours > 0 {
+        format!("{}h {:02}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -2,7 +2,6 @@ use crate::utils::AppError;
 use lazy_static::lazy_static;
 use regex::Regex;
 use std::collections::HashMap;
-use std::path::PathBuf;
 
 pub struct AcfParser {
     content: String,
@@ -16,15 +15,6 @@ pub struct AcfData {
     pub size_on_disk: u64,
     pub buildid: String,
     pub last_updated: Option<i64>,
-    pub state: u32,
-}
-
-#[derive(Debug)]
-pub struct LibraryFolder {
-    pub path: PathBuf,
-    pub label: String,
-    pub mounted: bool,
-    pub size: u64,
 }
 
 impl AcfParser {
@@ -79,11 +69,6 @@ impl AcfParser {
             buildid: data.get("buildid").unwrap_or(&"0".to_string()).clone(),
 
             last_updated: data.get("LastUpdated").and_then(|s| s.parse().ok()),
-
-            state: data
-                .get("StateFlags")
-                .and_then(|s| s.parse().ok())
-                .unwrap_or(0),
         })
     }
 }
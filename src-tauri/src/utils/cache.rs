@@ -1,11 +1,11 @@
 use crate::utils::AppError;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
 use reqwest::Client;
 use sha2::{Digest, Sha256};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime};
 use tokio::fs as async_fs;
-use tauri::path::BaseDirectory;
 use crate::log_debug;
 use tauri::AppHandle;
 use tauri::Manager;
@@ -20,9 +20,9 @@ impl MediaCache {
         // Utiliser app_data_dir de tauri pour obtenir le bon chemin
         let cache_dir = app.path()
             .app_data_dir()
-            .or_else(|_| Err(AppError {
+            .map_err(|_| AppError {
                 message: "Could not determine cache directory".to_string(),
-            }))?
+            })?
             .join("media");
 
         fs::create_dir_all(&cache_dir).map_err(|e| AppError {
@@ -104,33 +104,65 @@ impl MediaCache {
        Ok(final_relative_path)
    }
 
-    pub fn clear_old_cache(&self, max_age: Duration) -> Result<(), AppError> {
-        let now = SystemTime::now();
-
-        let entries = fs::read_dir(&self.cache_dir).map_err(|e| AppError {
-            message: format!("Failed to read cache directory: {}", e),
+    // Copie une image locale dans le cache ; la clé dépend du chemin, de la taille
+    // et de la date de modification pour suivre les images remplacées
+    pub async fn import_file(&self, source: &Path) -> Result<String, AppError> {
+        let metadata = async_fs::metadata(source).await.map_err(|e| AppError {
+            message: format!("Failed to read {}: {}", source.display(), e),
         })?;
-
-        for entry in entries {
-            let entry = entry.map_err(|e| AppError {
-                message: format!("Failed to read directory entry: {}", e),
-            })?;
-
-            let metadata = entry.metadata().map_err(|e| AppError {
-                message: format!("Failed to read file metadata: {}", e),
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.duration_since(SystemTime::UNIX_EPOCH).ok())
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+
+        let cache_key = format!("{:x}", {
+            let mut hasher = Sha256::new();
+            hasher.update(source.to_string_lossy().as_bytes());
+            hasher.update(metadata.len().to_le_bytes());
+            hasher.update(modified.to_le_bytes());
+            hasher.finalize()
+        });
+        let extension = source
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| extension.to_ascii_lowercase())
+            .unwrap_or_else(|| "jpg".to_string());
+
+        let file_name = format!("{}.{}", cache_key, extension);
+        let cache_path = self.cache_dir.join(&file_name);
+        if !cache_path.exists() {
+            async_fs::copy(source, &cache_path).await.map_err(|e| AppError {
+                message: format!("Failed to copy {} to cache: {}", source.display(), e),
             })?;
-
-            if let Ok(modified) = metadata.modified() {
-                if let Ok(age) = now.duration_since(modified) {
-                    if age > max_age {
-                        if let Err(e) = fs::remove_file(entry.path()) {
-                            eprintln!("Failed to remove cached file: {}", e);
-                        }
-                    }
-                }
-            }
+            log_debug!("Imported local file: {} -> media/{}", source.display(), file_name);
         }
 
-        Ok(())
+        Ok(format!("media/{}", file_name))
+    }
+
+    // Image encodée en data URI, pour les pages HTML autonomes. Accepte un chemin
+    // renvoyé par get_or_download, un chemin absolu ou une URL à mettre en cache
+    pub async fn data_uri(&self, media: &str) -> Option<String> {
+        let relative_path = if media.starts_with("http://") || media.starts_with("https://") {
+            self.get_or_download(media, Duration::from_secs(30 * 24 * 60 * 60))
+                .await
+                .ok()?
+        } else {
+            media.to_string()
+        };
+
+        // Les chemins du cache sont relatifs au dossier parent ("media/...")
+        let path = self.cache_dir.parent()?.join(relative_path);
+        let bytes = async_fs::read(&path).await.ok()?;
+        let mime = match path.extension().and_then(|extension| extension.to_str()) {
+            Some("png") => "image/png",
+            Some("gif") => "image/gif",
+            Some("webp") => "image/webp",
+            _ => "image/jpeg",
+        };
+
+        Some(format!("data:{};base64,{}", mime, STANDARD.encode(bytes)))
     }
 }
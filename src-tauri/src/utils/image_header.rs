@@ -0,0 +1,197 @@
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+// Extensions des captures d'écran reconnues
+pub const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "bmp", "webp"];
+
+pub fn is_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            IMAGE_EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str())
+        })
+}
+
+// Largeur et hauteur lues dans l'en-tête, sans décoder l'image
+pub fn image_dimensions(path: &Path) -> Option<(u32, u32)> {
+    let mut reader = BufReader::new(File::open(path).ok()?);
+    let mut header = [0u8; 30];
+    let read = read_up_to(&mut reader, &mut header)?;
+    let header = &header[..read];
+
+    if header.starts_with(b"\x89PNG\r\n\x1a\n") && header.len() >= 24 {
+        // IHDR est toujours le premier bloc
+        return Some((be_u32(&header[16..20]), be_u32(&header[20..24])));
+    }
+    if header.starts_with(b"BM") && header.len() >= 26 {
+        let width = le_u32(&header[18..22]);
+        // Hauteur négative : image stockée de haut en bas
+        let height = (le_u32(&header[22..26]) as i32).unsigned_abs();
+        return Some((width, height));
+    }
+    if header.starts_with(b"RIFF") && header.len() >= 30 && &header[8..12] == b"WEBP" {
+        return webp_dimensions(header);
+    }
+    if header.starts_with(&[0xFF, 0xD8]) {
+        reader.seek(SeekFrom::Start(2)).ok()?;
+        return jpeg_dimensions(&mut reader);
+    }
+
+    None
+}
+
+fn webp_dimensions(header: &[u8]) -> Option<(u32, u32)> {
+    match &header[12..16] {
+        b"VP8X" => {
+            let width = 1 + (header[24] as u32 | (header[25] as u32) << 8 | (header[26] as u32) << 16);
+            let height = 1 + (header[27] as u32 | (header[28] as u32) << 8 | (header[29] as u32) << 16);
+            Some((width, height))
+        }
+        b"VP8 " => {
+            let width = (header[26] as u32 | (header[27] as u32) << 8) & 0x3FFF;
+            let height = (header[28] as u32 | (header[29] as u32) << 8) & 0x3FFF;
+            Some((width, height))
+        }
+        b"VP8L" => {
+            let bits = le_u32(&header[21..25]);
+            Some(((bits & 0x3FFF) + 1, ((bits >> 14) & 0x3FFF) + 1))
+        }
+        _ => None,
+    }
+}
+
+// Parcourt les segments jusqu'au premier SOFn
+fn jpeg_dimensions(reader: &mut impl Read) -> Option<(u32, u32)> {
+    loop {
+        let mut marker = [0u8; 2];
+        reader.read_exact(&mut marker).ok()?;
+        if marker[0] != 0xFF {
+            return None;
+        }
+        // Octets de remplissage entre segments
+        let mut kind = marker[1];
+        while kind == 0xFF {
+            let mut next = [0u8; 1];
+            reader.read_exact(&mut next).ok()?;
+            kind = next[0];
+        }
+        // Marqueurs sans longueur
+        if kind == 0x01 || (0xD0..=0xD9).contains(&kind) {
+            continue;
+        }
+
+        let mut length = [0u8; 2];
+        reader.read_exact(&mut length).ok()?;
+        let length = u16::from_be_bytes(length) as usize;
+        if length < 2 {
+            return None;
+        }
+
+        let is_frame = matches!(kind, 0xC0..=0xCF) && !matches!(kind, 0xC4 | 0xC8 | 0xCC);
+        if is_frame {
+            let mut frame = [0u8; 5];
+            reader.read_exact(&mut frame).ok()?;
+            let height = u16::from_be_bytes([frame[1], frame[2]]) as u32;
+            let width = u16::from_be_bytes([frame[3], frame[4]]) as u32;
+            return Some((width, height));
+        }
+
+        std::io::copy(&mut reader.by_ref().take(length as u64 - 2), &mut std::io::sink()).ok()?;
+    }
+}
+
+fn read_up_to(reader: &mut impl Read, buffer: &mut [u8]) -> Option<usize> {
+    let mut read = 0;
+    while read < buffer.len() {
+        match reader.read(&mut buffer[read..]).ok()? {
+            0 => break,
+            count => read += count,
+        }
+    }
+    Some(read)
+}
+
+fn be_u32(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+fn le_u32(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("image");
+        std::fs::write(&path, bytes).unwrap();
+        image_dimensions(&path)
+    }
+
+    fn webp(chunk: &[u8; 4], payload: &[u8]) -> Vec<u8> {
+        let mut bytes = b"RIFF\0\0\0\0WEBP".to_vec();
+        bytes.extend(chunk);
+        bytes.extend(10u32.to_le_bytes());
+        bytes.extend(payload);
+        bytes
+    }
+
+    #[test]
+    fn reads_png_and_bmp_headers() {
+        let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        png.extend(1920u32.to_be_bytes());
+        png.extend(1080u32.to_be_bytes());
+        png.extend([8, 6, 0, 0, 0]);
+        assert_eq!(dimensions(&png), Some((1920, 1080)));
+
+        // Hauteur négative : lignes stockées de haut en bas
+        let mut bmp = b"BM".to_vec();
+        bmp.extend([0u8; 16]);
+        bmp.extend(800u32.to_le_bytes());
+        bmp.extend((-600i32).to_le_bytes());
+        assert_eq!(dimensions(&bmp), Some((800, 600)));
+    }
+
+    #[test]
+    fn reads_every_webp_flavour() {
+        // VP8X : dimensions moins un sur 24 bits
+        let mut extended = vec![0u8; 4];
+        extended.extend(&3839u32.to_le_bytes()[..3]);
+        extended.extend(&2159u32.to_le_bytes()[..3]);
+        assert_eq!(dimensions(&webp(b"VP8X", &extended)), Some((3840, 2160)));
+
+        // VP8 : 14 bits de dimension, les 2 bits hauts portent l'échelle
+        let mut lossy = vec![0u8, 0, 0, 0x9d, 0x01, 0x2a];
+        lossy.extend((640u16 | 1 << 14).to_le_bytes());
+        lossy.extend(480u16.to_le_bytes());
+        assert_eq!(dimensions(&webp(b"VP8 ", &lossy)), Some((640, 480)));
+
+        // VP8L : signature puis largeur et hauteur moins un sur 14 bits
+        let mut lossless = vec![0x2f];
+        lossless.extend((1023u32 | 767 << 14).to_le_bytes());
+        lossless.extend([0u8; 5]);
+        assert_eq!(dimensions(&webp(b"VP8L", &lossless)), Some((1024, 768)));
+    }
+
+    #[test]
+    fn walks_jpeg_segments_up_to_the_frame() {
+        let mut jpeg = vec![0xFF, 0xD8];
+        // APP0 puis une table de Huffman (0xC4), qui n'est pas une trame
+        jpeg.extend([0xFF, 0xE0, 0x00, 0x10]);
+        jpeg.extend(b"JFIF\0\x01\x01\0\0\x01\0\x01\0\0");
+        jpeg.extend([0xFF, 0xC4, 0x00, 0x04, 0x00, 0x00]);
+        // Octets de remplissage avant SOF2
+        jpeg.extend([0xFF, 0xFF, 0xC2, 0x00, 0x11, 0x08]);
+        jpeg.extend(720u16.to_be_bytes());
+        jpeg.extend(1280u16.to_be_bytes());
+        jpeg.extend([0u8; 10]);
+        assert_eq!(dimensions(&jpeg), Some((1280, 720)));
+
+        // Fichier coupé avant la trame
+        assert_eq!(dimensions(&jpeg[..24]), None);
+        assert_eq!(dimensions(b"GIF89a"), None);
+    }
+}
@@ -92,8 +92,8 @@ impl Logger {
 #[macro_export]
 macro_rules! log_info {
     ($($arg:tt)*) => {
-        crate::utils::logger::Logger::log(
-            crate::utils::logger::LogLevel::Info,
+        $crate::utils::logger::Logger::log(
+            $crate::utils::logger::LogLevel::Info,
             &format!($($arg)*)
         )
     };
@@ -102,8 +102,8 @@ macro_rules! log_info {
 #[macro_export]
 macro_rules! log_warn {
     ($($arg:tt)*) => {
-        crate::utils::logger::Logger::log(
-            crate::utils::logger::LogLevel::Warning,
+        $crate::utils::logger::Logger::log(
+            $crate::utils::logger::LogLevel::Warning,
             &format!($($arg)*)
         )
     };
@@ -112,8 +112,8 @@ macro_rules! log_warn {
 #[macro_export]
 macro_rules! log_error {
     ($($arg:tt)*) => {
-        crate::utils::logger::Logger::log(
-            crate::utils::logger::LogLevel::Error,
+        $crate::utils::logger::Logger::log(
+            $crate::utils::logger::LogLevel::Error,
             &format!($($arg)*)
         )
     };
@@ -122,8 +122,8 @@ macro_rules! log_error {
 #[macro_export]
 macro_rules! log_debug {
     ($($arg:tt)*) => {
-        crate::utils::logger::Logger::log(
-            crate::utils::logger::LogLevel::Debug,
+        $crate::utils::logger::Logger::log(
+            $crate::utils::logger::LogLevel::Debug,
             &format!($($arg)*)
         )
     };
@@ -1,10 +1,10 @@
-use std::error::Error;
-use std::fmt;
 pub mod acf;
 pub mod cache;
 pub mod executable_finder;
+pub mod image_header;
 pub mod logger;
 pub mod settings;
+pub mod text;
 pub mod vdf;
 pub mod secrets;
 pub mod paths;
@@ -38,12 +38,6 @@ impl From<&str> for AppError {
     }
 }
 
-pub fn to_app_error<E: std::error::Error>(err: E) -> AppError {
-    AppError {
-        message: err.to_string(),
-    }
-}
-
 impl From<reqwest::Error> for AppError {
     fn from(error: reqwest::Error) -> Self {
         AppError {
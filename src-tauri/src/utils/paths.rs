@@ -5,11 +5,11 @@ use crate::utils::AppError;
 use std::fs;
 
 pub struct AppPaths {
-    pub root: PathBuf,
     pub media: PathBuf,
     pub database: PathBuf,
     pub logs: PathBuf,
     pub settings: PathBuf,
+    pub prefixes: PathBuf,
 }
 
 impl AppPaths {
@@ -27,7 +27,7 @@ impl AppPaths {
             database: root.join("data"),
             logs: root.join("logs"),
             settings: root.join("config"),
-            root,
+            prefixes: root.join("prefixes"),
         };
 
         // Créer tous les dossiers nécessaires
@@ -43,6 +43,9 @@ impl AppPaths {
         fs::create_dir_all(&paths.settings).map_err(|e| AppError {
             message: format!("Failed to create settings directory: {}", e)
         })?;
+        fs::create_dir_all(&paths.prefixes).map_err(|e| AppError {
+            message: format!("Failed to create prefixes directory: {}", e)
+        })?;
 
         Ok(paths)
     }
@@ -58,4 +61,8 @@ impl AppPaths {
     pub fn get_log_path(&self) -> PathBuf {
         self.logs.join("app.log")
     }
+
+    pub fn get_prefix_path(&self, game_id: &str) -> PathBuf {
+        self.prefixes.join(game_id)
+    }
 }
\ No newline at end of file
@@ -1,5 +1,4 @@
 use serde::{Deserialize, Serialize};
-use keyring::Entry;
 use std::env;
 use std::collections::HashMap;
 use crate::utils::AppError;
@@ -75,14 +74,18 @@ impl CompiledSecrets {
 }
 
 pub struct SecretsManager {
-    app_name: String,
     secrets: CompiledSecrets,
 }
 
+impl Default for SecretsManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl SecretsManager {
-    pub fn new(app_name: &str) -> Self {
+    pub fn new() -> Self {
         Self {
-            app_name: app_name.to_string(),
             secrets: CompiledSecrets::new().expect("Failed to load secrets"),
         }
     }
@@ -100,36 +103,6 @@ impl SecretsManager {
         }
     }
 
-    pub fn store_token(&self, service: &str, token: &str) -> Result<(), AppError> {
-        let entry = Entry::new(&self.app_name, service);
-        match entry.set_password(token) {
-            Ok(_) => Ok(()),
-            Err(e) => Err(AppError {
-                message: format!("Failed to store token: {}", e)
-            })
-        }
-    }
-
-    pub fn get_token(&self, service: &str) -> Result<String, AppError> {
-        let entry = Entry::new(&self.app_name, service);
-        match entry.get_password() {
-            Ok(pwd) => Ok(pwd),
-            Err(e) => Err(AppError {
-                message: format!("Failed to retrieve token: {}", e)
-            })
-        }
-    }
-
-    pub fn delete_token(&self, service: &str) -> Result<(), AppError> {
-        let entry = Entry::new(&self.app_name, service);
-        match entry.delete_password() {
-            Ok(_) => Ok(()),
-            Err(e) => Err(AppError {
-                message: format!("Failed to delete token: {}", e)
-            })
-        }
-    }
-
     pub fn validate_required_secrets(&self) -> Result<(), AppError> {
         let required_secrets = [
             "IGDB_CLIENT_ID",
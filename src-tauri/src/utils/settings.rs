@@ -1,20 +1,118 @@
+use crate::models::PlaytimeMode;
 use crate::utils::AppError;
 use serde::{Deserialize, Serialize};
+#[cfg(windows)]
 use std::env;
 use std::fs;
 use std::path::PathBuf;
-use tauri::AppHandle;
+use std::time::Duration;
+use tauri::{AppHandle, Window};
 use crate::utils::AppPaths;
+#[cfg(windows)]
 use winreg::enums::*;
+#[cfg(windows)]
 use winreg::RegKey;
-use windows::Win32::UI::WindowsAndMessaging::*;
-use windows::Win32::Foundation::HWND;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppSettings {
     pub start_with_windows: bool,
     pub minimize_to_tray: bool,
     pub check_updates_on_startup: bool,
+    // Délai avant de clore une session quand plus aucun processus du jeu ne tourne
+    #[serde(default = "default_session_grace_period_secs")]
+    pub session_grace_period_secs: u64,
+    // Détection des jeux lancés hors de glaunch
+    #[serde(default = "default_true")]
+    pub auto_detect_games: bool,
+    #[serde(default = "default_auto_detect_interval_secs")]
+    pub auto_detect_interval_secs: u64,
+    #[serde(default = "default_true")]
+    pub auto_detect_pause_on_battery: bool,
+    // Temps sans saisie au-delà duquel une session est comptée comme inactive
+    #[serde(default = "default_idle_threshold_secs")]
+    pub idle_threshold_secs: u64,
+    #[serde(default)]
+    pub playtime_mode: PlaytimeMode,
+    // Délai accordé à un jeu pour se fermer avant l'arrêt forcé
+    #[serde(default = "default_stop_game_timeout_secs")]
+    pub stop_game_timeout_secs: u64,
+    // Un jeu qui se ferme avant ce délai est compté comme un crash au lancement
+    #[serde(default = "default_crash_on_start_secs")]
+    pub crash_on_start_secs: u64,
+    #[serde(default)]
+    pub api_endpoints: ApiEndpoints,
+    // Actualités Steam : rafraîchissement en arrière-plan et durée de validité du cache
+    #[serde(default = "default_true")]
+    pub news_auto_refresh: bool,
+    #[serde(default = "default_news_ttl_mins")]
+    pub news_ttl_mins: u64,
+    // Dossiers de captures en plus de ceux de Steam
+    #[serde(default)]
+    pub screenshot_folders: Vec<ScreenshotFolder>,
+}
+
+// Sans game_id, les captures sont attribuées d'après le nom du sous-dossier
+// ou le début du nom de fichier (Xbox Game Bar, GeForce Experience...)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScreenshotFolder {
+    pub path: PathBuf,
+    #[serde(default)]
+    pub game_id: Option<String>,
+}
+
+// URLs de base des services en ligne, modifiables pour pointer vers un serveur de test
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApiEndpoints {
+    pub steam_api: String,
+    pub steam_store: String,
+    pub epic_library: String,
+    pub epic_catalog: String,
+}
+
+impl Default for ApiEndpoints {
+    fn default() -> Self {
+        Self {
+            steam_api: "https://api.steampowered.com".to_string(),
+            steam_store: "https://store.steampowered.com/api".to_string(),
+            epic_library: "https://library-service.live.use1a.on.epicgames.com".to_string(),
+            epic_catalog: "https://catalog-public-service-prod06.ol.epicgames.com".to_string(),
+        }
+    }
+}
+
+fn default_session_grace_period_secs() -> u64 {
+    15
+}
+
+fn default_auto_detect_interval_secs() -> u64 {
+    10
+}
+
+fn default_idle_threshold_secs() -> u64 {
+    300
+}
+
+fn default_stop_game_timeout_secs() -> u64 {
+    10
+}
+
+fn default_crash_on_start_secs() -> u64 {
+    30
+}
+
+fn default_news_ttl_mins() -> u64 {
+    180
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl AppSettings {
+    pub fn session_grace_period(&self) -> Duration {
+        Duration::from_secs(self.session_grace_period_secs)
+    }
 }
 
 impl Default for AppSettings {
@@ -23,6 +121,18 @@ impl Default for AppSettings {
             start_with_windows: false,
             minimize_to_tray: true,
             check_updates_on_startup: true,
+            session_grace_period_secs: default_session_grace_period_secs(),
+            auto_detect_games: true,
+            auto_detect_interval_secs: default_auto_detect_interval_secs(),
+            auto_detect_pause_on_battery: true,
+            idle_threshold_secs: default_idle_threshold_secs(),
+            playtime_mode: PlaytimeMode::default(),
+            stop_game_timeout_secs: default_stop_game_timeout_secs(),
+            crash_on_start_secs: default_crash_on_start_secs(),
+            api_endpoints: ApiEndpoints::default(),
+            news_auto_refresh: true,
+            news_ttl_mins: default_news_ttl_mins(),
+            screenshot_folders: Vec::new(),
         }
     }
 }
@@ -82,6 +192,7 @@ impl SettingsManager {
         Ok(())
     }
 
+    #[cfg(windows)]
     fn update_autostart(&self) -> Result<(), AppError> {
         let hkcu = RegKey::predef(HKEY_CURRENT_USER);
         let path = r"Software\Microsoft\Windows\CurrentVersion\Run";
@@ -122,19 +233,18 @@ impl SettingsManager {
         Ok(())
     }
 
-    pub fn should_minimize_to_tray(&self, hwnd: HWND) -> bool {
+    // Le lancement au démarrage passe par la clé Run du registre, propre à Windows
+    #[cfg(not(windows))]
+    fn update_autostart(&self) -> Result<(), AppError> {
+        Ok(())
+    }
+
+    pub fn should_minimize_to_tray(&self, window: &Window) -> bool {
         if !self.settings.minimize_to_tray {
             return false;
         }
 
-        unsafe {
-            let style = GetWindowLongW(hwnd, GWL_STYLE);
-            if (style as u32 & WS_MINIMIZE.0) != 0 {
-                return false;
-            }
-        }
-
-        true
+        !window.is_minimized().unwrap_or(false)
     }
 
     pub fn export_database(&self, export_path: PathBuf) -> Result<(), AppError> {
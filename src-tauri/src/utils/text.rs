@@ -0,0 +1,8 @@
+// Forme de comparaison d'un titre ou d'un nom de dossier : lettres et chiffres
+// en minuscules, sans ponctuation ni espaces
+pub fn normalize(text: &str) -> String {
+    text.chars()
+        .filter(|character| character.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
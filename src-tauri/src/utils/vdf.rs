@@ -7,13 +7,114 @@ use std::path::{Path, PathBuf};
 #[derive(Debug)]
 pub struct LibraryFolder {
     pub path: PathBuf,
-    pub label: String,
     pub mounted: bool,
 }
 
+// Arbre KeyValues générique (localconfig.vdf, screenshots.vdf, appworkshop_*.acf...)
+#[derive(Debug, Clone)]
+pub enum VdfNode {
+    Value(String),
+    Object(Vec<(String, VdfNode)>),
+}
+
+impl VdfNode {
+    // Steam n'est pas constant sur la casse des clés ("apps" / "Apps")
+    pub fn get(&self, key: &str) -> Option<&VdfNode> {
+        match self {
+            VdfNode::Object(entries) => entries
+                .iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(key))
+                .map(|(_, node)| node),
+            VdfNode::Value(_) => None,
+        }
+    }
+
+    pub fn get_path(&self, path: &[&str]) -> Option<&VdfNode> {
+        path.iter().try_fold(self, |node, key| node.get(key))
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            VdfNode::Value(value) => Some(value),
+            VdfNode::Object(_) => None,
+        }
+    }
+
+    pub fn value(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(VdfNode::as_str)
+    }
+
+    pub fn entries(&self) -> &[(String, VdfNode)] {
+        match self {
+            VdfNode::Object(entries) => entries,
+            VdfNode::Value(_) => &[],
+        }
+    }
+}
+
 pub struct VdfParser;
 
 impl VdfParser {
+    // Lit un fichier KeyValues texte ; la racine est un objet contenant la clé de tête
+    pub fn parse_file(path: &Path) -> Result<VdfNode, AppError> {
+        let content = fs::read_to_string(path).map_err(|e| AppError {
+            message: format!("Failed to read {}: {}", path.display(), e),
+        })?;
+        Self::parse(&content)
+    }
+
+    pub fn parse(content: &str) -> Result<VdfNode, AppError> {
+        // stack : objets ouverts ; pending_key : clé de chaque objet ouvert, plus la clé
+        // en attente de sa valeur au niveau courant s'il y en a une
+        let mut stack: Vec<Vec<(String, VdfNode)>> = vec![Vec::new()];
+        let mut pending_key: Vec<String> = Vec::new();
+
+        for token in tokenize(content) {
+            let key_waiting = pending_key.len() == stack.len();
+            match token {
+                VdfToken::Open => {
+                    if !key_waiting {
+                        return Err(AppError {
+                            message: "Invalid VDF: object without a key".to_string(),
+                        });
+                    }
+                    stack.push(Vec::new());
+                }
+                VdfToken::Close => {
+                    if key_waiting || stack.len() < 2 {
+                        return Err(AppError {
+                            message: "Invalid VDF: unbalanced braces".to_string(),
+                        });
+                    }
+                    let entries = stack.pop().unwrap_or_default();
+                    let key = pending_key.pop().unwrap_or_default();
+                    if let Some(parent) = stack.last_mut() {
+                        parent.push((key, VdfNode::Object(entries)));
+                    }
+                }
+                VdfToken::Text(text) => {
+                    // Une clé attend sa valeur : la paire est complète
+                    if key_waiting {
+                        let key = pending_key.pop().unwrap_or_default();
+                        if let Some(entries) = stack.last_mut() {
+                            entries.push((key, VdfNode::Value(text)));
+                        }
+                    } else {
+                        pending_key.push(text);
+                    }
+                }
+            }
+        }
+
+        match (stack.pop(), stack.is_empty() && pending_key.is_empty()) {
+            (Some(root), true) => Ok(VdfNode::Object(root)),
+            _ => Err(AppError {
+                message: "Invalid VDF: unclosed object".to_string(),
+            }),
+        }
+    }
+
+
     pub fn parse_library_folders(steam_path: &Path) -> Result<Vec<LibraryFolder>, AppError> {
         let vdf_path = steam_path.join("steamapps/libraryfolders.vdf");
         let content = fs::read_to_string(&vdf_path).map_err(|e| AppError {
@@ -23,7 +124,6 @@ impl VdfParser {
         lazy_static! {
             // Mise à jour des regex pour mieux correspondre au format VDF
             static ref FOLDER_REGEX: Regex = Regex::new(r#""path"\s*"([^"]+)""#).unwrap();
-            static ref LABEL_REGEX: Regex = Regex::new(r#""label"\s*"([^"]+)""#).unwrap();
         }
 
         let mut libraries = Vec::new();
@@ -31,7 +131,6 @@ impl VdfParser {
         // Ajouter le dossier Steam par défaut
         libraries.push(LibraryFolder {
             path: steam_path.join("steamapps"),
-            label: "Installation Steam".to_string(),
             mounted: true,
         });
 
@@ -45,7 +144,6 @@ impl VdfParser {
                 if library_path.exists() {
                     libraries.push(LibraryFolder {
                         path: library_path,
-                        label: get_library_label(&path_str),
                         mounted: true,
                     });
                 }
@@ -56,10 +154,67 @@ impl VdfParser {
     }
 }
 
-fn get_library_label(path: &str) -> String {
-    Path::new(path)
-        .file_name()
-        .and_then(|name| name.to_str())
-        .map(|name| name.to_string())
-        .unwrap_or_else(|| "Bibliothèque Steam".to_string())
+enum VdfToken {
+    Text(String),
+    Open,
+    Close,
+}
+
+fn tokenize(content: &str) -> Vec<VdfToken> {
+    let mut tokens = Vec::new();
+    let mut chars = content.chars().peekable();
+
+    while let Some(character) = chars.next() {
+        match character {
+            '{' => tokens.push(VdfToken::Open),
+            '}' => tokens.push(VdfToken::Close),
+            '"' => {
+                let mut text = String::new();
+                while let Some(character) = chars.next() {
+                    match character {
+                        '"' => break,
+                        '\\' => match chars.next() {
+                            Some('n') => text.push('\n'),
+                            Some('t') => text.push('\t'),
+                            Some(escaped) => text.push(escaped),
+                            None => break,
+                        },
+                        _ => text.push(character),
+                    }
+                }
+                tokens.push(VdfToken::Text(text));
+            }
+            // Commentaires
+            '/' if chars.peek() == Some(&'/') => {
+                for character in chars.by_ref() {
+                    if character == '\n' {
+                        break;
+                    }
+                }
+            }
+            // Conditions de plateforme ([$WIN32]) : ignorées
+            '[' => {
+                for character in chars.by_ref() {
+                    if character == ']' {
+                        break;
+                    }
+                }
+            }
+            character if character.is_whitespace() => {}
+            // Texte sans guillemets
+            _ => {
+                let mut text = character.to_string();
+                while let Some(&next) = chars.peek() {
+                    if next.is_whitespace() || matches!(next, '{' | '}' | '"') {
+                        break;
+                    }
+                    text.push(next);
+                    chars.next();
+                }
+                tokens.push(VdfToken::Text(text));
+            }
+        }
+    }
+
+    tokens
 }